@@ -1,11 +1,36 @@
+use czkawka_core::common_content_type::ContentType;
+use czkawka_core::common_date_filter::DateFilter;
 use czkawka_core::duplicate::{CheckingMethod, DeleteMethod};
+#[cfg(feature = "same-music")]
 use czkawka_core::same_music::MusicSimilarity;
-use czkawka_core::similar_images::Similarity;
+#[cfg(feature = "similar-images")]
+use czkawka_core::similar_images::{self, HashAlgorithm, Similarity};
 use std::path::PathBuf;
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 #[structopt(name = "czkawka", help_message = HELP_MESSAGE, template = HELP_TEMPLATE)]
+pub struct Arguments {
+    #[structopt(
+        long,
+        value_name = "name",
+        help = "Uses settings from saved profile",
+        long_help = "Uses settings from profile saved in GUI or in profiles file, values given in command line replace values from profile"
+    )]
+    pub profile: Option<String>,
+    #[structopt(
+        short,
+        long,
+        parse(from_occurrences),
+        help = "Prints duration of scan phases, use -vv to print more",
+        long_help = "Prints to stderr duration and number of processed entries of each scan phase, use -vv to print also debug messages"
+    )]
+    pub verbose: u8,
+    #[structopt(subcommand)]
+    pub command: Commands,
+}
+
+#[derive(Debug, StructOpt)]
 pub enum Commands {
     #[structopt(name = "dup", about = "Finds duplicate files", help_message = HELP_MESSAGE, after_help = "EXAMPLE:\n    czkawka dup -d /home/rafal -e /home/rafal/Obrazy  -m 25 -x 7z rar IMAGE -s hashmb -f results.txt -D aeo")]
     Duplicates {
@@ -15,18 +40,30 @@ pub enum Commands {
         excluded_directories: ExcludedDirectories,
         #[structopt(flatten)]
         excluded_items: ExcludedItems,
-        #[structopt(short, long, parse(try_from_str = parse_minimal_file_size), default_value = "1024", help = "Minimum size in bytes", long_help = "Minimum size of checked files in bytes, assigning bigger value may speed up searching")]
-        minimal_file_size: u64,
+        #[structopt(short, long, parse(try_from_str = parse_minimal_file_size), help = "Minimum size in bytes [default: 1024]", long_help = "Minimum size of checked files in bytes, assigning bigger value may speed up searching")]
+        minimal_file_size: Option<u64>,
         #[structopt(flatten)]
         allowed_extensions: AllowedExtensions,
-        #[structopt(short, long, default_value = "HASH", parse(try_from_str = parse_checking_method), help = "Search method (NAME, SIZE, HASH, HASHMB)", long_help = "Methods to search files.\nNAME - Fast but but rarely usable,\nSIZE - Fast but not accurate, checking by the file's size,\nHASHMB - More accurate but slower, checking by the hash of the file's first mebibyte or\nHASH - The slowest method, checking by the hash of the entire file")]
-        search_method: CheckingMethod,
-        #[structopt(short = "D", long, default_value = "NONE", parse(try_from_str = parse_delete_method), help = "Delete method (AEN, AEO, ON, OO)", long_help = "Methods to delete the files.\nAEN - All files except the newest,\nAEO - All files except the oldest,\nON - Only 1 file, the newest,\nOO - Only 1 file, the oldest\nNONE - not delete files")]
-        delete_method: DeleteMethod,
+        #[structopt(short, long, parse(try_from_str = parse_checking_method), help = "Search method (NAME, SIZE, HASH, HASHMB) [default: HASH]", long_help = "Methods to search files.\nNAME - Fast but but rarely usable,\nSIZE - Fast but not accurate, checking by the file's size,\nHASHMB - More accurate but slower, checking by the hash of the file's first mebibyte or\nHASH - The slowest method, checking by the hash of the entire file")]
+        search_method: Option<CheckingMethod>,
+        #[structopt(short = "D", long, parse(try_from_str = parse_delete_method), help = "Delete method (AEN, AEO, ON, OO) [default: NONE]", long_help = "Methods to delete the files.\nAEN - All files except the newest,\nAEO - All files except the oldest,\nON - Only 1 file, the newest,\nOO - Only 1 file, the oldest\nNONE - not delete files")]
+        delete_method: Option<DeleteMethod>,
         #[structopt(flatten)]
         file_to_save: FileToSave,
         #[structopt(flatten)]
         not_recursive: NotRecursive,
+        #[structopt(flatten)]
+        use_ignore_files: UseIgnoreFiles,
+        #[structopt(flatten)]
+        modification_date: ModificationDate,
+        #[structopt(flatten)]
+        max_depth: MaxDepth,
+        #[structopt(flatten)]
+        content_types: ContentTypes,
+        #[structopt(flatten)]
+        file_list: FileList,
+        #[structopt(flatten)]
+        resource_limits: ResourceLimits,
     },
     #[structopt(name = "empty-folders", about = "Finds empty folders", help_message = HELP_MESSAGE, after_help = "EXAMPLE:\n    czkawka empty-folders -d /home/rafal/rr /home/gateway -f results.txt")]
     EmptyFolders {
@@ -40,6 +77,12 @@ pub enum Commands {
         delete_folders: bool,
         #[structopt(flatten)]
         file_to_save: FileToSave,
+        #[structopt(flatten)]
+        use_ignore_files: UseIgnoreFiles,
+        #[structopt(flatten)]
+        modification_date: ModificationDate,
+        #[structopt(flatten)]
+        max_depth: MaxDepth,
     },
     #[structopt(name = "big", about = "Finds big files", help_message = HELP_MESSAGE, after_help = "EXAMPLE:\n    czkawka big -d /home/rafal/ /home/piszczal -e /home/rafal/Roman -n 25 -x VIDEO -f results.txt")]
     BiggestFiles {
@@ -51,14 +94,24 @@ pub enum Commands {
         excluded_items: ExcludedItems,
         #[structopt(flatten)]
         allowed_extensions: AllowedExtensions,
-        #[structopt(short, long, default_value = "50", help = "Number of files to be shown")]
-        number_of_files: usize,
+        #[structopt(short, long, help = "Number of files to be shown [default: 50]")]
+        number_of_files: Option<usize>,
         #[structopt(short = "D", long, help = "Delete found files")]
         delete_files: bool,
         #[structopt(flatten)]
         file_to_save: FileToSave,
         #[structopt(flatten)]
         not_recursive: NotRecursive,
+        #[structopt(flatten)]
+        use_ignore_files: UseIgnoreFiles,
+        #[structopt(flatten)]
+        modification_date: ModificationDate,
+        #[structopt(flatten)]
+        max_depth: MaxDepth,
+        #[structopt(flatten)]
+        content_types: ContentTypes,
+        #[structopt(flatten)]
+        file_list: FileList,
     },
     #[structopt(name = "empty-files", about = "Finds emtpy files", help_message = HELP_MESSAGE, after_help = "EXAMPLE:\n    czkawka empty-files -d /home/rafal /home/szczekacz -e /home/rafal/Pulpit -R -f results.txt")]
     EmptyFiles {
@@ -76,6 +129,16 @@ pub enum Commands {
         file_to_save: FileToSave,
         #[structopt(flatten)]
         not_recursive: NotRecursive,
+        #[structopt(flatten)]
+        use_ignore_files: UseIgnoreFiles,
+        #[structopt(flatten)]
+        modification_date: ModificationDate,
+        #[structopt(flatten)]
+        max_depth: MaxDepth,
+        #[structopt(flatten)]
+        content_types: ContentTypes,
+        #[structopt(flatten)]
+        file_list: FileList,
     },
     #[structopt(name = "temp", about = "Finds temporary files", help_message = HELP_MESSAGE, after_help = "EXAMPLE:\n    czkawka temp -d /home/rafal/ -E */.git */tmp* *Pulpit -f results.txt -D")]
     Temporary {
@@ -85,29 +148,116 @@ pub enum Commands {
         excluded_directories: ExcludedDirectories,
         #[structopt(flatten)]
         excluded_items: ExcludedItems,
+        #[structopt(flatten)]
+        allowed_extensions: AllowedExtensions,
         #[structopt(short = "D", long, help = "Delete found files")]
         delete_files: bool,
         #[structopt(flatten)]
         file_to_save: FileToSave,
         #[structopt(flatten)]
         not_recursive: NotRecursive,
+        #[structopt(flatten)]
+        use_ignore_files: UseIgnoreFiles,
+        #[structopt(flatten)]
+        modification_date: ModificationDate,
+        #[structopt(flatten)]
+        max_depth: MaxDepth,
+        #[structopt(flatten)]
+        content_types: ContentTypes,
+        #[structopt(flatten)]
+        file_list: FileList,
     },
+    #[cfg(feature = "similar-images")]
     #[structopt(name = "image", about = "Finds similar images", help_message = HELP_MESSAGE, after_help = "EXAMPLE:\n    czkawka image -d /home/rafal/ -E */.git */tmp* *Pulpit -f results.txt")]
     SimilarImages {
         #[structopt(flatten)]
         directories: Directories,
         #[structopt(flatten)]
         excluded_directories: ExcludedDirectories,
-        #[structopt(short, long, parse(try_from_str = parse_minimal_file_size), default_value = "16384", help = "Minimum size in bytes", long_help = "Minimum size of checked files in bytes, assigning bigger value may speed up searching")]
-        minimal_file_size: u64,
-        #[structopt(short, long, default_value = "High", parse(try_from_str = parse_similar_images_similarity), help = "Similairty level (VerySmall, Small, Medium, High, Very High)", long_help = "Methods to choose similarity level of images which will be considered as duplicated.")]
-        similarity: Similarity,
+        #[structopt(short, long, parse(try_from_str = parse_minimal_file_size), help = "Minimum size in bytes [default: 16384]", long_help = "Minimum size of checked files in bytes, assigning bigger value may speed up searching")]
+        minimal_file_size: Option<u64>,
+        #[structopt(short, long, parse(try_from_str = parse_similar_images_similarity), help = "Similairty level (VerySmall, Small, Medium, High, Very High) [default: High]", long_help = "Methods to choose similarity level of images which will be considered as duplicated.")]
+        similarity: Option<Similarity>,
+        #[structopt(
+            long,
+            conflicts_with = "similarity",
+            help = "Maximum distance between hashes of similar images",
+            long_help = "Maximum hamming distance between hashes of images which will be considered as similar, allows finer control than similarity level, e.g. 6 or 8 for heavily recompressed images"
+        )]
+        max_distance: Option<u64>,
+        #[structopt(
+            long,
+            parse(try_from_str = parse_hash_algorithm),
+            help = "Hash algorithm (Mean, Gradient, DoubleGradient, Blockhash) [default: Gradient]",
+            long_help = "Algorithm used to compute hash of image, Mean is the simplest, Blockhash the fastest and DoubleGradient compares rows and columns of image"
+        )]
+        hash_alg: Option<HashAlgorithm>,
+        #[structopt(
+            long,
+            parse(try_from_str = parse_hash_size),
+            help = "Hash size (8, 16, 32) [default: 8]",
+            long_help = "Length of side of hash, bigger hash finds similar images more accurately but is slower, similarity levels are scaled to hash size"
+        )]
+        hash_size: Option<u8>,
+        #[structopt(long, help = "Uses DCT preprocessing", long_help = "Computes hash from Discrete Cosine Transform of image, which is more resistant to edits of image, but is slower")]
+        dct: bool,
+        #[structopt(long, conflicts_with = "dct", help = "Doesn't use DCT preprocessing, also when profile enables it")]
+        no_dct: bool,
+        #[structopt(long, help = "Doesn't use cache of image hashes", long_help = "Hashes all images again instead of reusing hashes of unchanged images saved in previous scans")]
+        no_cache: bool,
+        #[structopt(long, conflicts_with = "no-cache", help = "Uses cache of image hashes, also when profile disables it")]
+        cache: bool,
+        #[structopt(
+            long,
+            help = "Finds also rotated and mirrored images",
+            long_help = "Compares images also after rotating them by 90, 180 and 270 degrees and mirroring, which finds copies rotated by phone or editor, but hashing is several times slower"
+        )]
+        rotations: bool,
+        #[structopt(long, conflicts_with = "rotations", help = "Doesn't find rotated and mirrored images, also when profile enables it")]
+        no_rotations: bool,
+        #[structopt(
+            long,
+            help = "Additional image extension(s)",
+            long_help = "Extension(s) of images checked in addition to formats supported by decoder(e.g. jfif), format of such files is recognized by their content"
+        )]
+        add_image_extensions: Vec<String>,
+        #[structopt(long, help = "Skipped image extension(s)", long_help = "Extension(s) of supported image formats which are not checked, e.g. ico")]
+        skip_image_extensions: Vec<String>,
+        #[structopt(
+            short = "D",
+            long,
+            parse(try_from_str = parse_similar_images_delete_method),
+            help = "Delete method (AEB) [default: NONE]",
+            long_help = "Methods to delete the images.\nAEB - All images except the one with the best quality(the highest resolution, then the biggest file, then lossless format like PNG over JPEG),\nNONE - not delete images"
+        )]
+        delete_method: Option<similar_images::DeleteMethod>,
+        #[structopt(
+            long,
+            parse(from_os_str),
+            help = "Finds only images similar to given image(s)",
+            long_help = "Reference image(s), only scanned images similar to them are found instead of all groups of similar images, e.g. to find copies of logo or of scanned document. Reference images are never deleted"
+        )]
+        similar_to: Vec<PathBuf>,
         #[structopt(flatten)]
         excluded_items: ExcludedItems,
         #[structopt(flatten)]
+        allowed_extensions: AllowedExtensions,
+        #[structopt(flatten)]
         file_to_save: FileToSave,
         #[structopt(flatten)]
         not_recursive: NotRecursive,
+        #[structopt(flatten)]
+        use_ignore_files: UseIgnoreFiles,
+        #[structopt(flatten)]
+        modification_date: ModificationDate,
+        #[structopt(flatten)]
+        max_depth: MaxDepth,
+        #[structopt(flatten)]
+        content_types: ContentTypes,
+        #[structopt(flatten)]
+        file_list: FileList,
+        #[structopt(flatten)]
+        resource_limits: ResourceLimits,
     },
     #[structopt(name = "zeroed", about = "Finds zeroed files", help_message = HELP_MESSAGE, after_help = "EXAMPLE:\n    czkawka zeroed -d /home/rafal -e /home/rafal/Pulpit -f results.txt")]
     ZeroedFiles {
@@ -125,9 +275,22 @@ pub enum Commands {
         file_to_save: FileToSave,
         #[structopt(flatten)]
         not_recursive: NotRecursive,
-        #[structopt(short, long, parse(try_from_str = parse_minimal_file_size), default_value = "1024", help = "Minimum size in bytes", long_help = "Minimum size of checked files in bytes, assigning bigger value may speed up searching")]
-        minimal_file_size: u64,
+        #[structopt(flatten)]
+        use_ignore_files: UseIgnoreFiles,
+        #[structopt(flatten)]
+        modification_date: ModificationDate,
+        #[structopt(flatten)]
+        max_depth: MaxDepth,
+        #[structopt(flatten)]
+        content_types: ContentTypes,
+        #[structopt(flatten)]
+        file_list: FileList,
+        #[structopt(flatten)]
+        resource_limits: ResourceLimits,
+        #[structopt(short, long, parse(try_from_str = parse_minimal_file_size), help = "Minimum size in bytes [default: 1024]", long_help = "Minimum size of checked files in bytes, assigning bigger value may speed up searching")]
+        minimal_file_size: Option<u64>,
     },
+    #[cfg(feature = "same-music")]
     #[structopt(name = "music", about = "Finds same music by tags", help_message = HELP_MESSAGE, after_help = "EXAMPLE:\n    czkawka music -d /home/rafal -f results.txt")]
     SameMusic {
         #[structopt(flatten)]
@@ -136,34 +299,74 @@ pub enum Commands {
         excluded_directories: ExcludedDirectories,
         #[structopt(flatten)]
         excluded_items: ExcludedItems,
+        #[structopt(flatten)]
+        allowed_extensions: AllowedExtensions,
         // #[structopt(short = "D", long, help = "Delete found files")]
         // delete_files: bool, TODO
-        #[structopt(short = "z", long, default_value = "artist,title", parse(try_from_str = parse_music_duplicate_type), help = "Search method (title, artist, album_title, album_artist, year)", long_help = "Sets which rows must be equal to set this files as duplicates(may be mixed, but must be divided by commas).")]
-        music_similarity: MusicSimilarity,
+        #[structopt(short = "z", long, parse(try_from_str = parse_music_duplicate_type), help = "Search method (title, artist, album_title, album_artist, year) [default: artist,title]", long_help = "Sets which rows must be equal to set this files as duplicates(may be mixed, but must be divided by commas).")]
+        music_similarity: Option<MusicSimilarity>,
         #[structopt(flatten)]
         file_to_save: FileToSave,
         #[structopt(flatten)]
         not_recursive: NotRecursive,
-        #[structopt(short, long, parse(try_from_str = parse_minimal_file_size), default_value = "1024", help = "Minimum size in bytes", long_help = "Minimum size of checked files in bytes, assigning bigger value may speed up searching")]
-        minimal_file_size: u64,
+        #[structopt(flatten)]
+        use_ignore_files: UseIgnoreFiles,
+        #[structopt(flatten)]
+        modification_date: ModificationDate,
+        #[structopt(flatten)]
+        max_depth: MaxDepth,
+        #[structopt(flatten)]
+        content_types: ContentTypes,
+        #[structopt(flatten)]
+        file_list: FileList,
+        #[structopt(flatten)]
+        resource_limits: ResourceLimits,
+        #[structopt(short, long, parse(try_from_str = parse_minimal_file_size), help = "Minimum size in bytes [default: 1024]", long_help = "Minimum size of checked files in bytes, assigning bigger value may speed up searching")]
+        minimal_file_size: Option<u64>,
+    },
+    #[cfg(feature = "similar-images")]
+    #[structopt(name = "image-cache", about = "Maintains cache of image hashes", help_message = HELP_MESSAGE, after_help = "EXAMPLE:\n    czkawka image-cache --prune")]
+    ImageCache {
+        #[structopt(long, help = "Removes entries of missing files from cache")]
+        prune: bool,
+        #[structopt(long, conflicts_with = "prune", help = "Removes whole cache")]
+        clear: bool,
     },
 }
 
 #[derive(Debug, StructOpt)]
 pub struct Directories {
-    #[structopt(short, long, parse(from_os_str), required = true, help = "Directorie(s) to search", long_help = "List of directorie(s) which will be searched(absolute path)")]
+    #[structopt(
+        short,
+        long,
+        parse(from_os_str),
+        help = "Directorie(s) to search",
+        long_help = "List of directorie(s) which will be searched(relative paths, ~ and $VARIABLES are expanded). Required, unless they are set in profile or file list is used"
+    )]
     pub directories: Vec<PathBuf>,
 }
 
 #[derive(Debug, StructOpt)]
 pub struct ExcludedDirectories {
-    #[structopt(short, long, parse(from_os_str), help = "Excluded directorie(s)", long_help = "List of directorie(s) which will be excluded from search(absolute path)")]
+    #[structopt(
+        short,
+        long,
+        parse(from_os_str),
+        help = "Excluded directorie(s)",
+        long_help = "List of directorie(s) which will be excluded from search(relative paths, ~ and $VARIABLES are expanded)"
+    )]
     pub excluded_directories: Vec<PathBuf>,
 }
 
 #[derive(Debug, StructOpt)]
 pub struct ExcludedItems {
-    #[structopt(short = "E", long, parse(from_os_str), help = "Excluded item(s)", long_help = "List of excluded item(s) which contains * wildcard(may be slow, so use -e where possible)")]
+    #[structopt(
+        short = "E",
+        long,
+        parse(from_os_str),
+        help = "Excluded item(s)",
+        long_help = "Excluded item(s) with wildcards(*, ?, [abc], **/) or regex: prefixed expressions(may be slow, so use -e where possible)"
+    )]
     pub excluded_items: Vec<PathBuf>,
 }
 
@@ -173,15 +376,163 @@ pub struct AllowedExtensions {
         short = "x",
         long,
         help = "Allowed file extension(s)",
-        long_help = "List of checked files with provided extension(s). There are also helpful macros which allow to easy use a typical extensions like:\nIMAGE(\"jpg,kra,gif,png,bmp,tiff,webp,hdr,svg\"),\nTEXT(\"txt,doc,docx,odt,rtf\"),\nVIDEO(\"mp4,flv,mkv,webm,vob,ogv,gifv,avi,mov,wmv,mpg,m4v,m4p,mpeg,3gp\") or\nMUSIC(\"mp3,flac,ogg,tta,wma,webm\")\n "
+        long_help = "List of checked files with provided extension(s), matched case-insensitively and possibly containing dots like tar.gz. There are also helpful macros which allow to easy use a typical extensions like:\nIMAGE(\"jpg,kra,gif,png,bmp,tiff,webp,hdr,svg\"),\nTEXT(\"txt,doc,docx,odt,rtf\"),\nVIDEO(\"mp4,flv,mkv,webm,vob,ogv,gifv,avi,mov,wmv,mpg,m4v,m4p,mpeg,3gp\") or\nMUSIC(\"mp3,flac,ogg,tta,wma,webm\")\n "
     )]
     pub allowed_extensions: Vec<String>,
+    #[structopt(
+        short = "X",
+        long,
+        help = "Excluded file extension(s)",
+        long_help = "List of extension(s) of files which are never checked, even if they are allowed. Accepts the same macros and extension groups as allowed extensions"
+    )]
+    pub excluded_extensions: Vec<String>,
+    #[structopt(
+        long = "extension-group",
+        value_name = "NAME=ext1,ext2",
+        help = "Defines group of extensions",
+        long_help = "Defines named group of extensions(e.g. ARCHIVE=zip,rar,tar.gz), which may be used in allowed and excluded extensions like built-in macros. Group with the same name as built-in macro replaces it"
+    )]
+    pub extension_groups: Vec<String>,
 }
 
 #[derive(Debug, StructOpt)]
 pub struct NotRecursive {
     #[structopt(short = "R", long, help = "Prevents from recursive check of folders")]
     pub not_recursive: bool,
+    #[structopt(long, conflicts_with = "not-recursive", help = "Checks folders recursively, also when profile prevents it")]
+    pub recursive: bool,
+}
+
+impl NotRecursive {
+    pub fn recursive_search(&self) -> Option<bool> {
+        flag_value(self.recursive, self.not_recursive)
+    }
+}
+
+#[derive(Debug, StructOpt)]
+pub struct MaxDepth {
+    #[structopt(
+        long,
+        value_name = "depth",
+        help = "Maximum depth of checked subfolders",
+        long_help = "Maximum number of levels of subfolders which are checked, 0 means that only files placed directly in searched directories are checked. By default there is no limit"
+    )]
+    pub max_depth: Option<usize>,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct ResourceLimits {
+    #[structopt(
+        long,
+        value_name = "bytes per second",
+        help = "Maximum speed of reading files",
+        long_help = "Maximum number of bytes read per second by all threads together, 0 or no value means that there is no limit. When searching for same music only estimated size of tags is counted"
+    )]
+    pub io_limit: Option<u64>,
+    #[structopt(long, help = "Lowers priority of scan", long_help = "Scan runs with lowered CPU and I/O priority, so it slows down other programs less. Works only on Linux")]
+    pub low_priority: bool,
+    #[structopt(long, conflicts_with = "low-priority", help = "Doesn't lower priority of scan, also when profile lowers it")]
+    pub no_low_priority: bool,
+    #[structopt(
+        short = "T",
+        long,
+        value_name = "number",
+        help = "Number of used threads",
+        long_help = "Number of threads used to check files, 0 or no value means that number of logical CPUs is used"
+    )]
+    pub threads: Option<usize>,
+}
+
+impl ResourceLimits {
+    pub fn low_priority(&self) -> Option<bool> {
+        flag_value(self.low_priority, self.no_low_priority)
+    }
+}
+
+#[derive(Debug, StructOpt)]
+pub struct UseIgnoreFiles {
+    #[structopt(
+        short = "g",
+        long,
+        help = "Uses rules from ignore files",
+        long_help = "Skips files and folders matched by rules from .gitignore, .ignore and .czkawkaignore files found in searched folders"
+    )]
+    pub use_ignore_files: bool,
+    #[structopt(long, conflicts_with = "use-ignore-files", help = "Doesn't use rules from ignore files, also when profile enables them")]
+    pub no_ignore_files: bool,
+}
+
+impl UseIgnoreFiles {
+    pub fn use_ignore_files(&self) -> Option<bool> {
+        flag_value(self.use_ignore_files, self.no_ignore_files)
+    }
+}
+
+#[derive(Debug, StructOpt)]
+pub struct ModificationDate {
+    #[structopt(
+        long,
+        value_name = "time",
+        parse(try_from_str = parse_modification_date),
+        help = "Skips files modified before this time",
+        long_help = "Skips files modified before this time. Accepts date in local time zone like 2020-12-31, 2020-12-31 23:59 or 2020-12-31T23:59:59, or time counted back from now like 12h, 30d, 2w, 6m or 1y"
+    )]
+    pub modified_after: Option<String>,
+    #[structopt(
+        long,
+        value_name = "time",
+        parse(try_from_str = parse_modification_date),
+        help = "Skips files modified at or after this time",
+        long_help = "Skips files modified at or after this time. Accepts the same formats as --modified-after"
+    )]
+    pub modified_before: Option<String>,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct ContentTypes {
+    #[structopt(
+        short = "t",
+        long,
+        parse(try_from_str = parse_content_type),
+        help = "Allowed content type(s) (image, audio, video, archive, document)",
+        long_help = "List of checked types of files (image, audio, video, archive, document). By default type is recognized by extension of file, use --detect-by-content to recognize it by the first bytes of file"
+    )]
+    pub content_types: Vec<String>,
+    #[structopt(
+        short = "c",
+        long,
+        help = "Recognizes type of files by their content",
+        long_help = "Reads the first bytes of files to recognize their type, so also files with wrong or without extension are found by similar images, same music and content types filter. Slower than checking extensions"
+    )]
+    pub detect_by_content: bool,
+    #[structopt(long, conflicts_with = "detect-by-content", help = "Recognizes type of files by their extension, also when profile recognizes it by content")]
+    pub no_detect_by_content: bool,
+}
+
+impl ContentTypes {
+    pub fn detect_by_content(&self) -> Option<bool> {
+        flag_value(self.detect_by_content, self.no_detect_by_content)
+    }
+}
+
+#[derive(Debug, StructOpt)]
+pub struct FileList {
+    #[structopt(
+        short = "L",
+        long,
+        parse(from_os_str),
+        value_name = "file-name",
+        help = "Checks only files from list",
+        long_help = "Checks only files from list(use - to read it from standard input) instead of files found in searched directories. Every path must be placed in separate line. Excluded directories, excluded items and other filters are still used, but ignore files are not"
+    )]
+    pub file_list: Option<PathBuf>,
+    #[structopt(
+        short = "0",
+        long,
+        help = "Paths in file list are separated by NUL characters",
+        long_help = "Paths in file list are separated by NUL characters instead of new lines, like in output of find -print0 or locate -0"
+    )]
+    pub null_separated: bool,
 }
 
 #[derive(Debug, StructOpt)]
@@ -200,6 +551,17 @@ impl FileToSave {
     }
 }
 
+/// Value of flag which may be negated in command line, None when neither flag was given, so value from profile is used
+pub fn flag_value(enabled: bool, disabled: bool) -> Option<bool> {
+    if enabled {
+        Some(true)
+    } else if disabled {
+        Some(false)
+    } else {
+        None
+    }
+}
+
 fn parse_checking_method(src: &str) -> Result<CheckingMethod, &'static str> {
     match src.to_ascii_lowercase().as_str() {
         "name" => Ok(CheckingMethod::Name),
@@ -221,6 +583,7 @@ fn parse_delete_method(src: &str) -> Result<DeleteMethod, &'static str> {
     }
 }
 
+#[cfg(feature = "similar-images")]
 fn parse_similar_images_similarity(src: &str) -> Result<Similarity, &'static str> {
     match src.to_ascii_lowercase().replace('_', "").as_str() {
         "verysmall" => Ok(Similarity::VerySmall),
@@ -232,6 +595,36 @@ fn parse_similar_images_similarity(src: &str) -> Result<Similarity, &'static str
     }
 }
 
+#[cfg(feature = "similar-images")]
+fn parse_similar_images_delete_method(src: &str) -> Result<similar_images::DeleteMethod, &'static str> {
+    match src.to_ascii_lowercase().as_str() {
+        "none" => Ok(similar_images::DeleteMethod::None),
+        "aeb" => Ok(similar_images::DeleteMethod::AllExceptBestQuality),
+        _ => Err("Couldn't parse the delete method (allowed: AEB, NONE)"),
+    }
+}
+
+#[cfg(feature = "similar-images")]
+fn parse_hash_algorithm(src: &str) -> Result<HashAlgorithm, &'static str> {
+    match src.to_ascii_lowercase().replace(&['_', '-'][..], "").as_str() {
+        "mean" => Ok(HashAlgorithm::Mean),
+        "gradient" => Ok(HashAlgorithm::Gradient),
+        "doublegradient" => Ok(HashAlgorithm::DoubleGradient),
+        "blockhash" => Ok(HashAlgorithm::Blockhash),
+        _ => Err("Couldn't parse the hash algorithm (allowed: mean, gradient, doublegradient, blockhash)"),
+    }
+}
+
+#[cfg(feature = "similar-images")]
+fn parse_hash_size(src: &str) -> Result<u8, &'static str> {
+    match src {
+        "8" => Ok(8),
+        "16" => Ok(16),
+        "32" => Ok(32),
+        _ => Err("Couldn't parse the hash size (allowed: 8, 16, 32)"),
+    }
+}
+
 fn parse_minimal_file_size(src: &str) -> Result<u64, String> {
     match src.parse::<u64>() {
         Ok(minimal_file_size) => {
@@ -245,6 +638,17 @@ fn parse_minimal_file_size(src: &str) -> Result<u64, String> {
     }
 }
 
+fn parse_modification_date(src: &str) -> Result<String, String> {
+    DateFilter::parse_time(src)?;
+    Ok(src.to_string())
+}
+
+fn parse_content_type(src: &str) -> Result<String, String> {
+    ContentType::from_name(src)?;
+    Ok(src.to_string())
+}
+
+#[cfg(feature = "same-music")]
 fn parse_music_duplicate_type(src: &str) -> Result<MusicSimilarity, String> {
     if src.is_empty() {
         return Ok(MusicSimilarity::NONE);
@@ -288,6 +692,9 @@ USAGE:
 FLAGS:
 {flags}
 
+OPTIONS:
+{options}
+
 SUBCOMMANDS:
 {subcommands}
 
@@ -295,10 +702,19 @@ SUBCOMMANDS:
 
 EXAMPLES:
     {bin} dup -d /home/rafal -e /home/rafal/Obrazy  -m 25 -x 7z rar IMAGE -s hashmb -f results.txt -D aeo
+    {bin} --profile photos dup
+    {bin} -v dup -d /home/rafal
     {bin} empty-folders -d /home/rafal/rr /home/gateway -f results.txt
     {bin} big -d /home/rafal/ /home/piszczal -e /home/rafal/Roman -n 25 -x VIDEO -f results.txt
     {bin} empty-files -d /home/rafal /home/szczekacz -e /home/rafal/Pulpit -R -f results.txt
     {bin} temp -d /home/rafal/ -E */.git */tmp* *Pulpit -f results.txt -D
     {bin} image -d /home/rafal -e /home/rafal/Pulpit -f results.txt
+    {bin} image -d /home/rafal --hash-alg doublegradient --hash-size 16 --dct
+    {bin} image -d /home/rafal --max-distance 8
+    {bin} image -d /home/rafal/Phone --rotations
+    {bin} image -d /home/rafal --add-image-extensions jfif --skip-image-extensions ico,dds
+    {bin} image -d /home/rafal/Photos -D aeb
+    {bin} image -d /home/rafal --similar-to /home/rafal/logo.png
+    {bin} image-cache --prune
     {bin} zeroed -d /home/rafal -e /home/krzak -f results.txt"
     {bin} music -d /home/rafal -e /home/rafal/Pulpit -z "artist,year, ARTISTALBUM, ALBUM___tiTlE"  -f results.txt"#;
@@ -0,0 +1,31 @@
+use log::{LevelFilter, Log, Metadata, Record};
+
+/// Prints log messages of core to stderr, where also progress is printed
+struct StderrLogger;
+
+static LOGGER: StderrLogger = StderrLogger;
+
+impl Log for StderrLogger {
+    fn enabled(&self, _metadata: &Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &Record) {
+        // Carriage return moves message to beginning of line which may be used by progress bar
+        eprintln!("\r[{}] {}", record.level(), record.args());
+    }
+
+    fn flush(&self) {}
+}
+
+/// Verbosity 0 prints only warnings, 1 also information about each scan phase and 2 or more everything
+pub fn init_logger(verbosity: u8) {
+    let level = match verbosity {
+        0 => LevelFilter::Warn,
+        1 => LevelFilter::Info,
+        _ => LevelFilter::Debug,
+    };
+    if log::set_logger(&LOGGER).is_ok() {
+        log::set_max_level(level);
+    }
+}
@@ -1,17 +1,28 @@
 mod commands;
+mod logger;
+mod progress;
 
-use commands::Commands;
+#[cfg(feature = "similar-images")]
+use commands::flag_value;
+use commands::{Arguments, Commands};
+use progress::ProgressBar;
 
+use czkawka_core::common_profile::{Profile, Profiles};
+use czkawka_core::common_tool::Tool;
 #[allow(unused_imports)] // It is used in release for print_results().
 use czkawka_core::common_traits::*;
 
+#[cfg(feature = "same-music")]
+use czkawka_core::same_music::SameMusic;
+#[cfg(feature = "similar-images")]
+use czkawka_core::similar_images::SimilarImages;
+#[cfg(feature = "similar-images")]
+use czkawka_core::similar_images_cache::ImageHashCache;
 use czkawka_core::{
     big_file::{self, BigFile},
     duplicate::DuplicateFinder,
     empty_files::{self, EmptyFiles},
     empty_folder::EmptyFolder,
-    same_music::SameMusic,
-    similar_images::SimilarImages,
     temporary::{self, Temporary},
     zeroed::{self, ZeroedFiles},
 };
@@ -23,13 +34,60 @@ fn path_list_to_str(path_list: Vec<PathBuf>) -> String {
     path_list.join(",")
 }
 
+/// Values given in command line replace values from profile
+fn list_or_profile(from_command_line: Vec<String>, from_profile: &[String]) -> Vec<String> {
+    if from_command_line.is_empty() {
+        from_profile.to_vec()
+    } else {
+        from_command_line
+    }
+}
+
+fn paths_or_profile(path_list: Vec<PathBuf>, from_profile: &[String]) -> String {
+    let paths = path_list_to_str(path_list);
+    if paths.is_empty() {
+        from_profile.join(",")
+    } else {
+        paths
+    }
+}
+
+/// Directories may be set also in profile, so it can't be checked when parsing arguments
+fn get_included_directories(path_list: Vec<PathBuf>, profile: &Profile, file_list: Option<&PathBuf>) -> String {
+    let included_directories = paths_or_profile(path_list, &profile.included_directories);
+    if included_directories.is_empty() && file_list.is_none() {
+        eprintln!("Directories to search must be given with --directories, in profile or by --file-list");
+        process::exit(1);
+    }
+    included_directories
+}
+
+/// Without name of profile, default settings are used
+fn load_profile(name: Option<String>) -> Profile {
+    let name = match name {
+        Some(name) => name,
+        None => return Profile::default(),
+    };
+    match Profiles::load().and_then(|profiles| profiles.get_profile(&name).cloned()) {
+        Ok(profile) => profile,
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+    }
+}
+
 fn main() {
-    let command = Commands::from_args();
+    let arguments = Arguments::from_args();
+
+    logger::init_logger(arguments.verbose);
 
     #[cfg(debug_assertions)]
-    println!("{:?}", command);
+    println!("{:?}", arguments);
+
+    let profile = load_profile(arguments.profile);
 
-    match command {
+    match arguments.command {
         Commands::Duplicates {
             directories,
             excluded_directories,
@@ -40,19 +98,45 @@ fn main() {
             delete_method,
             file_to_save,
             not_recursive,
+            use_ignore_files,
+            modification_date,
+            max_depth,
+            content_types,
+            resource_limits,
+            file_list,
         } => {
             let mut df = DuplicateFinder::new();
 
-            df.set_included_directory(path_list_to_str(directories.directories));
-            df.set_excluded_directory(path_list_to_str(excluded_directories.excluded_directories));
-            df.set_excluded_items(path_list_to_str(excluded_items.excluded_items));
-            df.set_minimal_file_size(minimal_file_size);
-            df.set_allowed_extensions(allowed_extensions.allowed_extensions.join(","));
-            df.set_check_method(search_method);
-            df.set_delete_method(delete_method);
-            df.set_recursive_search(!not_recursive.not_recursive);
+            let included_directories = get_included_directories(directories.directories, &profile, file_list.file_list.as_ref());
+            if !included_directories.is_empty() {
+                df.set_included_directory(included_directories);
+            }
+            df.set_excluded_directory(paths_or_profile(excluded_directories.excluded_directories, &profile.excluded_directories));
+            df.set_excluded_items(paths_or_profile(excluded_items.excluded_items, &profile.excluded_items));
+            df.set_minimal_file_size(minimal_file_size.unwrap_or(profile.tools.duplicate_minimal_file_size));
+            df.set_allowed_extensions(list_or_profile(allowed_extensions.allowed_extensions, &profile.allowed_extensions).join(","));
+            df.set_excluded_extensions(list_or_profile(allowed_extensions.excluded_extensions, &profile.excluded_extensions).join(","));
+            df.set_extension_groups(list_or_profile(allowed_extensions.extension_groups, &profile.extension_groups).join(";"));
+            df.set_check_method(search_method.unwrap_or_else(|| profile.tools.duplicate_check_method.clone()));
+            df.set_delete_method(delete_method.unwrap_or_else(|| profile.delete_policy.duplicate_delete_method.clone()));
+            df.set_recursive_search(not_recursive.recursive_search().unwrap_or(profile.recursive_search));
+            df.set_use_ignore_files(use_ignore_files.use_ignore_files().unwrap_or(profile.use_ignore_files));
+            df.set_modified_after(modification_date.modified_after.unwrap_or_else(|| profile.modified_after.clone()));
+            df.set_modified_before(modification_date.modified_before.unwrap_or_else(|| profile.modified_before.clone()));
+            df.set_max_depth(max_depth.max_depth.or(profile.max_depth));
+            df.set_detect_by_content(content_types.detect_by_content().unwrap_or(profile.detect_by_content));
+            df.set_content_types(list_or_profile(content_types.content_types, &profile.content_types).join(","));
+            df.set_io_limit(resource_limits.io_limit.or(profile.io_limit));
+            df.set_low_priority(resource_limits.low_priority().unwrap_or(profile.low_priority));
+            df.set_threads(resource_limits.threads.or(profile.io_threads));
+            if !df.set_file_list(file_list.file_list.map(|e| e.to_string_lossy().to_string()).unwrap_or_default(), file_list.null_separated) {
+                df.get_text_messages().print_messages();
+                process::exit(1);
+            }
 
-            df.find_duplicates(None, None);
+            let progress_bar = ProgressBar::new(progress::duplicate_progress);
+            df.find_duplicates(None, Some(progress_bar.sender()));
+            progress_bar.finish();
 
             if let Some(file_name) = file_to_save.file_name() {
                 if !df.save_results_to_file(file_name) {
@@ -71,15 +155,24 @@ fn main() {
             file_to_save,
             excluded_directories,
             excluded_items,
+            use_ignore_files,
+            modification_date,
+            max_depth,
         } => {
             let mut ef = EmptyFolder::new();
 
-            ef.set_included_directory(path_list_to_str(directories.directories));
-            ef.set_excluded_directory(path_list_to_str(excluded_directories.excluded_directories));
-            ef.set_excluded_items(path_list_to_str(excluded_items.excluded_items));
+            ef.set_included_directory(get_included_directories(directories.directories, &profile, None));
+            ef.set_excluded_directory(paths_or_profile(excluded_directories.excluded_directories, &profile.excluded_directories));
+            ef.set_excluded_items(paths_or_profile(excluded_items.excluded_items, &profile.excluded_items));
             ef.set_delete_folder(delete_folders);
+            ef.set_use_ignore_files(use_ignore_files.use_ignore_files().unwrap_or(profile.use_ignore_files));
+            ef.set_modified_after(modification_date.modified_after.unwrap_or_else(|| profile.modified_after.clone()));
+            ef.set_modified_before(modification_date.modified_before.unwrap_or_else(|| profile.modified_before.clone()));
+            ef.set_max_depth(max_depth.max_depth.or(profile.max_depth));
 
-            ef.find_empty_folders(None, None);
+            let progress_bar = ProgressBar::new(progress::empty_folder_progress);
+            ef.find_empty_folders(None, Some(progress_bar.sender()));
+            progress_bar.finish();
 
             if let Some(file_name) = file_to_save.file_name() {
                 if !ef.save_results_to_file(file_name) {
@@ -100,21 +193,43 @@ fn main() {
             number_of_files,
             file_to_save,
             not_recursive,
+            use_ignore_files,
+            modification_date,
+            max_depth,
+            content_types,
+            file_list,
             delete_files,
         } => {
             let mut bf = BigFile::new();
 
-            bf.set_included_directory(path_list_to_str(directories.directories));
-            bf.set_excluded_directory(path_list_to_str(excluded_directories.excluded_directories));
-            bf.set_excluded_items(path_list_to_str(excluded_items.excluded_items));
-            bf.set_allowed_extensions(allowed_extensions.allowed_extensions.join(","));
-            bf.set_number_of_files_to_check(number_of_files);
-            bf.set_recursive_search(!not_recursive.not_recursive);
+            let included_directories = get_included_directories(directories.directories, &profile, file_list.file_list.as_ref());
+            if !included_directories.is_empty() {
+                bf.set_included_directory(included_directories);
+            }
+            bf.set_excluded_directory(paths_or_profile(excluded_directories.excluded_directories, &profile.excluded_directories));
+            bf.set_excluded_items(paths_or_profile(excluded_items.excluded_items, &profile.excluded_items));
+            bf.set_allowed_extensions(list_or_profile(allowed_extensions.allowed_extensions, &profile.allowed_extensions).join(","));
+            bf.set_excluded_extensions(list_or_profile(allowed_extensions.excluded_extensions, &profile.excluded_extensions).join(","));
+            bf.set_extension_groups(list_or_profile(allowed_extensions.extension_groups, &profile.extension_groups).join(";"));
+            bf.set_number_of_files_to_check(number_of_files.unwrap_or(profile.tools.big_files_number));
+            bf.set_recursive_search(not_recursive.recursive_search().unwrap_or(profile.recursive_search));
+            bf.set_use_ignore_files(use_ignore_files.use_ignore_files().unwrap_or(profile.use_ignore_files));
+            bf.set_modified_after(modification_date.modified_after.unwrap_or_else(|| profile.modified_after.clone()));
+            bf.set_modified_before(modification_date.modified_before.unwrap_or_else(|| profile.modified_before.clone()));
+            bf.set_max_depth(max_depth.max_depth.or(profile.max_depth));
+            bf.set_detect_by_content(content_types.detect_by_content().unwrap_or(profile.detect_by_content));
+            bf.set_content_types(list_or_profile(content_types.content_types, &profile.content_types).join(","));
+            if !bf.set_file_list(file_list.file_list.map(|e| e.to_string_lossy().to_string()).unwrap_or_default(), file_list.null_separated) {
+                bf.get_text_messages().print_messages();
+                process::exit(1);
+            }
             if delete_files {
                 bf.set_delete_method(big_file::DeleteMethod::Delete);
             }
 
-            bf.find_big_files(None, None);
+            let progress_bar = ProgressBar::new(progress::big_file_progress);
+            bf.find_big_files(None, Some(progress_bar.sender()));
+            progress_bar.finish();
 
             if let Some(file_name) = file_to_save.file_name() {
                 if !bf.save_results_to_file(file_name) {
@@ -135,20 +250,42 @@ fn main() {
             delete_files,
             file_to_save,
             not_recursive,
+            use_ignore_files,
+            modification_date,
+            max_depth,
+            content_types,
+            file_list,
         } => {
             let mut ef = EmptyFiles::new();
 
-            ef.set_included_directory(path_list_to_str(directories.directories));
-            ef.set_excluded_directory(path_list_to_str(excluded_directories.excluded_directories));
-            ef.set_excluded_items(path_list_to_str(excluded_items.excluded_items));
-            ef.set_allowed_extensions(allowed_extensions.allowed_extensions.join(","));
-            ef.set_recursive_search(!not_recursive.not_recursive);
+            let included_directories = get_included_directories(directories.directories, &profile, file_list.file_list.as_ref());
+            if !included_directories.is_empty() {
+                ef.set_included_directory(included_directories);
+            }
+            ef.set_excluded_directory(paths_or_profile(excluded_directories.excluded_directories, &profile.excluded_directories));
+            ef.set_excluded_items(paths_or_profile(excluded_items.excluded_items, &profile.excluded_items));
+            ef.set_allowed_extensions(list_or_profile(allowed_extensions.allowed_extensions, &profile.allowed_extensions).join(","));
+            ef.set_excluded_extensions(list_or_profile(allowed_extensions.excluded_extensions, &profile.excluded_extensions).join(","));
+            ef.set_extension_groups(list_or_profile(allowed_extensions.extension_groups, &profile.extension_groups).join(";"));
+            ef.set_recursive_search(not_recursive.recursive_search().unwrap_or(profile.recursive_search));
+            ef.set_use_ignore_files(use_ignore_files.use_ignore_files().unwrap_or(profile.use_ignore_files));
+            ef.set_modified_after(modification_date.modified_after.unwrap_or_else(|| profile.modified_after.clone()));
+            ef.set_modified_before(modification_date.modified_before.unwrap_or_else(|| profile.modified_before.clone()));
+            ef.set_max_depth(max_depth.max_depth.or(profile.max_depth));
+            ef.set_detect_by_content(content_types.detect_by_content().unwrap_or(profile.detect_by_content));
+            ef.set_content_types(list_or_profile(content_types.content_types, &profile.content_types).join(","));
+            if !ef.set_file_list(file_list.file_list.map(|e| e.to_string_lossy().to_string()).unwrap_or_default(), file_list.null_separated) {
+                ef.get_text_messages().print_messages();
+                process::exit(1);
+            }
 
             if delete_files {
                 ef.set_delete_method(empty_files::DeleteMethod::Delete);
             }
 
-            ef.find_empty_files(None, None);
+            let progress_bar = ProgressBar::new(progress::empty_files_progress);
+            ef.find_empty_files(None, Some(progress_bar.sender()));
+            progress_bar.finish();
 
             if let Some(file_name) = file_to_save.file_name() {
                 if !ef.save_results_to_file(file_name) {
@@ -165,22 +302,46 @@ fn main() {
             directories,
             excluded_directories,
             excluded_items,
+            allowed_extensions,
             delete_files,
             file_to_save,
             not_recursive,
+            use_ignore_files,
+            modification_date,
+            max_depth,
+            content_types,
+            file_list,
         } => {
             let mut tf = Temporary::new();
 
-            tf.set_included_directory(path_list_to_str(directories.directories));
-            tf.set_excluded_directory(path_list_to_str(excluded_directories.excluded_directories));
-            tf.set_excluded_items(path_list_to_str(excluded_items.excluded_items));
-            tf.set_recursive_search(!not_recursive.not_recursive);
+            let included_directories = get_included_directories(directories.directories, &profile, file_list.file_list.as_ref());
+            if !included_directories.is_empty() {
+                tf.set_included_directory(included_directories);
+            }
+            tf.set_excluded_directory(paths_or_profile(excluded_directories.excluded_directories, &profile.excluded_directories));
+            tf.set_excluded_items(paths_or_profile(excluded_items.excluded_items, &profile.excluded_items));
+            tf.set_allowed_extensions(list_or_profile(allowed_extensions.allowed_extensions, &profile.allowed_extensions).join(","));
+            tf.set_excluded_extensions(list_or_profile(allowed_extensions.excluded_extensions, &profile.excluded_extensions).join(","));
+            tf.set_extension_groups(list_or_profile(allowed_extensions.extension_groups, &profile.extension_groups).join(";"));
+            tf.set_recursive_search(not_recursive.recursive_search().unwrap_or(profile.recursive_search));
+            tf.set_use_ignore_files(use_ignore_files.use_ignore_files().unwrap_or(profile.use_ignore_files));
+            tf.set_modified_after(modification_date.modified_after.unwrap_or_else(|| profile.modified_after.clone()));
+            tf.set_modified_before(modification_date.modified_before.unwrap_or_else(|| profile.modified_before.clone()));
+            tf.set_max_depth(max_depth.max_depth.or(profile.max_depth));
+            tf.set_detect_by_content(content_types.detect_by_content().unwrap_or(profile.detect_by_content));
+            tf.set_content_types(list_or_profile(content_types.content_types, &profile.content_types).join(","));
+            if !tf.set_file_list(file_list.file_list.map(|e| e.to_string_lossy().to_string()).unwrap_or_default(), file_list.null_separated) {
+                tf.get_text_messages().print_messages();
+                process::exit(1);
+            }
 
             if delete_files {
                 tf.set_delete_method(temporary::DeleteMethod::Delete);
             }
 
-            tf.find_temporary_files(None, None);
+            let progress_bar = ProgressBar::new(progress::temporary_progress);
+            tf.find_temporary_files(None, Some(progress_bar.sender()));
+            progress_bar.finish();
 
             if let Some(file_name) = file_to_save.file_name() {
                 if !tf.save_results_to_file(file_name) {
@@ -193,25 +354,84 @@ fn main() {
             tf.print_results();
             tf.get_text_messages().print_messages();
         }
+        #[cfg(feature = "similar-images")]
         Commands::SimilarImages {
             directories,
             excluded_directories,
             excluded_items,
+            allowed_extensions,
             file_to_save,
             minimal_file_size,
             similarity,
+            max_distance,
+            hash_alg,
+            hash_size,
+            dct,
+            no_dct,
+            no_cache,
+            cache,
+            rotations,
+            no_rotations,
+            add_image_extensions,
+            skip_image_extensions,
+            delete_method,
+            similar_to,
             not_recursive,
+            use_ignore_files,
+            modification_date,
+            max_depth,
+            content_types,
+            resource_limits,
+            file_list,
         } => {
             let mut sf = SimilarImages::new();
 
-            sf.set_included_directory(path_list_to_str(directories.directories));
-            sf.set_excluded_directory(path_list_to_str(excluded_directories.excluded_directories));
-            sf.set_excluded_items(path_list_to_str(excluded_items.excluded_items));
-            sf.set_minimal_file_size(minimal_file_size);
-            sf.set_recursive_search(!not_recursive.not_recursive);
-            sf.set_similarity(similarity);
+            let included_directories = get_included_directories(directories.directories, &profile, file_list.file_list.as_ref());
+            if !included_directories.is_empty() {
+                sf.set_included_directory(included_directories);
+            }
+            sf.set_excluded_directory(paths_or_profile(excluded_directories.excluded_directories, &profile.excluded_directories));
+            sf.set_excluded_items(paths_or_profile(excluded_items.excluded_items, &profile.excluded_items));
+            sf.set_allowed_extensions(list_or_profile(allowed_extensions.allowed_extensions, &profile.allowed_extensions).join(","));
+            sf.set_excluded_extensions(list_or_profile(allowed_extensions.excluded_extensions, &profile.excluded_extensions).join(","));
+            sf.set_extension_groups(list_or_profile(allowed_extensions.extension_groups, &profile.extension_groups).join(";"));
+            sf.set_minimal_file_size(minimal_file_size.unwrap_or(profile.tools.similar_images_minimal_file_size));
+            sf.set_recursive_search(not_recursive.recursive_search().unwrap_or(profile.recursive_search));
+            sf.set_use_ignore_files(use_ignore_files.use_ignore_files().unwrap_or(profile.use_ignore_files));
+            sf.set_modified_after(modification_date.modified_after.unwrap_or_else(|| profile.modified_after.clone()));
+            sf.set_modified_before(modification_date.modified_before.unwrap_or_else(|| profile.modified_before.clone()));
+            sf.set_max_depth(max_depth.max_depth.or(profile.max_depth));
+            sf.set_detect_by_content(content_types.detect_by_content().unwrap_or(profile.detect_by_content));
+            sf.set_content_types(list_or_profile(content_types.content_types, &profile.content_types).join(","));
+            sf.set_io_limit(resource_limits.io_limit.or(profile.io_limit));
+            sf.set_low_priority(resource_limits.low_priority().unwrap_or(profile.low_priority));
+            sf.set_threads(resource_limits.threads.or(profile.cpu_threads));
+            if !sf.set_file_list(file_list.file_list.map(|e| e.to_string_lossy().to_string()).unwrap_or_default(), file_list.null_separated) {
+                sf.get_text_messages().print_messages();
+                process::exit(1);
+            }
+            sf.set_similarity(similarity.clone().unwrap_or_else(|| profile.tools.similar_images_similarity.clone()));
+            // Similarity level given in command line is more important than max distance from profile
+            sf.set_max_distance(max_distance.or(if similarity.is_none() { profile.tools.similar_images_max_distance } else { None }));
+            sf.set_hash_algorithm(hash_alg.unwrap_or(profile.tools.similar_images_hash_algorithm));
+            if !sf.set_hash_size(hash_size.unwrap_or(profile.tools.similar_images_hash_size)) {
+                sf.get_text_messages().print_messages();
+                process::exit(1);
+            }
+            sf.set_use_dct(flag_value(dct, no_dct).unwrap_or(profile.tools.similar_images_use_dct));
+            sf.set_use_cache(flag_value(cache, no_cache).unwrap_or(profile.tools.similar_images_use_cache));
+            sf.set_check_rotations(flag_value(rotations, no_rotations).unwrap_or(profile.tools.similar_images_check_rotations));
+            sf.set_additional_image_extensions(list_or_profile(add_image_extensions, &profile.tools.similar_images_additional_extensions).join(","));
+            sf.set_excluded_image_extensions(list_or_profile(skip_image_extensions, &profile.tools.similar_images_excluded_extensions).join(","));
+            sf.set_delete_method(delete_method.unwrap_or(profile.delete_policy.similar_images_delete_method));
+            if !sf.set_reference_images(&similar_to) {
+                sf.get_text_messages().print_messages();
+                process::exit(1);
+            }
 
-            sf.find_similar_images(None, None);
+            let progress_bar = ProgressBar::new(progress::similar_images_progress);
+            sf.find_similar_images(None, Some(progress_bar.sender()));
+            progress_bar.finish();
 
             if let Some(file_name) = file_to_save.file_name() {
                 if !sf.save_results_to_file(file_name) {
@@ -224,6 +444,24 @@ fn main() {
             sf.print_results();
             sf.get_text_messages().print_messages();
         }
+        #[cfg(feature = "similar-images")]
+        Commands::ImageCache { prune, clear } => {
+            let result = if clear {
+                ImageHashCache::clear_all().map(|removed| format!("Removed {} cache files", removed))
+            } else if prune {
+                ImageHashCache::prune_all().map(|removed| format!("Removed {} entries of missing files from cache", removed))
+            } else {
+                eprintln!("One of --prune or --clear must be given");
+                process::exit(1);
+            };
+            match result {
+                Ok(message) => println!("{}", message),
+                Err(e) => {
+                    eprintln!("{}", e);
+                    process::exit(1);
+                }
+            }
+        }
         Commands::ZeroedFiles {
             directories,
             excluded_directories,
@@ -232,22 +470,48 @@ fn main() {
             delete_files,
             file_to_save,
             not_recursive,
+            use_ignore_files,
+            modification_date,
+            max_depth,
+            content_types,
+            resource_limits,
+            file_list,
             minimal_file_size,
         } => {
             let mut zf = ZeroedFiles::new();
 
-            zf.set_included_directory(path_list_to_str(directories.directories));
-            zf.set_excluded_directory(path_list_to_str(excluded_directories.excluded_directories));
-            zf.set_excluded_items(path_list_to_str(excluded_items.excluded_items));
-            zf.set_allowed_extensions(allowed_extensions.allowed_extensions.join(","));
-            zf.set_minimal_file_size(minimal_file_size);
-            zf.set_recursive_search(!not_recursive.not_recursive);
+            let included_directories = get_included_directories(directories.directories, &profile, file_list.file_list.as_ref());
+            if !included_directories.is_empty() {
+                zf.set_included_directory(included_directories);
+            }
+            zf.set_excluded_directory(paths_or_profile(excluded_directories.excluded_directories, &profile.excluded_directories));
+            zf.set_excluded_items(paths_or_profile(excluded_items.excluded_items, &profile.excluded_items));
+            zf.set_allowed_extensions(list_or_profile(allowed_extensions.allowed_extensions, &profile.allowed_extensions).join(","));
+            zf.set_excluded_extensions(list_or_profile(allowed_extensions.excluded_extensions, &profile.excluded_extensions).join(","));
+            zf.set_extension_groups(list_or_profile(allowed_extensions.extension_groups, &profile.extension_groups).join(";"));
+            zf.set_minimal_file_size(minimal_file_size.unwrap_or(profile.tools.zeroed_minimal_file_size));
+            zf.set_recursive_search(not_recursive.recursive_search().unwrap_or(profile.recursive_search));
+            zf.set_use_ignore_files(use_ignore_files.use_ignore_files().unwrap_or(profile.use_ignore_files));
+            zf.set_modified_after(modification_date.modified_after.unwrap_or_else(|| profile.modified_after.clone()));
+            zf.set_modified_before(modification_date.modified_before.unwrap_or_else(|| profile.modified_before.clone()));
+            zf.set_max_depth(max_depth.max_depth.or(profile.max_depth));
+            zf.set_detect_by_content(content_types.detect_by_content().unwrap_or(profile.detect_by_content));
+            zf.set_content_types(list_or_profile(content_types.content_types, &profile.content_types).join(","));
+            zf.set_io_limit(resource_limits.io_limit.or(profile.io_limit));
+            zf.set_low_priority(resource_limits.low_priority().unwrap_or(profile.low_priority));
+            zf.set_threads(resource_limits.threads.or(profile.io_threads));
+            if !zf.set_file_list(file_list.file_list.map(|e| e.to_string_lossy().to_string()).unwrap_or_default(), file_list.null_separated) {
+                zf.get_text_messages().print_messages();
+                process::exit(1);
+            }
 
             if delete_files {
                 zf.set_delete_method(zeroed::DeleteMethod::Delete);
             }
 
-            zf.find_zeroed_files(None, None);
+            let progress_bar = ProgressBar::new(progress::zeroed_progress);
+            zf.find_zeroed_files(None, Some(progress_bar.sender()));
+            progress_bar.finish();
 
             if let Some(file_name) = file_to_save.file_name() {
                 if !zf.save_results_to_file(file_name) {
@@ -260,30 +524,59 @@ fn main() {
             zf.print_results();
             zf.get_text_messages().print_messages();
         }
+        #[cfg(feature = "same-music")]
         Commands::SameMusic {
             directories,
             excluded_directories,
             excluded_items,
+            allowed_extensions,
             // delete_files,
             file_to_save,
             not_recursive,
+            use_ignore_files,
+            modification_date,
+            max_depth,
+            content_types,
+            resource_limits,
+            file_list,
             minimal_file_size,
             music_similarity,
         } => {
             let mut mf = SameMusic::new();
 
-            mf.set_included_directory(path_list_to_str(directories.directories));
-            mf.set_excluded_directory(path_list_to_str(excluded_directories.excluded_directories));
-            mf.set_excluded_items(path_list_to_str(excluded_items.excluded_items));
-            mf.set_minimal_file_size(minimal_file_size);
-            mf.set_recursive_search(!not_recursive.not_recursive);
-            mf.set_music_similarity(music_similarity);
+            let included_directories = get_included_directories(directories.directories, &profile, file_list.file_list.as_ref());
+            if !included_directories.is_empty() {
+                mf.set_included_directory(included_directories);
+            }
+            mf.set_excluded_directory(paths_or_profile(excluded_directories.excluded_directories, &profile.excluded_directories));
+            mf.set_excluded_items(paths_or_profile(excluded_items.excluded_items, &profile.excluded_items));
+            mf.set_allowed_extensions(list_or_profile(allowed_extensions.allowed_extensions, &profile.allowed_extensions).join(","));
+            mf.set_excluded_extensions(list_or_profile(allowed_extensions.excluded_extensions, &profile.excluded_extensions).join(","));
+            mf.set_extension_groups(list_or_profile(allowed_extensions.extension_groups, &profile.extension_groups).join(";"));
+            mf.set_minimal_file_size(minimal_file_size.unwrap_or(profile.tools.same_music_minimal_file_size));
+            mf.set_recursive_search(not_recursive.recursive_search().unwrap_or(profile.recursive_search));
+            mf.set_use_ignore_files(use_ignore_files.use_ignore_files().unwrap_or(profile.use_ignore_files));
+            mf.set_modified_after(modification_date.modified_after.unwrap_or_else(|| profile.modified_after.clone()));
+            mf.set_modified_before(modification_date.modified_before.unwrap_or_else(|| profile.modified_before.clone()));
+            mf.set_max_depth(max_depth.max_depth.or(profile.max_depth));
+            mf.set_detect_by_content(content_types.detect_by_content().unwrap_or(profile.detect_by_content));
+            mf.set_content_types(list_or_profile(content_types.content_types, &profile.content_types).join(","));
+            mf.set_io_limit(resource_limits.io_limit.or(profile.io_limit));
+            mf.set_low_priority(resource_limits.low_priority().unwrap_or(profile.low_priority));
+            mf.set_threads(resource_limits.threads.or(profile.io_threads));
+            if !mf.set_file_list(file_list.file_list.map(|e| e.to_string_lossy().to_string()).unwrap_or_default(), file_list.null_separated) {
+                mf.get_text_messages().print_messages();
+                process::exit(1);
+            }
+            mf.set_music_similarity(music_similarity.unwrap_or_else(|| profile.tools.get_same_music_similarity()));
 
             // if delete_files {
             //     // TODO mf.set_delete_method(same_music::DeleteMethod::Delete);
             // }
 
-            mf.find_same_music(None, None);
+            let progress_bar = ProgressBar::new(progress::same_music_progress);
+            mf.find_same_music(None, Some(progress_bar.sender()));
+            progress_bar.finish();
 
             if let Some(file_name) = file_to_save.file_name() {
                 if !mf.save_results_to_file(file_name) {
@@ -0,0 +1,124 @@
+use czkawka_core::common_progress::BytesProgress;
+#[cfg(feature = "same-music")]
+use czkawka_core::same_music;
+#[cfg(feature = "similar-images")]
+use czkawka_core::similar_images;
+use czkawka_core::{big_file, duplicate, empty_files, empty_folder, temporary, zeroed};
+use futures::channel::mpsc;
+use futures::StreamExt;
+use std::io::Write;
+use std::thread;
+
+const BAR_WIDTH: usize = 30;
+const CHANNEL_SIZE: usize = 20;
+
+/// Progress line printed to stderr, so it doesn't mix with results printed to stdout
+/// When stderr is redirected to file or pipe, progress isn't printed, because it would only fill logs
+pub struct ProgressBar<T> {
+    sender: mpsc::Sender<T>,
+    handle: thread::JoinHandle<()>,
+}
+
+impl<T: Send + 'static> ProgressBar<T> {
+    pub fn new(describe: fn(&T) -> String) -> Self {
+        let (sender, mut receiver) = mpsc::channel::<T>(CHANNEL_SIZE);
+        let is_terminal = atty::is(atty::Stream::Stderr);
+        let handle = thread::spawn(move || {
+            let mut last_length: usize = 0;
+            // Channel is closed when sender is dropped in finish()
+            while let Some(item) = futures::executor::block_on(receiver.next()) {
+                // Progress is still received, because tools expect that channel is open
+                if !is_terminal {
+                    continue;
+                }
+                let line = describe(&item);
+                let length = line.chars().count();
+                eprint!("\r{}{}", line, " ".repeat(last_length.saturating_sub(length)));
+                std::io::stderr().flush().unwrap();
+                last_length = length;
+            }
+            if last_length > 0 {
+                eprint!("\r{}\r", " ".repeat(last_length));
+                std::io::stderr().flush().unwrap();
+            }
+        });
+        Self { sender, handle }
+    }
+
+    pub fn sender(&self) -> &mpsc::Sender<T> {
+        &self.sender
+    }
+
+    /// Must be called after scan ends, to clean progress line before printing results
+    pub fn finish(self) {
+        drop(self.sender);
+        self.handle.join().unwrap();
+    }
+}
+
+fn format_bar(fraction: f64) -> String {
+    let filled = ((fraction * BAR_WIDTH as f64).round() as usize).min(BAR_WIDTH);
+    format!("[{}{}] {:>3}%", "#".repeat(filled), "-".repeat(BAR_WIDTH - filled), (fraction * 100.0) as u32)
+}
+
+/// Uses size of files when it is known, because number of files may be very misleading when sizes of files differ
+fn format_stage(current_stage: u8, max_stage: u8, checked: usize, to_check: usize, bytes_progress: &BytesProgress) -> String {
+    let fraction = if bytes_progress.bytes_to_check != 0 {
+        bytes_progress.fraction()
+    } else if to_check != 0 {
+        checked as f64 / to_check as f64
+    } else {
+        0.0
+    };
+    let mut line = format!("Stage {}/{} {} {}/{} files", current_stage + 1, max_stage + 1, format_bar(fraction), checked, to_check);
+    if bytes_progress.bytes_to_check != 0 {
+        line.push_str(&format!(", {}", bytes_progress));
+    }
+    line
+}
+
+pub fn duplicate_progress(item: &duplicate::ProgressData) -> String {
+    if item.current_stage == 0 {
+        return format!("Stage 1/{} Scanned {} files", item.max_stage + 1, item.files_checked);
+    }
+    format_stage(item.current_stage, item.max_stage, item.files_checked, item.files_to_check, &item.bytes_progress)
+}
+
+pub fn empty_files_progress(item: &empty_files::ProgressData) -> String {
+    format!("Scanned {} files", item.files_checked)
+}
+
+pub fn empty_folder_progress(item: &empty_folder::ProgressData) -> String {
+    format!("Scanned {} folders", item.folders_checked)
+}
+
+pub fn big_file_progress(item: &big_file::ProgressData) -> String {
+    format!("Scanned {} files", item.files_checked)
+}
+
+pub fn temporary_progress(item: &temporary::ProgressData) -> String {
+    format!("Scanned {} files", item.files_checked)
+}
+
+#[cfg(feature = "similar-images")]
+pub fn similar_images_progress(item: &similar_images::ProgressData) -> String {
+    if item.current_stage == 0 {
+        return format!("Stage 1/{} Scanned {} files", item.max_stage + 1, item.images_checked);
+    }
+    format_stage(item.current_stage, item.max_stage, item.images_checked, item.images_to_check, &item.bytes_progress)
+}
+
+pub fn zeroed_progress(item: &zeroed::ProgressData) -> String {
+    if item.current_stage == 0 {
+        return format!("Stage 1/{} Scanned {} files", item.max_stage + 1, item.files_checked);
+    }
+    format_stage(item.current_stage, item.max_stage, item.files_checked, item.files_to_check, &item.bytes_progress)
+}
+
+#[cfg(feature = "same-music")]
+pub fn same_music_progress(item: &same_music::ProgressData) -> String {
+    if item.current_stage == 0 {
+        return format!("Stage 1/{} Scanned {} files", item.max_stage + 1, item.music_checked);
+    }
+    format_stage(item.current_stage, item.max_stage, item.music_checked, item.music_to_check, &item.bytes_progress)
+}
@@ -1,22 +1,25 @@
 use crate::common::Common;
+use crate::common_content_type::ContentFilter;
+use crate::common_date_filter::DateFilter;
+use crate::common_dir_traversal::DirTraversal;
+pub use crate::common_dir_traversal::FileEntry;
 use crate::common_directory::Directories;
 use crate::common_extensions::Extensions;
 use crate::common_items::ExcludedItems;
-use crate::common_messages::Messages;
+use crate::common_messages::{Issue, IssueKind, Messages};
+use crate::common_scan_controller::ScanController;
+use crate::common_tool::{ResultEntry, ResultGroup, Tool};
 use crate::common_traits::{DebugPrint, PrintResults, SaveResults};
-use crossbeam_channel::Receiver;
 use humansize::{file_size_opts as options, FileSize};
 use std::collections::BTreeMap;
-use std::ffi::OsStr;
-use std::fs::{File, Metadata};
+use std::fs::File;
 use std::io::Write;
-use std::path::PathBuf;
 use std::sync::atomic::Ordering;
-use std::sync::atomic::{AtomicBool, AtomicU64};
+use std::sync::atomic::{AtomicBool, AtomicUsize};
 use std::sync::Arc;
 use std::thread::sleep;
 use std::time::Duration;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::SystemTime;
 use std::{fs, thread};
 
 #[derive(Debug)]
@@ -24,13 +27,6 @@ pub struct ProgressData {
     pub files_checked: usize,
 }
 
-#[derive(Clone)]
-pub struct FileEntry {
-    pub path: PathBuf,
-    pub size: u64,
-    pub modified_date: u64,
-}
-
 #[derive(Eq, PartialEq, Clone, Debug)]
 pub enum DeleteMethod {
     None,
@@ -62,6 +58,10 @@ pub struct BigFile {
     directories: Directories,
     allowed_extensions: Extensions,
     recursive_search: bool,
+    max_depth: Option<usize>,
+    use_ignore_files: bool,
+    date_filter: DateFilter,
+    content_filter: ContentFilter,
     number_of_files_to_check: usize,
     delete_method: DeleteMethod,
     stopped_search: bool,
@@ -77,33 +77,29 @@ impl BigFile {
             directories: Directories::new(),
             allowed_extensions: Extensions::new(),
             recursive_search: true,
+            max_depth: None,
+            use_ignore_files: false,
+            date_filter: DateFilter::new(),
+            content_filter: ContentFilter::new(),
             number_of_files_to_check: 50,
             delete_method: DeleteMethod::None,
             stopped_search: false,
         }
     }
 
-    pub fn find_big_files(&mut self, stop_receiver: Option<&Receiver<()>>, progress_sender: Option<&futures::channel::mpsc::Sender<ProgressData>>) {
+    pub fn find_big_files(&mut self, scan_controller: Option<&ScanController>, progress_sender: Option<&futures::channel::mpsc::Sender<ProgressData>>) {
         self.optimize_directories();
-        if !self.look_for_big_files(stop_receiver, progress_sender) {
+        if !self.look_for_big_files(scan_controller, progress_sender) {
             self.stopped_search = true;
             return;
         }
         self.delete_files();
         self.debug_print();
     }
-    pub fn get_stopped_search(&self) -> bool {
-        self.stopped_search
-    }
-
     pub const fn get_big_files(&self) -> &BTreeMap<u64, Vec<FileEntry>> {
         &self.big_files
     }
 
-    pub const fn get_text_messages(&self) -> &Messages {
-        &self.text_messages
-    }
-
     pub const fn get_information(&self) -> &Info {
         &self.information
     }
@@ -116,26 +112,39 @@ impl BigFile {
         self.recursive_search = recursive_search;
     }
 
+    /// Only files with these content types(image, audio, video, archive, document) will be checked
+    pub fn set_content_types(&mut self, content_types: String) {
+        self.content_filter.set_content_types(content_types, &mut self.text_messages);
+    }
+
+    /// Recognizes type of files by their content instead of extension
+    pub fn set_detect_by_content(&mut self, detect_by_content: bool) {
+        self.content_filter.set_detect_by_content(detect_by_content);
+    }
+
     /// List of allowed extensions, only files with this extensions will be checking if are duplicates
     pub fn set_allowed_extensions(&mut self, allowed_extensions: String) {
         self.allowed_extensions.set_allowed_extensions(allowed_extensions, &mut self.text_messages);
     }
 
-    fn look_for_big_files(&mut self, stop_receiver: Option<&Receiver<()>>, progress_sender: Option<&futures::channel::mpsc::Sender<ProgressData>>) -> bool {
-        let start_time: SystemTime = SystemTime::now();
-        let mut folders_to_check: Vec<PathBuf> = Vec::with_capacity(1024 * 2); // This should be small enough too not see to big difference and big enough to store most of paths without needing to resize vector
+    /// Files with these extensions are skipped even if they are allowed
+    pub fn set_excluded_extensions(&mut self, excluded_extensions: String) {
+        self.allowed_extensions.set_excluded_extensions(excluded_extensions, &mut self.text_messages);
+    }
 
-        // Add root folders for finding
-        for id in &self.directories.included_directories {
-            folders_to_check.push(id.clone());
-        }
-        self.information.number_of_checked_folders += folders_to_check.len();
+    /// Named groups of extensions which can be used in allowed and excluded extensions
+    pub fn set_extension_groups(&mut self, extension_groups: String) {
+        self.allowed_extensions.set_extension_groups(extension_groups, &mut self.text_messages);
+    }
+
+    fn look_for_big_files(&mut self, scan_controller: Option<&ScanController>, progress_sender: Option<&futures::channel::mpsc::Sender<ProgressData>>) -> bool {
+        let start_time: SystemTime = SystemTime::now();
 
         //// PROGRESS THREAD START
         const LOOP_DURATION: u32 = 200; //in ms
         let progress_thread_run = Arc::new(AtomicBool::new(true));
 
-        let atomic_file_counter = Arc::new(AtomicU64::new(0));
+        let atomic_file_counter = Arc::new(AtomicUsize::new(0));
 
         let progress_thread_handle;
         if let Some(progress_sender) = progress_sender {
@@ -145,7 +154,7 @@ impl BigFile {
             progress_thread_handle = thread::spawn(move || loop {
                 progress_send
                     .try_send(ProgressData {
-                        files_checked: atomic_file_counter.load(Ordering::Relaxed) as usize,
+                        files_checked: atomic_file_counter.load(Ordering::Relaxed),
                     })
                     .unwrap();
                 if !progress_thread_run.load(Ordering::Relaxed) {
@@ -159,105 +168,32 @@ impl BigFile {
 
         //// PROGRESS THREAD END
 
-        while !folders_to_check.is_empty() {
-            if stop_receiver.is_some() && stop_receiver.unwrap().try_recv().is_ok() {
-                // Be sure that every thread is closed
-                progress_thread_run.store(false, Ordering::Relaxed);
-                progress_thread_handle.join().unwrap();
-                return false;
-            }
-
-            let current_folder = folders_to_check.pop().unwrap();
-            let read_dir = match fs::read_dir(&current_folder) {
-                Ok(t) => t,
-                Err(_) => {
-                    self.text_messages.warnings.push(format!("Cannot open dir {}", current_folder.display()));
-                    continue;
-                } // Permissions denied
-            };
-            'dir: for entry in read_dir {
-                let entry_data = match entry {
-                    Ok(t) => t,
-                    Err(_) => {
-                        self.text_messages.warnings.push(format!("Cannot read entry in dir {}", current_folder.display()));
-                        continue;
-                    } //Permissions denied
-                };
-                let metadata: Metadata = match entry_data.metadata() {
-                    Ok(t) => t,
-                    Err(_) => {
-                        self.text_messages.warnings.push(format!("Cannot read metadata in dir {}", current_folder.display()));
-                        continue;
-                    } //Permissions denied
-                };
-                if metadata.is_dir() {
-                    self.information.number_of_checked_folders += 1;
-
-                    if !self.recursive_search {
-                        continue;
-                    }
-
-                    let next_folder = current_folder.join(entry_data.file_name());
-                    if self.directories.is_excluded(&next_folder) || self.excluded_items.is_excluded(&next_folder) {
-                        continue 'dir;
-                    }
-
-                    folders_to_check.push(next_folder);
-                } else if metadata.is_file() {
-                    atomic_file_counter.fetch_add(1, Ordering::Relaxed);
-                    // Extracting file extension
-                    let file_extension = entry_data.path().extension().and_then(OsStr::to_str).map(str::to_lowercase);
-
-                    // Checking allowed extensions
-                    if !self.allowed_extensions.file_extensions.is_empty() {
-                        let allowed = self.allowed_extensions.file_extensions.iter().map(|e| e.to_lowercase()).any(|e| file_extension == Some(e));
-                        if !allowed {
-                            // Not an allowed extension, ignore it.
-                            self.information.number_of_ignored_files += 1;
-                            continue 'dir;
-                        }
-                    }
-
-                    // Checking expressions
-                    let current_file_name = current_folder.join(entry_data.file_name());
-                    if self.excluded_items.is_excluded(&current_file_name) {
-                        continue 'dir;
-                    }
-
-                    // Creating new file entry
-                    let fe: FileEntry = FileEntry {
-                        path: current_file_name.clone(),
-                        size: metadata.len(),
-                        modified_date: match metadata.modified() {
-                            Ok(t) => match t.duration_since(UNIX_EPOCH) {
-                                Ok(d) => d.as_secs(),
-                                Err(_) => {
-                                    self.text_messages.warnings.push(format!("File {} seems to be modified before Unix Epoch.", current_file_name.display()));
-                                    0
-                                }
-                            },
-                            Err(_) => {
-                                self.text_messages.warnings.push(format!("Unable to get modification date from file {}", current_file_name.display()));
-                                continue;
-                            } // Permissions Denied
-                        },
-                    };
-
-                    self.big_files.entry(metadata.len()).or_insert_with(Vec::new);
-                    self.big_files.get_mut(&metadata.len()).unwrap().push(fe);
-
-                    self.information.number_of_checked_files += 1;
-                } else {
-                    // Probably this is symbolic links so we are free to ignore this
-                    self.information.number_of_ignored_things += 1;
-                }
-            }
-        }
+        let mut dir_traversal = DirTraversal::new(&self.directories, &self.excluded_items);
+        dir_traversal.set_extensions(&self.allowed_extensions);
+        dir_traversal.set_recursive_search(self.recursive_search);
+        dir_traversal.set_max_depth(self.max_depth);
+        dir_traversal.set_use_ignore_files(self.use_ignore_files);
+        dir_traversal.set_date_filter(self.date_filter);
+        dir_traversal.set_content_filter(&self.content_filter);
+        let traversal_result = dir_traversal.run(scan_controller, &atomic_file_counter, &mut self.text_messages);
 
         // End thread which send info to gui
         progress_thread_run.store(false, Ordering::Relaxed);
         progress_thread_handle.join().unwrap();
 
+        let (files, traversal_information) = match traversal_result {
+            Some(t) => t,
+            None => return false,
+        };
+        self.information.number_of_checked_files += traversal_information.number_of_checked_files;
+        self.information.number_of_checked_folders += traversal_information.number_of_checked_folders;
+        self.information.number_of_ignored_files += traversal_information.number_of_ignored_files;
+        self.information.number_of_ignored_things += traversal_information.number_of_ignored_things;
+
+        for fe in files {
+            self.big_files.entry(fe.size).or_insert_with(Vec::new).push(fe);
+        }
+
         // Extract n biggest files to new TreeMap
         let mut new_map: BTreeMap<u64, Vec<FileEntry>> = Default::default();
 
@@ -279,7 +215,7 @@ impl BigFile {
         }
         self.big_files = new_map;
 
-        Common::print_time(start_time, SystemTime::now(), "look_for_big_files".to_string());
+        Common::log_phase(self.get_id(), "traversal", start_time, self.information.number_of_checked_files);
         true
     }
 
@@ -287,47 +223,100 @@ impl BigFile {
         self.number_of_files_to_check = number_of_files_to_check;
     }
 
-    /// Setting excluded items which needs to contains * wildcard
-    /// Are a lot of slower than absolute path, so it should be used to heavy
-    pub fn set_excluded_items(&mut self, excluded_items: String) {
-        self.excluded_items.set_excluded_items(excluded_items, &mut self.text_messages);
-    }
-
     /// Remove unused entries when included or excluded overlaps with each other or are duplicated etc.
     fn optimize_directories(&mut self) {
         self.directories.optimize_directories(self.recursive_search, &mut self.text_messages);
     }
 
-    /// Setting included directories, at least one must be provided
-    pub fn set_included_directory(&mut self, included_directory: String) {
-        self.directories.set_included_directory(included_directory, &mut self.text_messages);
-    }
-
-    /// Setting absolute path to exclude
-    pub fn set_excluded_directory(&mut self, excluded_directory: String) {
-        self.directories.set_excluded_directory(excluded_directory, &mut self.text_messages);
+    /// Files from list(read from file or "-" for standard input) are checked instead of files from included directories
+    pub fn set_file_list(&mut self, file_list: String, null_separated: bool) -> bool {
+        self.directories.set_file_list(file_list, null_separated, &mut self.text_messages)
     }
 
     /// Function to delete files, from filed Vector
     fn delete_files(&mut self) {
         let start_time: SystemTime = SystemTime::now();
 
-        match self.delete_method {
-            DeleteMethod::Delete => {
-                for vec_file_entry in self.big_files.values() {
-                    for file_entry in vec_file_entry {
-                        if fs::remove_file(file_entry.path.clone()).is_err() {
-                            self.text_messages.warnings.push(file_entry.path.display().to_string());
-                        }
-                    }
-                }
-            }
-            DeleteMethod::None => {
-                //Just do nothing
+        if self.delete_method == DeleteMethod::None {
+            return;
+        }
+
+        let mut removed_files: usize = 0;
+        for file_entry in self.big_files.values().flatten() {
+            match fs::remove_file(&file_entry.path) {
+                Ok(_) => removed_files += 1,
+                Err(e) => self.text_messages.warnings.push(Issue::new(IssueKind::DeleteFailed, "Failed to delete").with_path(&file_entry.path).with_io_error(e)),
             }
         }
 
-        Common::print_time(start_time, SystemTime::now(), "delete_files".to_string());
+        Common::log_phase(self.get_id(), "deletion", start_time, removed_files);
+    }
+}
+
+impl Tool for BigFile {
+    type ProgressData = ProgressData;
+
+    fn get_name(&self) -> &'static str {
+        "big files"
+    }
+
+    fn get_id(&self) -> &'static str {
+        "big_files"
+    }
+
+    /// Setting included directories, at least one must be provided
+    fn set_included_directory(&mut self, included_directory: String) -> bool {
+        self.directories.set_included_directory(included_directory, &mut self.text_messages)
+    }
+
+    /// Setting absolute path to exclude
+    fn set_excluded_directory(&mut self, excluded_directory: String) {
+        self.directories.set_excluded_directory(excluded_directory, &mut self.text_messages);
+    }
+
+    /// Setting excluded items which needs to contains * wildcard
+    /// Are a lot of slower than absolute path, so it should be used to heavy
+    fn set_excluded_items(&mut self, excluded_items: String) {
+        self.excluded_items.set_excluded_items(excluded_items, &mut self.text_messages);
+    }
+
+    /// Uses rules from .gitignore, .ignore and .czkawkaignore files found in scanned folders
+    fn set_use_ignore_files(&mut self, use_ignore_files: bool) {
+        self.use_ignore_files = use_ignore_files;
+    }
+
+    /// Limits number of levels of subfolders which are checked, 0 means that only included directories are checked
+    fn set_max_depth(&mut self, max_depth: Option<usize>) {
+        self.max_depth = max_depth;
+    }
+
+    fn set_modified_after(&mut self, modified_after: String) {
+        self.date_filter.set_modified_after(modified_after, &mut self.text_messages);
+    }
+
+    fn set_modified_before(&mut self, modified_before: String) {
+        self.date_filter.set_modified_before(modified_before, &mut self.text_messages);
+    }
+
+    fn get_stopped_search(&self) -> bool {
+        self.stopped_search
+    }
+
+    fn get_text_messages(&self) -> &Messages {
+        &self.text_messages
+    }
+
+    fn search(&mut self, scan_controller: Option<&ScanController>, progress_sender: Option<&futures::channel::mpsc::Sender<ProgressData>>) {
+        self.find_big_files(scan_controller, progress_sender);
+    }
+
+    fn get_result_groups(&self) -> Vec<ResultGroup> {
+        if self.big_files.is_empty() {
+            return Vec::new();
+        }
+        vec![ResultGroup {
+            entries: self.big_files.values().rev().flatten().map(ResultEntry::from).collect(),
+        }]
     }
 }
 
@@ -360,10 +349,16 @@ impl DebugPrint for BigFile {
         println!("### Other");
         println!("Big files size {} in {} groups", self.information.number_of_real_files, self.big_files.len());
         println!("Allowed extensions - {:?}", self.allowed_extensions.file_extensions);
+        println!("Excluded extensions - {:?}", self.allowed_extensions.excluded_extensions);
         println!("Excluded items - {:?}", self.excluded_items.items);
         println!("Included directories - {:?}", self.directories.included_directories);
+        println!("Number of files in file list - {:?}", self.directories.file_list.as_ref().map(|e| e.len()));
         println!("Excluded directories - {:?}", self.directories.excluded_directories);
         println!("Recursive search - {}", self.recursive_search.to_string());
+        println!("Max depth - {:?}", self.max_depth);
+        println!("Use ignore files - {}", self.use_ignore_files);
+        println!("Date filter - {:?}", self.date_filter);
+        println!("Content filter - {:?}", self.content_filter);
         println!("Number of files to check - {:?}", self.number_of_files_to_check);
         println!("-----------------------------------------");
     }
@@ -380,8 +375,8 @@ impl SaveResults for BigFile {
 
         let mut file = match File::create(&file_name) {
             Ok(t) => t,
-            Err(_) => {
-                self.text_messages.errors.push("Failed to create file ".to_string() + file_name.as_str());
+            Err(e) => {
+                self.text_messages.errors.push(Issue::new(IssueKind::SaveFailed, "Failed to create file").with_path(&file_name).with_io_error(e));
                 return false;
             }
         };
@@ -393,7 +388,7 @@ impl SaveResults for BigFile {
         )
         .is_err()
         {
-            self.text_messages.errors.push(format!("Failed to save results to file {}", file_name));
+            self.text_messages.errors.push(Issue::new(IssueKind::SaveFailed, "Failed to save results to file").with_path(&file_name));
             return false;
         }
 
@@ -408,7 +403,7 @@ impl SaveResults for BigFile {
         } else {
             write!(file, "Not found any files.").unwrap();
         }
-        Common::print_time(start_time, SystemTime::now(), "save_results_to_file".to_string());
+        Common::log_time(start_time, "save_results_to_file");
         true
     }
 }
@@ -423,6 +418,6 @@ impl PrintResults for BigFile {
                 println!("{} ({} bytes) - {}", size.file_size(options::BINARY).unwrap(), size, entry.path.display());
             }
         }
-        Common::print_time(start_time, SystemTime::now(), "print_entries".to_string());
+        Common::log_time(start_time, "print_entries");
     }
 }
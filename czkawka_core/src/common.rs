@@ -1,3 +1,4 @@
+use log::{debug, info};
 use std::ffi::OsString;
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -7,11 +8,18 @@ use std::time::SystemTime;
 
 pub struct Common();
 impl Common {
-    /// Printing time which took between start and stop point and prints also function name
-    #[allow(unused_variables)]
-    pub fn print_time(start_time: SystemTime, end_time: SystemTime, function_name: String) {
-        #[cfg(debug_assertions)]
-        println!("Execution of function \"{}\" took {:?}", function_name, end_time.duration_since(start_time).expect("Time cannot go reverse."));
+    /// Logs time which took between start point and now, together with function name
+    pub fn log_time(start_time: SystemTime, function_name: &str) {
+        debug!("function={} duration_ms={}", function_name, Common::elapsed_ms(start_time));
+    }
+
+    /// Logs end of scan phase of tool(e.g. traversal, pre_hash, deletion) with its duration and number of processed entries
+    pub fn log_phase(tool: &str, phase: &str, start_time: SystemTime, entries: usize) {
+        info!("tool={} phase={} duration_ms={} entries={}", tool, phase, Common::elapsed_ms(start_time), entries);
+    }
+
+    fn elapsed_ms(start_time: SystemTime) -> u128 {
+        SystemTime::now().duration_since(start_time).unwrap_or_default().as_millis()
     }
 
     pub fn delete_multiple_entries(entries: &[String]) -> Vec<String> {
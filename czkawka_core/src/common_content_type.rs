@@ -0,0 +1,166 @@
+use crate::common_extensions::has_extension;
+use crate::common_messages::{Issue, IssueKind, Messages};
+use infer::MatcherType;
+use std::path::Path;
+
+const IMAGE_EXTENSIONS: [&str; 17] = ["jpg", "jpeg", "png", "gif", "bmp", "tif", "tiff", "webp", "ico", "dds", "hdr", "svg", "kra", "heic", "heif", "avif", "psd"];
+const AUDIO_EXTENSIONS: [&str; 13] = ["mp3", "flac", "m4a", "ogg", "oga", "opus", "wav", "wma", "aac", "tta", "aif", "aiff", "amr"];
+const VIDEO_EXTENSIONS: [&str; 15] = ["mp4", "m4v", "mkv", "webm", "avi", "mov", "wmv", "flv", "mpg", "mpeg", "3gp", "ogv", "vob", "gifv", "m4p"];
+const ARCHIVE_EXTENSIONS: [&str; 17] = ["zip", "rar", "7z", "tar", "gz", "tgz", "bz2", "xz", "zst", "lz", "lz4", "z", "cab", "deb", "rpm", "iso", "jar"];
+const DOCUMENT_EXTENSIONS: [&str; 13] = ["pdf", "doc", "docx", "odt", "ods", "odp", "xls", "xlsx", "ppt", "pptx", "rtf", "epub", "mobi"];
+
+/// Kind of file, recognized by extension or by first bytes of file
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ContentType {
+    Image,
+    Audio,
+    Video,
+    Archive,
+    Document,
+}
+
+impl ContentType {
+    pub fn from_name(name: &str) -> Result<ContentType, String> {
+        match name.trim().to_lowercase().as_str() {
+            "image" => Ok(ContentType::Image),
+            "audio" | "music" => Ok(ContentType::Audio),
+            "video" => Ok(ContentType::Video),
+            "archive" => Ok(ContentType::Archive),
+            "document" => Ok(ContentType::Document),
+            _ => Err(format!("{} is not valid content type(allowed: image, audio, video, archive, document)", name.trim())),
+        }
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        match self {
+            ContentType::Image => &IMAGE_EXTENSIONS,
+            ContentType::Audio => &AUDIO_EXTENSIONS,
+            ContentType::Video => &VIDEO_EXTENSIONS,
+            ContentType::Archive => &ARCHIVE_EXTENSIONS,
+            ContentType::Document => &DOCUMENT_EXTENSIONS,
+        }
+    }
+
+    /// Recognizes type only by extension of lowercase file name, which is fast but may be wrong
+    pub fn from_extension(file_name_lowercase: &str) -> Option<ContentType> {
+        [ContentType::Image, ContentType::Audio, ContentType::Video, ContentType::Archive, ContentType::Document]
+            .iter()
+            .find(|content_type| content_type.extensions().iter().any(|e| has_extension(file_name_lowercase, e)))
+            .copied()
+    }
+
+    /// Recognizes type by magic bytes at the start of file, returns None when file cannot be read or its type is unknown
+    pub fn from_content(path: &Path) -> Option<ContentType> {
+        let file_type = infer::get_from_path(path).ok()??;
+        match file_type.matcher_type() {
+            MatcherType::Image => Some(ContentType::Image),
+            MatcherType::Audio => Some(ContentType::Audio),
+            MatcherType::Video => Some(ContentType::Video),
+            MatcherType::Archive => Some(ContentType::Archive),
+            MatcherType::Doc | MatcherType::Book => Some(ContentType::Document),
+            _ => None,
+        }
+    }
+}
+
+/// Allows only files with chosen types of content
+#[derive(Clone, Debug, Default)]
+pub struct ContentFilter {
+    /// Allowed types, when empty all files are allowed
+    pub content_types: Vec<ContentType>,
+    /// Reads first bytes of each file instead of trusting its extension
+    pub detect_by_content: bool,
+    /// Type of files which tool is able to handle, checked additionally to types chosen by user
+    pub(crate) required_content_type: Option<ContentType>,
+}
+
+impl ContentFilter {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Content types must be separated by commas e.g. "image,video"
+    pub fn set_content_types(&mut self, content_types: String, text_messages: &mut Messages) {
+        self.content_types.clear();
+        for name in content_types.split(',') {
+            if name.trim().is_empty() {
+                continue;
+            }
+            match ContentType::from_name(name) {
+                Ok(content_type) => {
+                    if !self.content_types.contains(&content_type) {
+                        self.content_types.push(content_type);
+                    }
+                }
+                Err(e) => text_messages.warnings.push(Issue::new(IssueKind::InvalidConfiguration, e)),
+            }
+        }
+    }
+
+    pub fn set_detect_by_content(&mut self, detect_by_content: bool) {
+        self.detect_by_content = detect_by_content;
+    }
+
+    /// Content of file is read only when detecting by content is enabled and some types are required
+    pub fn is_allowed(&self, path: &Path, file_name_lowercase: &str) -> bool {
+        if self.content_types.is_empty() && self.required_content_type.is_none() {
+            return true;
+        }
+
+        let content_type = if self.detect_by_content { ContentType::from_content(path) } else { ContentType::from_extension(file_name_lowercase) };
+        let content_type = match content_type {
+            Some(t) => t,
+            None => return false,
+        };
+
+        if let Some(required_content_type) = self.required_content_type {
+            if content_type != required_content_type {
+                return false;
+            }
+        }
+        self.content_types.is_empty() || self.content_types.contains(&content_type)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::common_content_type::{ContentFilter, ContentType};
+    use crate::common_messages::Messages;
+    use std::fs;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_content_filter() {
+        let root: PathBuf = std::env::temp_dir().join(format!("czkawka_test_content_type_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        // PNG signature followed by beginning of IHDR chunk
+        let png_bytes: [u8; 16] = [0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A, 0x00, 0x00, 0x00, 0x0D, 0x49, 0x48, 0x44, 0x52];
+        fs::write(root.join("image.dat"), png_bytes).unwrap();
+        fs::write(root.join("fake.png"), "not an image").unwrap();
+
+        assert_eq!(ContentType::from_content(&root.join("image.dat")), Some(ContentType::Image));
+        assert_eq!(ContentType::from_content(&root.join("fake.png")), None);
+        assert_eq!(ContentType::from_extension("archive.tar.gz"), Some(ContentType::Archive));
+        assert_eq!(ContentType::from_extension("file.dat"), None);
+
+        let mut text_messages = Messages::new();
+        let mut content_filter = ContentFilter::new();
+        assert!(content_filter.is_allowed(&root.join("image.dat"), "image.dat"));
+
+        content_filter.set_content_types("Image, video,unknown".to_string(), &mut text_messages);
+        assert_eq!(content_filter.content_types, vec![ContentType::Image, ContentType::Video]);
+        assert_eq!(text_messages.warnings.len(), 1);
+        assert!(!content_filter.is_allowed(&root.join("image.dat"), "image.dat"));
+        assert!(content_filter.is_allowed(&root.join("fake.png"), "fake.png"));
+
+        content_filter.set_detect_by_content(true);
+        assert!(content_filter.is_allowed(&root.join("image.dat"), "image.dat"));
+        assert!(!content_filter.is_allowed(&root.join("fake.png"), "fake.png"));
+
+        content_filter.required_content_type = Some(ContentType::Audio);
+        assert!(!content_filter.is_allowed(&root.join("image.dat"), "image.dat"));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
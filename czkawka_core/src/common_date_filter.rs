@@ -0,0 +1,143 @@
+use crate::common_messages::{Issue, IssueKind, Messages};
+use chrono::{Local, NaiveDate, NaiveDateTime, TimeZone};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const DATE_TIME_FORMATS: [&str; 4] = ["%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M", "%Y-%m-%dT%H:%M"];
+
+/// Allows only files modified in the given range of time
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DateFilter {
+    pub modified_after: Option<u64>,
+    pub modified_before: Option<u64>,
+}
+
+impl DateFilter {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Files modified before this time are ignored, empty value disables checking
+    pub fn set_modified_after(&mut self, modified_after: String, text_messages: &mut Messages) {
+        self.modified_after = parse_time_setting(&modified_after, "Modified After", text_messages);
+    }
+
+    /// Files modified at or after this time are ignored, empty value disables checking
+    pub fn set_modified_before(&mut self, modified_before: String, text_messages: &mut Messages) {
+        self.modified_before = parse_time_setting(&modified_before, "Modified Before", text_messages);
+    }
+
+    /// Checks if modification date(in seconds since Unix Epoch) is inside of allowed range
+    pub fn is_allowed(&self, modified_date: u64) -> bool {
+        if let Some(modified_after) = self.modified_after {
+            if modified_date < modified_after {
+                return false;
+            }
+        }
+        if let Some(modified_before) = self.modified_before {
+            if modified_date >= modified_before {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Parses date in local time zone(2020-12-31, 2020-12-31 23:59 or 2020-12-31T23:59:59) or duration counted back from now(12h, 30d, 2w, 6m, 1y)
+    /// Returns number of seconds since Unix Epoch
+    pub fn parse_time(time: &str) -> Result<u64, String> {
+        let time = time.trim();
+
+        if let Some(seconds) = parse_duration(time) {
+            let now = SystemTime::now().duration_since(UNIX_EPOCH).expect("Time cannot go reverse.").as_secs();
+            return Ok(now.saturating_sub(seconds));
+        }
+
+        let mut date_time: Option<NaiveDateTime> = NaiveDate::parse_from_str(time, "%Y-%m-%d").ok().and_then(|e| e.and_hms_opt(0, 0, 0));
+        for format in DATE_TIME_FORMATS.iter() {
+            if date_time.is_some() {
+                break;
+            }
+            date_time = NaiveDateTime::parse_from_str(time, format).ok();
+        }
+
+        let date_time = match date_time {
+            Some(t) => t,
+            None => return Err(format!("{} is neither a date like 2020-12-31 or 2020-12-31 23:59, nor a duration like 30d", time)),
+        };
+        match Local.from_local_datetime(&date_time).earliest() {
+            Some(t) if t.timestamp() >= 0 => Ok(t.timestamp() as u64),
+            _ => Err(format!("{} is not a valid date after 1970-01-01", time)),
+        }
+    }
+}
+
+fn parse_time_setting(time: &str, setting_name: &str, text_messages: &mut Messages) -> Option<u64> {
+    if time.trim().is_empty() {
+        return None;
+    }
+    match DateFilter::parse_time(time) {
+        Ok(t) => Some(t),
+        Err(e) => {
+            text_messages.errors.push(Issue::new(IssueKind::InvalidConfiguration, format!("{} ERROR: {}", setting_name, e)));
+            None
+        }
+    }
+}
+
+/// Converts durations like 30d to number of seconds, months and years are approximated to 30 and 365 days
+fn parse_duration(duration: &str) -> Option<u64> {
+    let unit = duration.chars().last()?;
+    let number: u64 = duration[..duration.len() - unit.len_utf8()].trim().parse().ok()?;
+    let unit_seconds: u64 = match unit {
+        'h' => 60 * 60,
+        'd' => 24 * 60 * 60,
+        'w' => 7 * 24 * 60 * 60,
+        'm' => 30 * 24 * 60 * 60,
+        'y' => 365 * 24 * 60 * 60,
+        _ => return None,
+    };
+    Some(number.saturating_mul(unit_seconds))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::common_date_filter::DateFilter;
+    use crate::common_messages::Messages;
+    use chrono::{Local, TimeZone};
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    #[test]
+    fn test_parse_time() {
+        let midnight = Local.ymd(2020, 12, 31).and_hms(0, 0, 0).timestamp() as u64;
+        assert_eq!(DateFilter::parse_time("2020-12-31"), Ok(midnight));
+        assert_eq!(DateFilter::parse_time("2020-12-31 01:02"), Ok(midnight + 3720));
+        assert_eq!(DateFilter::parse_time(" 2020-12-31T01:02:03 "), Ok(midnight + 3723));
+
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+        let thirty_days_ago = DateFilter::parse_time("30d").unwrap();
+        assert!(now - thirty_days_ago >= 30 * 24 * 60 * 60 && now - thirty_days_ago < 30 * 24 * 60 * 60 + 10);
+
+        assert!(DateFilter::parse_time("2020-13-01").is_err());
+        assert!(DateFilter::parse_time("30x").is_err());
+        assert!(DateFilter::parse_time("d").is_err());
+        assert!(DateFilter::parse_time("1960-01-01").is_err());
+    }
+
+    #[test]
+    fn test_is_allowed() {
+        let mut text_messages = Messages::new();
+        let mut date_filter = DateFilter::new();
+        assert!(date_filter.is_allowed(0));
+
+        date_filter.set_modified_after("1970-01-03".to_string(), &mut text_messages);
+        date_filter.set_modified_before("1970-01-05".to_string(), &mut text_messages);
+        let day = 24 * 60 * 60;
+        assert!(!date_filter.is_allowed(date_filter.modified_after.unwrap() - 1));
+        assert!(date_filter.is_allowed(date_filter.modified_after.unwrap()));
+        assert!(date_filter.is_allowed(date_filter.modified_after.unwrap() + day));
+        assert!(!date_filter.is_allowed(date_filter.modified_before.unwrap()));
+
+        date_filter.set_modified_before("yesterday".to_string(), &mut text_messages);
+        assert_eq!(text_messages.errors.len(), 1);
+        assert_eq!(date_filter.modified_before, None);
+    }
+}
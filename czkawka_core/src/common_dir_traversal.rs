@@ -0,0 +1,474 @@
+use crate::common_content_type::ContentFilter;
+use crate::common_date_filter::DateFilter;
+use crate::common_directory::Directories;
+use crate::common_extensions::Extensions;
+use crate::common_items::ExcludedItems;
+use crate::common_messages::{Issue, IssueKind, Messages};
+use crate::common_scan_controller::ScanController;
+use crate::common_tool::ResultEntry;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use ignore::Match;
+use std::fs;
+use std::fs::{DirEntry, Metadata};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::UNIX_EPOCH;
+
+/// Files with gitignore like rules, sorted from the lowest to the highest priority
+pub const IGNORE_FILES: [&str; 3] = [".gitignore", ".ignore", ".czkawkaignore"];
+
+/// File which passed all common filters used when traversing directories
+#[derive(Clone, Debug)]
+pub struct FileEntry {
+    pub path: PathBuf,
+    pub size: u64,
+    pub modified_date: u64,
+}
+
+impl From<&FileEntry> for ResultEntry {
+    fn from(file_entry: &FileEntry) -> Self {
+        Self {
+            path: file_entry.path.clone(),
+            size: file_entry.size,
+            modified_date: file_entry.modified_date,
+        }
+    }
+}
+
+/// Info struck with helpful information's about traversed directories
+#[derive(Default)]
+pub struct TraversalInfo {
+    pub number_of_checked_files: usize,
+    pub number_of_checked_folders: usize,
+    pub number_of_ignored_files: usize,
+    pub number_of_ignored_things: usize,
+}
+
+/// Rules loaded from ignore files of one folder, connected with rules from its parent folders
+pub(crate) struct IgnoreRules {
+    matcher: Gitignore,
+    parent: Option<Rc<IgnoreRules>>,
+}
+
+impl IgnoreRules {
+    /// Loads ignore files found between entries of folder, returns parent rules when folder don't have any
+    pub(crate) fn load(folder: &Path, entries: &[DirEntry], parent: Option<Rc<IgnoreRules>>, text_messages: &mut Messages) -> Option<Rc<IgnoreRules>> {
+        let mut builder = GitignoreBuilder::new(folder);
+        let mut found_ignore_file = false;
+
+        for ignore_file in IGNORE_FILES.iter() {
+            if !entries.iter().any(|e| e.file_name() == *ignore_file) {
+                continue;
+            }
+            found_ignore_file = true;
+            let ignore_file_path = folder.join(ignore_file);
+            if let Some(e) = builder.add(&ignore_file_path) {
+                text_messages
+                    .warnings
+                    .push(Issue::new(IssueKind::ReadFailed, format!("Failed to load some rules({}) from ignore file", e)).with_path(&ignore_file_path));
+            }
+        }
+        if !found_ignore_file {
+            return parent;
+        }
+
+        match builder.build() {
+            Ok(matcher) => Some(Rc::new(IgnoreRules { matcher, parent })),
+            Err(e) => {
+                text_messages.warnings.push(Issue::new(IssueKind::InvalidConfiguration, format!("Failed to use ignore files({}) from folder", e)).with_path(folder));
+                parent
+            }
+        }
+    }
+
+    /// Checks rules from the deepest folder, so rules from subfolders may override(or whitelist with !) rules from parents
+    pub(crate) fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        let mut rules = Some(self);
+        while let Some(current_rules) = rules {
+            match current_rules.matcher.matched(path, is_dir) {
+                Match::Ignore(_) => return true,
+                Match::Whitelist(_) => return false,
+                Match::None => rules = current_rules.parent.as_deref(),
+            }
+        }
+        false
+    }
+}
+
+/// Checks if subfolders of folder placed at given depth are beyond maximum depth
+pub(crate) fn is_too_deep(depth: usize, max_depth: Option<usize>) -> bool {
+    match max_depth {
+        Some(max_depth) => depth >= max_depth,
+        None => false,
+    }
+}
+
+/// Additional check used by tool, which gets lowercase file name and metadata of file
+type FileFilter<'a> = Box<dyn Fn(&str, &Metadata) -> bool + 'a>;
+
+/// Walks through included directories(or checks files from list) and collects files which pass common filters
+pub struct DirTraversal<'a> {
+    directories: &'a Directories,
+    excluded_items: &'a ExcludedItems,
+    extensions: Option<&'a Extensions>,
+    recursive_search: bool,
+    max_depth: Option<usize>,
+    use_ignore_files: bool,
+    minimal_file_size: u64,
+    date_filter: DateFilter,
+    content_filter: Option<&'a ContentFilter>,
+    file_filter: Option<FileFilter<'a>>,
+}
+
+impl<'a> DirTraversal<'a> {
+    pub fn new(directories: &'a Directories, excluded_items: &'a ExcludedItems) -> Self {
+        Self {
+            directories,
+            excluded_items,
+            extensions: None,
+            recursive_search: true,
+            max_depth: None,
+            use_ignore_files: false,
+            minimal_file_size: 0,
+            date_filter: DateFilter::new(),
+            content_filter: None,
+            file_filter: None,
+        }
+    }
+
+    /// Allowed and excluded extensions of files
+    pub fn set_extensions(&mut self, extensions: &'a Extensions) {
+        self.extensions = Some(extensions);
+    }
+
+    pub fn set_recursive_search(&mut self, recursive_search: bool) {
+        self.recursive_search = recursive_search;
+    }
+
+    /// Number of levels of subfolders which are checked, 0 means only included directories and None means no limit
+    pub fn set_max_depth(&mut self, max_depth: Option<usize>) {
+        self.max_depth = max_depth;
+    }
+
+    /// Rules from .gitignore, .ignore and .czkawkaignore files found in scanned folders are used to skip files and folders
+    pub fn set_use_ignore_files(&mut self, use_ignore_files: bool) {
+        self.use_ignore_files = use_ignore_files;
+    }
+
+    pub fn set_minimal_file_size(&mut self, minimal_file_size: u64) {
+        self.minimal_file_size = minimal_file_size;
+    }
+
+    pub fn set_date_filter(&mut self, date_filter: DateFilter) {
+        self.date_filter = date_filter;
+    }
+
+    /// Types of files, which may be recognized by reading beginning of each file, so it is checked after all other filters
+    pub fn set_content_filter(&mut self, content_filter: &'a ContentFilter) {
+        self.content_filter = Some(content_filter);
+    }
+
+    /// Additional check used by tool, which gets lowercase file name and metadata of file
+    pub fn set_file_filter(&mut self, file_filter: impl Fn(&str, &Metadata) -> bool + 'a) {
+        self.file_filter = Some(Box::new(file_filter));
+    }
+
+    /// Returns None when search was stopped by user
+    pub fn run(&self, scan_controller: Option<&ScanController>, atomic_file_counter: &AtomicUsize, text_messages: &mut Messages) -> Option<(Vec<FileEntry>, TraversalInfo)> {
+        if let Some(file_list) = &self.directories.file_list {
+            return self.run_file_list(file_list, scan_controller, atomic_file_counter, text_messages);
+        }
+
+        let mut files: Vec<FileEntry> = Vec::new();
+        let mut information = TraversalInfo::default();
+        let mut folders_to_check: Vec<(PathBuf, Option<Rc<IgnoreRules>>, usize)> = Vec::with_capacity(1024 * 2); // This should be small enough too not see to big difference and big enough to store most of paths without needing to resize vector
+
+        // Add root folders for finding
+        for id in &self.directories.included_directories {
+            folders_to_check.push((id.clone(), None, 0));
+        }
+        information.number_of_checked_folders += folders_to_check.len();
+
+        while let Some((current_folder, parent_rules, depth)) = folders_to_check.pop() {
+            if scan_controller.is_some() && scan_controller.unwrap().should_stop() {
+                return None;
+            }
+
+            // Read current dir, if permission are denied just go to next
+            let read_dir = match fs::read_dir(&current_folder) {
+                Ok(t) => t,
+                Err(e) => {
+                    text_messages.warnings.push(Issue::new(IssueKind::ReadFailed, "Cannot open dir").with_path(&current_folder).with_io_error(e));
+                    continue;
+                } // Permissions denied
+            };
+
+            let mut entries: Vec<DirEntry> = Vec::new();
+            for entry in read_dir {
+                match entry {
+                    Ok(t) => entries.push(t),
+                    Err(e) => text_messages.warnings.push(Issue::new(IssueKind::ReadFailed, "Cannot read entry in dir").with_path(&current_folder).with_io_error(e)), //Permissions denied
+                }
+            }
+
+            let ignore_rules = match self.use_ignore_files {
+                true => IgnoreRules::load(&current_folder, &entries, parent_rules, text_messages),
+                false => None,
+            };
+
+            // Check every sub folder/file/link etc.
+            'dir: for entry_data in entries {
+                let metadata: Metadata = match entry_data.metadata() {
+                    Ok(t) => t,
+                    Err(e) => {
+                        text_messages.warnings.push(Issue::new(IssueKind::InvalidMetadata, "Cannot read metadata of").with_path(entry_data.path()).with_io_error(e));
+                        continue 'dir;
+                    } //Permissions denied
+                };
+                if metadata.is_dir() {
+                    information.number_of_checked_folders += 1;
+
+                    if !self.recursive_search || is_too_deep(depth, self.max_depth) {
+                        continue 'dir;
+                    }
+
+                    let next_folder = current_folder.join(entry_data.file_name());
+                    if self.directories.is_excluded(&next_folder) || self.excluded_items.is_excluded(&next_folder) {
+                        continue 'dir;
+                    }
+
+                    if let Some(ignore_rules) = &ignore_rules {
+                        if ignore_rules.is_ignored(&next_folder, true) {
+                            continue 'dir;
+                        }
+                    }
+
+                    folders_to_check.push((next_folder, ignore_rules.clone(), depth + 1));
+                } else if metadata.is_file() {
+                    atomic_file_counter.fetch_add(1, Ordering::Relaxed);
+
+                    if let Some(fe) = self.check_file(current_folder.join(entry_data.file_name()), &metadata, ignore_rules.as_deref(), &mut information, text_messages) {
+                        files.push(fe);
+                        information.number_of_checked_files += 1;
+                    }
+                } else {
+                    // Probably this is symbolic links so we are free to ignore this
+                    information.number_of_ignored_things += 1;
+                }
+            }
+        }
+
+        Some((files, information))
+    }
+
+    /// Checks only files from list instead of walking included directories, ignore files are not used here
+    fn run_file_list(&self, file_list: &[PathBuf], scan_controller: Option<&ScanController>, atomic_file_counter: &AtomicUsize, text_messages: &mut Messages) -> Option<(Vec<FileEntry>, TraversalInfo)> {
+        let mut files: Vec<FileEntry> = Vec::new();
+        let mut information = TraversalInfo::default();
+
+        for path in file_list {
+            if scan_controller.is_some() && scan_controller.unwrap().should_stop() {
+                return None;
+            }
+
+            let metadata: Metadata = match fs::symlink_metadata(path) {
+                Ok(t) => t,
+                Err(e) => {
+                    text_messages.warnings.push(Issue::new(IssueKind::InvalidMetadata, "Cannot read metadata of").with_path(path).with_io_error(e));
+                    continue;
+                }
+            };
+            if !metadata.is_file() {
+                // Folders and symbolic links are not checked, because list should contain only files
+                information.number_of_ignored_things += 1;
+                continue;
+            }
+            if self.directories.is_inside_excluded(path) {
+                continue;
+            }
+            atomic_file_counter.fetch_add(1, Ordering::Relaxed);
+
+            if let Some(fe) = self.check_file(path.clone(), &metadata, None, &mut information, text_messages) {
+                files.push(fe);
+                information.number_of_checked_files += 1;
+            }
+        }
+
+        Some((files, information))
+    }
+
+    /// Checks file with all filters, returns None when file should be skipped
+    fn check_file(&self, current_file_name: PathBuf, metadata: &Metadata, ignore_rules: Option<&IgnoreRules>, information: &mut TraversalInfo, text_messages: &mut Messages) -> Option<FileEntry> {
+        let file_name_lowercase: String = current_file_name.file_name()?.to_str()?.to_lowercase();
+
+        // Checking allowed and excluded extensions
+        if let Some(extensions) = self.extensions {
+            if !extensions.is_allowed(&file_name_lowercase) {
+                information.number_of_ignored_files += 1;
+                return None;
+            }
+        }
+
+        // Checking size and conditions specific for tool
+        if metadata.len() < self.minimal_file_size {
+            information.number_of_ignored_files += 1;
+            return None;
+        }
+        if let Some(file_filter) = &self.file_filter {
+            if !file_filter(&file_name_lowercase, metadata) {
+                information.number_of_ignored_files += 1;
+                return None;
+            }
+        }
+
+        if self.excluded_items.is_excluded(&current_file_name) {
+            return None;
+        }
+
+        if let Some(ignore_rules) = ignore_rules {
+            if ignore_rules.is_ignored(&current_file_name, false) {
+                information.number_of_ignored_files += 1;
+                return None;
+            }
+        }
+
+        let modified_date = match metadata.modified() {
+            Ok(t) => match t.duration_since(UNIX_EPOCH) {
+                Ok(d) => d.as_secs(),
+                Err(_) => {
+                    text_messages.warnings.push(Issue::new(IssueKind::InvalidMetadata, "Modification date is before Unix Epoch in file").with_path(&current_file_name));
+                    0
+                }
+            },
+            Err(e) => {
+                text_messages
+                    .warnings
+                    .push(Issue::new(IssueKind::InvalidMetadata, "Unable to get modification date from file").with_path(&current_file_name).with_io_error(e));
+                return None;
+            } // Permissions Denied
+        };
+
+        if !self.date_filter.is_allowed(modified_date) {
+            information.number_of_ignored_files += 1;
+            return None;
+        }
+
+        if let Some(content_filter) = self.content_filter {
+            if !content_filter.is_allowed(&current_file_name, &file_name_lowercase) {
+                information.number_of_ignored_files += 1;
+                return None;
+            }
+        }
+
+        Some(FileEntry {
+            path: current_file_name,
+            size: metadata.len(),
+            modified_date,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::common_dir_traversal::DirTraversal;
+    use crate::common_directory::Directories;
+    use crate::common_extensions::Extensions;
+    use crate::common_items::ExcludedItems;
+    use crate::common_messages::Messages;
+    use std::fs;
+    use std::path::PathBuf;
+    use std::sync::atomic::AtomicUsize;
+
+    #[test]
+    fn test_ignore_files() {
+        let root: PathBuf = std::env::temp_dir().join(format!("czkawka_test_ignore_files_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("project/target/debug")).unwrap();
+        fs::create_dir_all(root.join("project/src")).unwrap();
+        fs::write(root.join(".gitignore"), "*.log\n").unwrap();
+        fs::write(root.join("project/.czkawkaignore"), "target/\n!important.log\n").unwrap();
+        fs::write(root.join("a.log"), "").unwrap();
+        fs::write(root.join("a.txt"), "").unwrap();
+        fs::write(root.join("project/important.log"), "").unwrap();
+        fs::write(root.join("project/other.log"), "").unwrap();
+        fs::write(root.join("project/src/main.rs"), "").unwrap();
+        fs::write(root.join("project/target/debug/main"), "").unwrap();
+
+        let mut text_messages = Messages::new();
+        let mut directories = Directories::new();
+        directories.set_included_directory(root.to_string_lossy().to_string(), &mut text_messages);
+        let excluded_items = ExcludedItems::new();
+
+        let mut traversal = DirTraversal::new(&directories, &excluded_items);
+        traversal.set_use_ignore_files(true);
+        let (files, _information) = traversal.run(None, &AtomicUsize::new(0), &mut text_messages).unwrap();
+        let mut found: Vec<PathBuf> = files.into_iter().map(|e| e.path.strip_prefix(&root).unwrap().to_path_buf()).collect();
+        found.sort();
+        let expected: Vec<PathBuf> = [".gitignore", "a.txt", "project/.czkawkaignore", "project/important.log", "project/src/main.rs"].iter().map(PathBuf::from).collect();
+        assert_eq!(found, expected);
+
+        traversal.set_use_ignore_files(false);
+        let (files, _information) = traversal.run(None, &AtomicUsize::new(0), &mut text_messages).unwrap();
+        assert_eq!(files.len(), 8);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_file_list() {
+        let root: PathBuf = std::env::temp_dir().join(format!("czkawka_test_file_list_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("excluded")).unwrap();
+        fs::write(root.join("a.txt"), "").unwrap();
+        fs::write(root.join("b.log"), "").unwrap();
+        fs::write(root.join("not listed.txt"), "").unwrap();
+        fs::write(root.join("excluded/c.txt"), "").unwrap();
+
+        let mut text_messages = Messages::new();
+        let mut directories = Directories::new();
+        directories.set_excluded_directory(root.join("excluded").to_string_lossy().to_string(), &mut text_messages);
+        let list = format!("{0}/a.txt\0{0}/./b.log\0{0}/excluded/c.txt\0{0}/missing.txt\0{0}\0{0}/a.txt\0", root.display());
+        assert!(directories.load_file_list(list.as_bytes(), true, &mut text_messages));
+        assert_eq!(directories.file_list.as_ref().unwrap().len(), 5);
+        assert!(directories.optimize_directories(true, &mut text_messages));
+        let excluded_items = ExcludedItems::new();
+        let mut extensions = Extensions::new();
+        extensions.set_excluded_extensions("log".to_string(), &mut text_messages);
+
+        let mut traversal = DirTraversal::new(&directories, &excluded_items);
+        traversal.set_extensions(&extensions);
+        let (files, information) = traversal.run(None, &AtomicUsize::new(0), &mut text_messages).unwrap();
+        let found: Vec<PathBuf> = files.into_iter().map(|e| e.path).collect();
+        assert_eq!(found, vec![root.join("a.txt")]);
+        assert_eq!(information.number_of_ignored_files, 1);
+        assert_eq!(information.number_of_ignored_things, 1);
+        assert_eq!(text_messages.warnings.len(), 1);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_max_depth() {
+        let root: PathBuf = std::env::temp_dir().join(format!("czkawka_test_max_depth_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("a/b/c")).unwrap();
+        for path in ["0.txt", "a/1.txt", "a/b/2.txt", "a/b/c/3.txt"].iter() {
+            fs::write(root.join(path), "").unwrap();
+        }
+
+        let mut text_messages = Messages::new();
+        let mut directories = Directories::new();
+        directories.set_included_directory(root.to_string_lossy().to_string(), &mut text_messages);
+        let excluded_items = ExcludedItems::new();
+
+        let mut traversal = DirTraversal::new(&directories, &excluded_items);
+        for (max_depth, expected_files) in [(Some(0), 1), (Some(2), 3), (None, 4)].iter() {
+            traversal.set_max_depth(*max_depth);
+            let (files, _information) = traversal.run(None, &AtomicUsize::new(0), &mut text_messages).unwrap();
+            assert_eq!(files.len(), *expected_files);
+        }
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
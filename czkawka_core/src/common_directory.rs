@@ -1,12 +1,20 @@
 use crate::common::Common;
-use crate::common_messages::Messages;
-use std::path::{Path, PathBuf};
+use crate::common_messages::{Issue, IssueKind, Messages};
+use regex::{Captures, Regex};
+use std::env;
+use std::fs;
+use std::fs::File;
+use std::io;
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
 use std::time::SystemTime;
 
 #[derive(Default)]
 pub struct Directories {
     pub excluded_directories: Vec<PathBuf>,
     pub included_directories: Vec<PathBuf>,
+    /// Files which are checked instead of files found in included directories
+    pub file_list: Option<Vec<PathBuf>>,
 }
 impl Directories {
     pub fn new() -> Self {
@@ -14,50 +22,72 @@ impl Directories {
     }
 
     /// Setting included directories, at least one must be provided
+    /// Relative paths, ~ and environment variables($VAR or ${VAR}) are expanded to absolute paths
     pub fn set_included_directory(&mut self, included_directory: String, text_messages: &mut Messages) -> bool {
         let start_time: SystemTime = SystemTime::now();
 
         if included_directory.is_empty() {
-            text_messages.errors.push("At least one directory must be provided".to_string());
+            text_messages.errors.push(Issue::new(IssueKind::InvalidConfiguration, "At least one directory must be provided".to_string()));
             return false;
         }
 
         let included_directory = included_directory.replace("\"", "");
-        let directories: Vec<_> = included_directory.split(',').map(|dir| dir.trim()).filter(|dir| !dir.is_empty()).map(PathBuf::from).collect();
+        let directories: Vec<&str> = included_directory.split(',').map(|dir| dir.trim()).filter(|dir| !dir.is_empty()).collect();
 
         let mut checked_directories: Vec<PathBuf> = Vec::new();
         for directory in directories {
-            if directory.to_string_lossy().contains('*') {
-                text_messages.warnings.push(format!("Included Directory Warning: Wildcards in path are not supported, ignoring {}", directory.display()));
-                continue;
-            }
-            if directory.is_relative() {
-                text_messages.warnings.push(format!("Included Directory Warning: Relative path are not supported, ignoring {}", directory.display()));
+            if directory.contains('*') {
+                text_messages
+                    .warnings
+                    .push(Issue::new(IssueKind::InvalidConfiguration, format!("Included Directory Warning: Wildcards in path are not supported, ignoring {}", directory)));
                 continue;
             }
+            let directory = match expand_path(directory) {
+                Ok(t) => t,
+                Err(e) => {
+                    text_messages.warnings.push(Issue::new(IssueKind::InvalidConfiguration, format!("Included Directory Warning: {}, ignoring {}", e, directory)));
+                    continue;
+                }
+            };
             if !directory.exists() {
-                text_messages.warnings.push(format!("Included Directory Warning: Provided folder path must exits, ignoring {}", directory.display()));
+                text_messages
+                    .warnings
+                    .push(Issue::new(IssueKind::InvalidConfiguration, format!("Included Directory Warning: Provided folder path must exits, ignoring {}", directory.display())));
                 continue;
             }
             if !directory.is_dir() {
-                text_messages.warnings.push(format!("Included Directory Warning: Provided path must point at the directory, ignoring {}", directory.display()));
+                text_messages.warnings.push(Issue::new(
+                    IssueKind::InvalidConfiguration,
+                    format!("Included Directory Warning: Provided path must point at the directory, ignoring {}", directory.display()),
+                ));
                 continue;
             }
+            let directory = match canonicalize_path(&directory) {
+                Ok(t) => t,
+                Err(e) => {
+                    text_messages
+                        .warnings
+                        .push(Issue::new(IssueKind::InvalidConfiguration, format!("Included Directory Warning: Failed to resolve path {} - {}", directory.display(), e)));
+                    continue;
+                }
+            };
             checked_directories.push(directory);
         }
 
         if checked_directories.is_empty() {
-            text_messages.errors.push("Included Directory ERROR: Not found even one correct path to included which is required.".to_string());
+            text_messages
+                .errors
+                .push(Issue::new(IssueKind::InvalidConfiguration, "Included Directory ERROR: Not found even one correct path to included which is required.".to_string()));
             return false;
         }
 
         self.included_directories = checked_directories;
 
-        Common::print_time(start_time, SystemTime::now(), "set_included_directory".to_string());
+        Common::log_time(start_time, "set_included_directory");
         true
     }
 
-    /// Setting absolute path to exclude
+    /// Setting paths to exclude, which are expanded the same way as included directories
     pub fn set_excluded_directory(&mut self, excluded_directory: String, text_messages: &mut Messages) {
         let start_time: SystemTime = SystemTime::now();
         if excluded_directory.is_empty() {
@@ -66,36 +96,113 @@ impl Directories {
 
         let excluded_directory = excluded_directory.replace("\"", "");
 
-        let directories: Vec<PathBuf> = excluded_directory.split(',').map(|dir| dir.trim()).filter(|dir| !dir.is_empty()).map(PathBuf::from).collect();
+        let directories: Vec<&str> = excluded_directory.split(',').map(|dir| dir.trim()).filter(|dir| !dir.is_empty()).collect();
 
         let mut checked_directories: Vec<PathBuf> = Vec::new();
         for directory in directories {
-            let directory_as_string = directory.to_string_lossy();
-            if directory_as_string == "/" {
-                text_messages.errors.push("Excluded Directory ERROR: Excluding / is pointless, because it means that no files will be scanned.".to_string());
-                break;
-            }
-            if directory_as_string.contains('*') {
-                text_messages.warnings.push(format!("Excluded Directory Warning: Wildcards in path are not supported, ignoring {}", directory.display()));
-                continue;
-            }
-            if directory.is_relative() {
-                text_messages.warnings.push(format!("Excluded Directory Warning: Relative path are not supported, ignoring {}", directory.display()));
+            if directory.contains('*') {
+                text_messages
+                    .warnings
+                    .push(Issue::new(IssueKind::InvalidConfiguration, format!("Excluded Directory Warning: Wildcards in path are not supported, ignoring {}", directory)));
                 continue;
             }
+            let directory = match expand_path(directory) {
+                Ok(t) => t,
+                Err(e) => {
+                    text_messages.warnings.push(Issue::new(IssueKind::InvalidConfiguration, format!("Excluded Directory Warning: {}, ignoring {}", e, directory)));
+                    continue;
+                }
+            };
             if !directory.exists() {
                 // text_messages.warnings.push(format!("Excluded Directory Warning: Provided folder path must exits, ignoring {}", directory.display()));
                 continue;
             }
             if !directory.is_dir() {
-                text_messages.warnings.push(format!("Excluded Directory Warning: Provided path must point at the directory, ignoring {}", directory.display()));
+                text_messages.warnings.push(Issue::new(
+                    IssueKind::InvalidConfiguration,
+                    format!("Excluded Directory Warning: Provided path must point at the directory, ignoring {}", directory.display()),
+                ));
                 continue;
             }
+            let directory = match canonicalize_path(&directory) {
+                Ok(t) => t,
+                Err(e) => {
+                    text_messages
+                        .warnings
+                        .push(Issue::new(IssueKind::InvalidConfiguration, format!("Excluded Directory Warning: Failed to resolve path {} - {}", directory.display(), e)));
+                    continue;
+                }
+            };
+            if directory.parent().is_none() {
+                text_messages.errors.push(Issue::new(
+                    IssueKind::InvalidConfiguration,
+                    "Excluded Directory ERROR: Excluding / is pointless, because it means that no files will be scanned.".to_string(),
+                ));
+                break;
+            }
             checked_directories.push(directory);
         }
         self.excluded_directories = checked_directories;
 
-        Common::print_time(start_time, SystemTime::now(), "set_excluded_directory".to_string());
+        Common::log_time(start_time, "set_excluded_directory");
+    }
+
+    /// Reads list of files to check from file or from standard input when "-" is given
+    /// Paths are separated by new lines or by NUL characters(like in output of find -print0) when null_separated is set
+    pub fn set_file_list(&mut self, file_list: String, null_separated: bool, text_messages: &mut Messages) -> bool {
+        let start_time: SystemTime = SystemTime::now();
+        if file_list.is_empty() {
+            return true;
+        }
+
+        let result = if file_list == "-" {
+            self.load_file_list(io::stdin().lock(), null_separated, text_messages)
+        } else {
+            match File::open(&file_list) {
+                Ok(t) => self.load_file_list(t, null_separated, text_messages),
+                Err(e) => {
+                    text_messages.errors.push(Issue::new(IssueKind::ReadFailed, "Failed to open file list").with_path(&file_list).with_io_error(e));
+                    false
+                }
+            }
+        };
+
+        Common::log_time(start_time, "set_file_list");
+        result
+    }
+
+    /// Loads list of files from any source, relative paths are joined with current working directory
+    pub fn load_file_list(&mut self, mut reader: impl Read, null_separated: bool, text_messages: &mut Messages) -> bool {
+        let mut content: Vec<u8> = Vec::new();
+        if let Err(e) = reader.read_to_end(&mut content) {
+            text_messages.errors.push(Issue::new(IssueKind::ReadFailed, "Failed to read file list").with_io_error(e));
+            return false;
+        }
+
+        let current_dir = env::current_dir().ok();
+        let separator = if null_separated { b'\0' } else { b'\n' };
+        let mut files: Vec<PathBuf> = Vec::new();
+        for item in content.split(|e| *e == separator) {
+            let item = if !null_separated && item.ends_with(b"\r") { &item[..item.len() - 1] } else { item };
+            if item.is_empty() {
+                continue;
+            }
+            let path = path_from_bytes(item);
+            let path: PathBuf = match &current_dir {
+                Some(current_dir) if path.is_relative() => current_dir.join(path),
+                _ => path,
+            };
+            // Removes ./ parts, which are common in output of find
+            files.push(path.components().filter(|e| *e != Component::CurDir).collect());
+        }
+        files.sort();
+        files.dedup();
+
+        if files.is_empty() {
+            text_messages.warnings.push(Issue::new(IssueKind::InvalidConfiguration, "File list doesn't contain any path"));
+        }
+        self.file_list = Some(files);
+        true
     }
 
     /// Remove unused entries when included or excluded overlaps with each other or are duplicated etc.
@@ -198,8 +305,9 @@ impl Directories {
         optimized_excluded = Vec::new();
 
         // Excluded paths must are inside included path, because
+        // Files from file list may be placed anywhere, so all excluded directories are kept
         for ed in &self.excluded_directories {
-            let mut is_inside: bool = false;
+            let mut is_inside: bool = self.file_list.is_some();
             for id in &self.included_directories {
                 if ed.starts_with(id) {
                     is_inside = true;
@@ -213,15 +321,17 @@ impl Directories {
 
         self.excluded_directories = optimized_excluded;
 
-        if self.included_directories.is_empty() {
-            text_messages.errors.push("Optimize Directories ERROR: Excluded directories overlaps all included directories.".to_string());
+        if self.included_directories.is_empty() && self.file_list.is_none() {
+            text_messages
+                .errors
+                .push(Issue::new(IssueKind::InvalidConfiguration, "Optimize Directories ERROR: Excluded directories overlaps all included directories.".to_string()));
             return false;
         }
 
         // Not needed, but better is to have sorted everything
         self.excluded_directories.sort();
         self.included_directories.sort();
-        Common::print_time(start_time, SystemTime::now(), "optimize_directories".to_string());
+        Common::log_time(start_time, "optimize_directories");
         true
     }
 
@@ -233,4 +343,120 @@ impl Directories {
         // We're assuming that `excluded_directories` are already normalized
         self.excluded_directories.iter().any(|p| p.as_path() == path)
     }
+
+    /// Checks whether a specified path is placed inside any excluded directory
+    pub fn is_inside_excluded(&self, path: impl AsRef<Path>) -> bool {
+        let path = path.as_ref();
+        #[cfg(target_family = "windows")]
+        let path = Common::normalize_windows_path(path);
+        self.excluded_directories.iter().any(|p| path.starts_with(p))
+    }
+}
+
+/// Expands ~ and environment variables in path and joins relative paths with current working directory
+/// Not set variables are left unchanged, because $ may be part of real name of folder, e.g. C:\$Recycle.Bin
+fn expand_path(path: &str) -> Result<PathBuf, String> {
+    let mut path: String = path.to_string();
+
+    if path == "~" || path.starts_with("~/") || (cfg!(target_family = "windows") && path.starts_with("~\\")) {
+        let home_variable = if cfg!(target_family = "windows") { "USERPROFILE" } else { "HOME" };
+        let home = match env::var(home_variable) {
+            Ok(t) => t,
+            Err(_) => return Err(format!("Cannot expand ~, because environment variable {} is not set", home_variable)),
+        };
+        path.replace_range(..1, &home);
+    }
+
+    let path = Regex::new(r"\$\{(\w+)\}|\$(\w+)")
+        .unwrap()
+        .replace_all(&path, |captures: &Captures| {
+            let name = captures.get(1).or_else(|| captures.get(2)).unwrap().as_str();
+            env::var(name).unwrap_or_else(|_| captures[0].to_string())
+        })
+        .to_string();
+
+    let path = PathBuf::from(path);
+    if path.is_relative() {
+        return match env::current_dir() {
+            Ok(t) => Ok(t.join(path)),
+            Err(e) => Err(format!("Cannot resolve relative path, because current directory is not available({})", e)),
+        };
+    }
+    Ok(path)
+}
+
+#[cfg(target_family = "unix")]
+fn path_from_bytes(bytes: &[u8]) -> PathBuf {
+    use std::os::unix::ffi::OsStrExt;
+    PathBuf::from(std::ffi::OsStr::from_bytes(bytes))
+}
+
+#[cfg(not(target_family = "unix"))]
+fn path_from_bytes(bytes: &[u8]) -> PathBuf {
+    PathBuf::from(String::from_utf8_lossy(bytes).to_string())
+}
+
+/// Resolves .. and symbolic links, so the same folder is always represented by the same path
+pub(crate) fn canonicalize_path(path: &Path) -> std::io::Result<PathBuf> {
+    let path = fs::canonicalize(path)?;
+
+    // Windows returns paths with \\?\ prefix, which are not handled by other functions
+    #[cfg(target_family = "windows")]
+    let path = match path.to_str().and_then(|e| e.strip_prefix(r"\\?\")) {
+        Some(t) if !t.starts_with("UNC") => PathBuf::from(t),
+        _ => path,
+    };
+
+    Ok(path)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::common_directory::{expand_path, Directories};
+    use crate::common_messages::Messages;
+    use std::env;
+    use std::fs;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_expand_path() {
+        env::set_var("CZKAWKA_TEST_FOLDER", "folder");
+        assert_eq!(expand_path("/home/$CZKAWKA_TEST_FOLDER/a").unwrap(), PathBuf::from("/home/folder/a"));
+        assert_eq!(expand_path("/home/${CZKAWKA_TEST_FOLDER}_2").unwrap(), PathBuf::from("/home/folder_2"));
+        assert_eq!(expand_path("/mnt/backup/$CZKAWKA_TEST_NOT_EXISTENT_VARIABLE").unwrap(), PathBuf::from("/mnt/backup/$CZKAWKA_TEST_NOT_EXISTENT_VARIABLE"));
+        assert_eq!(expand_path("/mnt/${CZKAWKA_TEST_NOT_EXISTENT_VARIABLE}/$").unwrap(), PathBuf::from("/mnt/${CZKAWKA_TEST_NOT_EXISTENT_VARIABLE}/$"));
+        assert_eq!(expand_path("relative/path").unwrap(), env::current_dir().unwrap().join("relative/path"));
+        #[cfg(target_family = "unix")]
+        {
+            let home = PathBuf::from(env::var("HOME").unwrap());
+            assert_eq!(expand_path("~/Pulpit").unwrap(), home.join("Pulpit"));
+            assert_eq!(expand_path("/a/~/b").unwrap(), PathBuf::from("/a/~/b"));
+        }
+    }
+
+    #[test]
+    #[cfg(target_family = "unix")]
+    fn test_canonicalized_directories() {
+        let root: PathBuf = fs::canonicalize(env::temp_dir()).unwrap().join(format!("czkawka_test_canonicalized_directories_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("folder/inner")).unwrap();
+        std::os::unix::fs::symlink(root.join("folder"), root.join("link")).unwrap();
+
+        let mut text_messages = Messages::new();
+        let mut directories = Directories::new();
+        // Folder with $ in name, which isn't environment variable
+        fs::create_dir_all(root.join("$Recycle.Bin")).unwrap();
+        assert!(directories.set_included_directory(format!("{}/$Recycle.Bin", root.display()), &mut text_messages));
+        assert_eq!(directories.included_directories, vec![root.join("$Recycle.Bin")]);
+
+        let mut directories = Directories::new();
+        let included = format!("{0}/folder,{0}/link,{0}/folder/inner/..,{0}/link/inner", root.display());
+        assert!(directories.set_included_directory(included, &mut text_messages));
+        directories.set_excluded_directory(format!("{}/link/inner", root.display()), &mut text_messages);
+        assert!(directories.optimize_directories(true, &mut text_messages));
+        assert_eq!(directories.included_directories, vec![root.join("folder")]);
+        assert_eq!(directories.excluded_directories, vec![root.join("folder/inner")]);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
 }
@@ -1,51 +1,194 @@
 use crate::common::Common;
-use crate::common_messages::Messages;
+use crate::common_messages::{Issue, IssueKind, Messages};
+use std::collections::BTreeMap;
 use std::time::SystemTime;
 
-#[derive(Default)]
+/// Groups available by default, user can override them or add new ones
+const DEFAULT_EXTENSION_GROUPS: [(&str, &str); 4] = [
+    ("IMAGE", "jpg,kra,gif,png,bmp,tiff,webp,hdr,svg"),
+    ("VIDEO", "mp4,flv,mkv,webm,vob,ogv,gifv,avi,mov,wmv,mpg,m4v,m4p,mpeg,3gp"),
+    ("MUSIC", "mp3,flac,ogg,tta,wma,webm"),
+    ("TEXT", "txt,doc,docx,odt,rtf"),
+];
+
 pub struct Extensions {
+    /// Allowed extensions(lowercase, without leading dot), when empty all extensions are allowed
     pub file_extensions: Vec<String>,
+    /// Extensions which are always skipped, even if they are allowed
+    pub excluded_extensions: Vec<String>,
+    /// Named groups of extensions, which may be used in allowed and excluded extensions instead of listing them one by one
+    pub extension_groups: BTreeMap<String, Vec<String>>,
+    allowed_items: Vec<String>,
+    excluded_items: Vec<String>,
+}
+
+impl Default for Extensions {
+    fn default() -> Self {
+        let mut extension_groups = BTreeMap::new();
+        for (name, extensions) in DEFAULT_EXTENSION_GROUPS.iter() {
+            extension_groups.insert(name.to_string(), extensions.split(',').map(String::from).collect());
+        }
+        Self {
+            file_extensions: Vec::new(),
+            excluded_extensions: Vec::new(),
+            extension_groups,
+            allowed_items: Vec::new(),
+            excluded_items: Vec::new(),
+        }
+    }
 }
 
 impl Extensions {
     pub fn new() -> Self {
         Default::default()
     }
-    /// List of allowed extensions, only files with this extensions will be checking if are duplicates
-    /// After, extensions cannot contains any dot, commas etc.
-    pub fn set_allowed_extensions(&mut self, mut allowed_extensions: String, text_messages: &mut Messages) {
+    /// List of allowed extensions, only files with this extensions will be checked
+    /// Extensions may contain dots inside like tar.gz, names of groups like IMAGE are replaced by their extensions
+    pub fn set_allowed_extensions(&mut self, allowed_extensions: String, text_messages: &mut Messages) {
         let start_time: SystemTime = SystemTime::now();
         if allowed_extensions.is_empty() {
             return;
         }
-        allowed_extensions = allowed_extensions.replace("IMAGE", "jpg,kra,gif,png,bmp,tiff,webp,hdr,svg");
-        allowed_extensions = allowed_extensions.replace("VIDEO", "mp4,flv,mkv,webm,vob,ogv,gifv,avi,mov,wmv,mpg,m4v,m4p,mpeg,3gp");
-        allowed_extensions = allowed_extensions.replace("MUSIC", "mp3,flac,ogg,tta,wma,webm");
-        allowed_extensions = allowed_extensions.replace("TEXT", "txt,doc,docx,odt,rtf");
+        self.allowed_items = parse_items(&allowed_extensions, text_messages);
+        self.update_extensions();
 
-        let extensions: Vec<String> = allowed_extensions.split(',').map(String::from).collect();
-        for mut extension in extensions {
-            if extension == "" || extension.replace('.', "").trim() == "" {
-                continue;
-            }
+        if self.file_extensions.is_empty() {
+            text_messages.messages.push("No valid extensions were provided, so allowing all extensions by default.".to_string());
+        }
+        Common::log_time(start_time, "set_allowed_extensions");
+    }
 
-            if extension.starts_with('.') {
-                extension = extension[1..].to_string();
-            }
+    /// List of extensions which are never checked, uses same format as allowed extensions
+    pub fn set_excluded_extensions(&mut self, excluded_extensions: String, text_messages: &mut Messages) {
+        if excluded_extensions.is_empty() {
+            return;
+        }
+        self.excluded_items = parse_items(&excluded_extensions, text_messages);
+        self.update_extensions();
+    }
 
-            if extension[1..].contains('.') {
-                text_messages.warnings.push(".".to_string() + extension.as_str() + " is not valid extension(valid extension doesn't have dot inside)");
+    /// Groups in format NAME=ext1,ext2 separated by semicolons or new lines, group with already existing name replaces it
+    pub fn set_extension_groups(&mut self, extension_groups: String, text_messages: &mut Messages) {
+        for group in extension_groups.split(|c| c == ';' || c == '\n') {
+            if group.trim().is_empty() {
+                continue;
+            }
+            let (name, extensions) = match group.find('=') {
+                Some(index) => (group[..index].trim(), &group[index + 1..]),
+                None => {
+                    text_messages
+                        .warnings
+                        .push(Issue::new(IssueKind::InvalidConfiguration, format!("{} is not valid extension group(valid group looks like NAME=ext1,ext2)", group.trim())));
+                    continue;
+                }
+            };
+            if name.is_empty() || !name.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_') {
+                text_messages.warnings.push(Issue::new(
+                    IssueKind::InvalidConfiguration,
+                    format!("{} is not valid name of extension group(only uppercase letters, digits and _ are allowed)", name),
+                ));
                 continue;
             }
 
-            if !self.file_extensions.contains(&extension.trim().to_string()) {
-                self.file_extensions.push(extension.trim().to_string());
+            let extensions: Vec<String> = parse_items(extensions, text_messages).iter().map(|e| e.to_lowercase()).collect();
+            self.extension_groups.insert(name.to_string(), extensions);
+        }
+        self.update_extensions();
+    }
+
+    /// Checks if file with given lowercase name is allowed by allowed and excluded extensions
+    pub fn is_allowed(&self, file_name_lowercase: &str) -> bool {
+        if self.excluded_extensions.iter().any(|e| has_extension(file_name_lowercase, e)) {
+            return false;
+        }
+        self.file_extensions.is_empty() || self.file_extensions.iter().any(|e| has_extension(file_name_lowercase, e))
+    }
+
+    /// Groups may be changed after setting extensions, so they are expanded again after each change
+    fn update_extensions(&mut self) {
+        self.file_extensions = self.expand_items(&self.allowed_items);
+        self.excluded_extensions = self.expand_items(&self.excluded_items);
+    }
+
+    fn expand_items(&self, items: &[String]) -> Vec<String> {
+        let mut extensions: Vec<String> = Vec::new();
+        for item in items {
+            let group_extensions = match self.extension_groups.get(item) {
+                Some(t) => t.clone(),
+                None => vec![item.to_lowercase()],
+            };
+            for extension in group_extensions {
+                if !extensions.contains(&extension) {
+                    extensions.push(extension);
+                }
             }
         }
+        extensions
+    }
+}
 
-        if self.file_extensions.is_empty() {
-            text_messages.messages.push("No valid extensions were provided, so allowing all extensions by default.".to_string());
+/// Checks if lowercase file name ends with extension(without leading dot), so archive.tar.gz has both tar.gz and gz extensions
+pub fn has_extension(file_name_lowercase: &str, extension: &str) -> bool {
+    file_name_lowercase.len() > extension.len() && file_name_lowercase.ends_with(extension) && file_name_lowercase.as_bytes()[file_name_lowercase.len() - extension.len() - 1] == b'.'
+}
+
+/// Splits list of extensions or group names by commas and removes leading dots
+fn parse_items(items: &str, text_messages: &mut Messages) -> Vec<String> {
+    let mut parsed: Vec<String> = Vec::new();
+    for item in items.split(',') {
+        let item = item.trim().trim_start_matches('.');
+        if item.is_empty() {
+            continue;
+        }
+        if item.ends_with('.') || item.contains("..") || item.contains(|c: char| c == '/' || c == '\\' || c.is_whitespace()) {
+            text_messages.warnings.push(Issue::new(IssueKind::InvalidConfiguration, format!(".{} is not valid extension", item)));
+            continue;
         }
-        Common::print_time(start_time, SystemTime::now(), "set_allowed_extensions".to_string());
+        if !parsed.iter().any(|e| e == item) {
+            parsed.push(item.to_string());
+        }
+    }
+    parsed
+}
+
+#[cfg(test)]
+mod test {
+    use crate::common_extensions::Extensions;
+    use crate::common_messages::Messages;
+
+    #[test]
+    fn test_allowed_and_excluded_extensions() {
+        let mut text_messages = Messages::new();
+        let mut extensions = Extensions::new();
+        assert!(extensions.is_allowed("file.iso"));
+
+        extensions.set_allowed_extensions("TEXT, .Tar.Gz,zip".to_string(), &mut text_messages);
+        assert!(extensions.is_allowed("notes.txt"));
+        assert!(extensions.is_allowed("backup.tar.gz"));
+        assert!(!extensions.is_allowed("backup.gz"));
+        assert!(!extensions.is_allowed("tar.gz"));
+        assert!(!extensions.is_allowed("file.xzip"));
+
+        extensions.set_excluded_extensions("docx,txt".to_string(), &mut text_messages);
+        assert!(!extensions.is_allowed("notes.txt"));
+        assert!(extensions.is_allowed("notes.doc"));
+        assert!(text_messages.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_extension_groups() {
+        let mut text_messages = Messages::new();
+        let mut extensions = Extensions::new();
+        extensions.set_excluded_extensions("DISK,IMAGE".to_string(), &mut text_messages);
+        assert!(extensions.is_allowed("debian.iso"));
+        assert!(!extensions.is_allowed("photo.jpg"));
+
+        // Groups may be set after extensions and override default ones
+        extensions.set_extension_groups("DISK=iso,vmdk,QCOW2;IMAGE=png\ninvalid;lower=abc".to_string(), &mut text_messages);
+        assert!(!extensions.is_allowed("debian.iso"));
+        assert!(!extensions.is_allowed("vm.qcow2"));
+        assert!(extensions.is_allowed("photo.jpg"));
+        assert!(!extensions.is_allowed("photo.png"));
+        assert_eq!(text_messages.warnings.len(), 2);
     }
 }
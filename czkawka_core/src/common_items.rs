@@ -1,19 +1,36 @@
 use crate::common::Common;
-use crate::common_messages::Messages;
+use crate::common_messages::{Issue, IssueKind, Messages};
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use regex::RegexSet;
 use std::path::Path;
 use std::time::SystemTime;
 
-#[derive(Default)]
+/// Expressions which starts with this prefix are treated as regular expressions instead of wildcards
+pub const REGEX_PREFIX: &str = "regex:";
+
 pub struct ExcludedItems {
     pub items: Vec<String>,
+    glob_set: GlobSet,
+    regex_set: RegexSet,
+}
+
+impl Default for ExcludedItems {
+    fn default() -> Self {
+        Self {
+            items: Vec::new(),
+            glob_set: GlobSet::empty(),
+            regex_set: RegexSet::empty(),
+        }
+    }
 }
 
 impl ExcludedItems {
     pub fn new() -> Self {
         Default::default()
     }
-    /// Setting excluded items which needs to contains * wildcard
-    /// Are a lot of slower than absolute path, so it should be used to heavy
+    /// Setting excluded items, which are compiled once, so checking them later is fast
+    /// Wildcards supports `*`(any characters, also path separators), `?`, `[abc]`, `[!a-z]`, `{a,b}` and `**/`(zero or more folders)
+    /// Expressions prefixed with "regex:" are full regular expressions matched against the whole path
     pub fn set_excluded_items(&mut self, mut excluded_items: String, text_messages: &mut Messages) {
         let start_time: SystemTime = SystemTime::now();
 
@@ -22,29 +39,65 @@ impl ExcludedItems {
         }
 
         excluded_items = excluded_items.replace("\"", "");
-        let expressions: Vec<String> = excluded_items.split(',').map(String::from).collect();
+        let expressions: Vec<String> = split_expressions(&excluded_items);
         let mut checked_expressions: Vec<String> = Vec::new();
+        let mut glob_set_builder = GlobSetBuilder::new();
+        let mut regexes: Vec<String> = Vec::new();
 
         for expression in expressions {
             let expression: String = expression.trim().to_string();
 
-            if expression == "" {
+            if expression.is_empty() {
                 continue;
             }
             if expression == "DEFAULT" {
                 // TODO add more files by default
-                checked_expressions.push("*/.git/*".to_string());
+                let expression = "*/.git/*".to_string();
+                glob_set_builder.add(build_glob(&expression).unwrap());
+                checked_expressions.push(expression);
                 continue;
             }
-            if !expression.contains('*') {
-                text_messages.warnings.push("Excluded Items Warning: Wildcard * is required in expression, ignoring ".to_string() + expression.as_str());
-                continue;
+
+            if let Some(regex) = expression.strip_prefix(REGEX_PREFIX) {
+                if let Err(e) = regex::Regex::new(regex) {
+                    text_messages.errors.push(Issue::new(IssueKind::InvalidConfiguration, format!("Excluded Items Error: Invalid regular expression {} - {}", regex, e)));
+                    continue;
+                }
+                regexes.push(regex.to_string());
+            } else {
+                match build_glob(&expression) {
+                    Ok(glob) => {
+                        glob_set_builder.add(glob);
+                    }
+                    Err(e) => {
+                        text_messages
+                            .errors
+                            .push(Issue::new(IssueKind::InvalidConfiguration, format!("Excluded Items Error: Invalid expression {} - {}", expression, e.kind())));
+                        continue;
+                    }
+                }
             }
 
             checked_expressions.push(expression);
         }
+
+        // Each pattern was validated above, so building sets shouldn't fail
+        self.glob_set = match glob_set_builder.build() {
+            Ok(t) => t,
+            Err(e) => {
+                text_messages.errors.push(Issue::new(IssueKind::InvalidConfiguration, format!("Excluded Items Error: Failed to compile expressions - {}", e)));
+                GlobSet::empty()
+            }
+        };
+        self.regex_set = match RegexSet::new(&regexes) {
+            Ok(t) => t,
+            Err(e) => {
+                text_messages.errors.push(Issue::new(IssueKind::InvalidConfiguration, format!("Excluded Items Error: Failed to compile regular expressions - {}", e)));
+                RegexSet::empty()
+            }
+        };
         self.items = checked_expressions;
-        Common::print_time(start_time, SystemTime::now(), "set_excluded_items".to_string());
+        Common::log_time(start_time, "set_excluded_items");
     }
 
     /// Checks whether a specified path is excluded from searching
@@ -52,11 +105,109 @@ impl ExcludedItems {
         #[cfg(target_family = "windows")]
         let path = Common::normalize_windows_path(path);
 
-        for expression in &self.items {
-            if Common::regex_check(expression, &path) {
-                return true;
-            }
+        let path = path.as_ref();
+        if self.glob_set.is_match(path) {
+            return true;
+        }
+        if !self.regex_set.is_empty() && self.regex_set.is_match(&path.to_string_lossy()) {
+            return true;
         }
         false
     }
 }
+
+/// Splits expressions by commas, but not these which are inside {} alternatives of wildcards or {m,n} repetitions of regular expressions
+fn split_expressions(excluded_items: &str) -> Vec<String> {
+    let mut expressions: Vec<String> = Vec::new();
+    let mut current: String = String::new();
+    let mut braces_depth: usize = 0;
+    // Escaped braces in regular expressions are literal characters
+    let mut escaped = false;
+
+    for character in excluded_items.chars() {
+        match character {
+            ',' if braces_depth == 0 => {
+                expressions.push(current);
+                current = String::new();
+                escaped = false;
+                continue;
+            }
+            '{' if !escaped => braces_depth += 1,
+            '}' if !escaped => braces_depth = braces_depth.saturating_sub(1),
+            _ => (),
+        }
+        escaped = !escaped && character == '\\' && current.trim_start().starts_with(REGEX_PREFIX);
+        current.push(character);
+    }
+    expressions.push(current);
+    expressions
+}
+
+fn build_glob(expression: &str) -> Result<globset::Glob, globset::Error> {
+    // Paths are normalized on Windows to lowercase with / separators, so expressions must be too
+    #[cfg(target_family = "windows")]
+    let expression = &expression.replace('\\', "/").to_lowercase();
+
+    GlobBuilder::new(expression).literal_separator(false).backslash_escape(cfg!(not(target_family = "windows"))).build()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::common_items::ExcludedItems;
+    use crate::common_messages::Messages;
+
+    fn excluded_items(expressions: &str) -> (ExcludedItems, Messages) {
+        let mut text_messages = Messages::new();
+        let mut excluded_items = ExcludedItems::new();
+        excluded_items.set_excluded_items(expressions.to_string(), &mut text_messages);
+        (excluded_items, text_messages)
+    }
+
+    #[test]
+    fn test_wildcards() {
+        let (items, _) = excluded_items("*.tmp?,**/node_modules/**,*/[Tt]humbs.db,*/cache/{a,b}/*");
+        assert!(items.is_excluded("/home/rafal/file.tmp1"));
+        assert!(!items.is_excluded("/home/rafal/file.tmp"));
+        assert!(items.is_excluded("/home/rafal/project/node_modules/a/b.js"));
+        assert!(items.is_excluded("/home/rafal/Thumbs.db"));
+        assert!(items.is_excluded("/home/rafal/thumbs.db"));
+        assert!(!items.is_excluded("/home/rafal/THUMBS.db"));
+        assert!(items.is_excluded("/home/rafal/cache/b/file"));
+        assert!(!items.is_excluded("/home/rafal/cache/c/file"));
+        assert_eq!(items.items.len(), 4);
+    }
+
+    #[test]
+    fn test_old_expressions() {
+        let (items, _) = excluded_items("DEFAULT,*/lost+found/*");
+        assert!(items.is_excluded("/home/rafal/.git/config"));
+        assert!(items.is_excluded("/home/rafal/project/.git/objects/ab/cd"));
+        assert!(items.is_excluded("/lost+found/file"));
+        assert!(!items.is_excluded("/home/rafal/git/config"));
+    }
+
+    #[test]
+    fn test_regex() {
+        let (items, _) = excluded_items("regex:^/home/[a-z]+/Downloads/.*\\.iso$");
+        assert!(items.is_excluded("/home/rafal/Downloads/ubuntu.iso"));
+        assert!(!items.is_excluded("/home/Rafal/Downloads/ubuntu.iso"));
+        assert!(!items.is_excluded("/mnt/home/rafal/Downloads/ubuntu.iso"));
+
+        // Commas inside of {m,n} don't split expression
+        let (items, text_messages) = excluded_items("regex:/IMG_\\d{2,3}\\.jpg$,regex:/\\{,*/cache/*");
+        assert!(text_messages.errors.is_empty());
+        assert_eq!(items.items.len(), 3);
+        assert!(items.is_excluded("/home/rafal/IMG_123.jpg"));
+        assert!(!items.is_excluded("/home/rafal/IMG_1.jpg"));
+        assert!(items.is_excluded("/home/rafal/{file"));
+        assert!(items.is_excluded("/home/rafal/cache/file"));
+    }
+
+    #[test]
+    fn test_invalid_expressions() {
+        let (items, text_messages) = excluded_items("*/[abc/*,regex:(abc,*/valid/*");
+        assert_eq!(text_messages.errors.len(), 2);
+        assert_eq!(items.items, vec!["*/valid/*".to_string()]);
+        assert!(items.is_excluded("/home/valid/file"));
+    }
+}
@@ -1,14 +1,107 @@
-#[derive(Default)]
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Category of warning or error, allows frontends to group and count them
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum IssueKind {
+    PermissionDenied,
+    NotFound,
+    ReadFailed,
+    InvalidMetadata,
+    DeleteFailed,
+    SaveFailed,
+    InvalidConfiguration,
+}
+
+impl fmt::Display for IssueKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            IssueKind::PermissionDenied => "Permission denied",
+            IssueKind::NotFound => "Not found",
+            IssueKind::ReadFailed => "Read failed",
+            IssueKind::InvalidMetadata => "Invalid metadata",
+            IssueKind::DeleteFailed => "Delete failed",
+            IssueKind::SaveFailed => "Save failed",
+            IssueKind::InvalidConfiguration => "Invalid configuration",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Warning or error with path of affected file or folder and error returned by OS if available
+#[derive(Debug)]
+pub struct Issue {
+    pub kind: IssueKind,
+    pub description: String,
+    pub path: Option<PathBuf>,
+    pub io_error: Option<io::Error>,
+}
+
+impl Issue {
+    pub fn new(kind: IssueKind, description: impl Into<String>) -> Self {
+        Self {
+            kind,
+            description: description.into(),
+            path: None,
+            io_error: None,
+        }
+    }
+
+    pub fn with_path(mut self, path: impl AsRef<Path>) -> Self {
+        self.path = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Failed reads are categorized more precisely when OS reports missing permissions or file
+    pub fn with_io_error(mut self, io_error: io::Error) -> Self {
+        if self.kind == IssueKind::ReadFailed || self.kind == IssueKind::InvalidMetadata {
+            match io_error.kind() {
+                io::ErrorKind::PermissionDenied => self.kind = IssueKind::PermissionDenied,
+                io::ErrorKind::NotFound => self.kind = IssueKind::NotFound,
+                _ => (),
+            }
+        }
+        self.io_error = Some(io_error);
+        self
+    }
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.description)?;
+        if let Some(path) = &self.path {
+            write!(f, " {}", path.display())?;
+        }
+        if let Some(io_error) = &self.io_error {
+            write!(f, " ({})", io_error)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default)]
 pub struct Messages {
     pub messages: Vec<String>,
-    pub warnings: Vec<String>,
-    pub errors: Vec<String>,
+    pub warnings: Vec<Issue>,
+    pub errors: Vec<Issue>,
 }
 
 impl Messages {
     pub fn new() -> Self {
         Default::default()
     }
+
+    /// Number of warnings and errors of each kind
+    pub fn count_by_kind(&self) -> BTreeMap<IssueKind, usize> {
+        let mut counts: BTreeMap<IssueKind, usize> = BTreeMap::new();
+        for issue in self.warnings.iter().chain(self.errors.iter()) {
+            *counts.entry(issue.kind).or_insert(0) += 1;
+        }
+        counts
+    }
+
     pub fn print_messages(&self) {
         if !self.messages.is_empty() {
             println!("-------------------------------MESSAGES--------------------------------");
@@ -35,5 +128,39 @@ impl Messages {
             }
             println!("----------------------------END OF ERRORS------------------------------");
         }
+
+        let counts = self.count_by_kind();
+        if !counts.is_empty() {
+            println!("--------------------------------SUMMARY--------------------------------");
+            for (kind, count) in &counts {
+                println!("{} - {}", kind, count);
+            }
+            println!("----------------------------END OF SUMMARY-----------------------------");
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::common_messages::{Issue, IssueKind, Messages};
+    use std::io;
+
+    #[test]
+    fn test_issues() {
+        let mut text_messages = Messages::new();
+        text_messages
+            .warnings
+            .push(Issue::new(IssueKind::ReadFailed, "Cannot open dir").with_path("/root").with_io_error(io::Error::from(io::ErrorKind::PermissionDenied)));
+        text_messages.warnings.push(Issue::new(IssueKind::DeleteFailed, "Failed to delete").with_io_error(io::Error::from(io::ErrorKind::PermissionDenied)));
+        text_messages.errors.push(Issue::new(IssueKind::DeleteFailed, "Failed to delete").with_path("/a"));
+
+        assert_eq!(text_messages.warnings[0].kind, IssueKind::PermissionDenied);
+        assert_eq!(text_messages.warnings[0].to_string(), "Cannot open dir /root (permission denied)");
+        assert_eq!(text_messages.errors[0].to_string(), "Failed to delete /a");
+
+        let counts = text_messages.count_by_kind();
+        assert_eq!(counts.get(&IssueKind::PermissionDenied), Some(&1));
+        assert_eq!(counts.get(&IssueKind::DeleteFailed), Some(&2));
+        assert_eq!(counts.len(), 2);
     }
 }
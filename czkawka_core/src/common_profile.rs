@@ -0,0 +1,380 @@
+use crate::duplicate::{CheckingMethod, DeleteMethod};
+#[cfg(feature = "same-music")]
+use crate::same_music::MusicSimilarity;
+#[cfg(feature = "similar-images")]
+use crate::similar_images::{self, HashAlgorithm, Similarity};
+use directories_next::ProjectDirs;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const DEFAULT_PROFILE_NAME: &str = "default";
+const PROFILES_FILE_NAME: &str = "czkawka_profiles.toml";
+/// File with configuration saved by GUI before profiles were added
+const OLD_GUI_CONFIG_FILE_NAME: &str = "czkawka_gui_config.txt";
+
+/// Settings of scan, which may be saved under chosen name and used by any frontend
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Profile {
+    pub included_directories: Vec<String>,
+    pub excluded_directories: Vec<String>,
+    pub excluded_items: Vec<String>,
+    pub allowed_extensions: Vec<String>,
+    pub excluded_extensions: Vec<String>,
+    pub extension_groups: Vec<String>,
+    pub content_types: Vec<String>,
+    pub detect_by_content: bool,
+    pub recursive_search: bool,
+    pub max_depth: Option<usize>,
+    pub use_ignore_files: bool,
+    pub modified_after: String,
+    pub modified_before: String,
+    pub low_priority: bool,
+    pub io_limit: Option<u64>,
+    /// Threads used by tools which mostly read files
+    pub io_threads: Option<usize>,
+    /// Threads used by tools which mostly compute, like similar images
+    pub cpu_threads: Option<usize>,
+    // Tables must be placed after simple values, otherwise profile can't be saved as TOML
+    pub tools: ToolSettings,
+    pub delete_policy: DeletePolicy,
+}
+
+impl Default for Profile {
+    fn default() -> Self {
+        Self {
+            included_directories: vec![],
+            excluded_directories: vec![],
+            excluded_items: vec![],
+            allowed_extensions: vec![],
+            excluded_extensions: vec![],
+            extension_groups: vec![],
+            content_types: vec![],
+            detect_by_content: false,
+            recursive_search: true,
+            max_depth: None,
+            use_ignore_files: false,
+            modified_after: "".to_string(),
+            modified_before: "".to_string(),
+            low_priority: false,
+            io_limit: None,
+            io_threads: None,
+            cpu_threads: None,
+            tools: ToolSettings::default(),
+            delete_policy: DeletePolicy::default(),
+        }
+    }
+}
+
+/// Settings used only by one tool
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ToolSettings {
+    pub duplicate_minimal_file_size: u64,
+    pub duplicate_check_method: CheckingMethod,
+    pub big_files_number: usize,
+    pub similar_images_minimal_file_size: u64,
+    #[cfg(feature = "similar-images")]
+    pub similar_images_similarity: Similarity,
+    /// Overrides similarity level when set
+    pub similar_images_max_distance: Option<u64>,
+    #[cfg(feature = "similar-images")]
+    pub similar_images_hash_algorithm: HashAlgorithm,
+    pub similar_images_hash_size: u8,
+    pub similar_images_use_dct: bool,
+    pub similar_images_use_cache: bool,
+    pub similar_images_check_rotations: bool,
+    /// Extensions of images checked in addition to formats supported by decoder
+    pub similar_images_additional_extensions: Vec<String>,
+    /// Extensions of supported image formats which are not checked
+    pub similar_images_excluded_extensions: Vec<String>,
+    pub zeroed_minimal_file_size: u64,
+    pub same_music_minimal_file_size: u64,
+    /// Names of equal tags - title, artist, album_title, album_artist or year
+    pub same_music_similarity: Vec<String>,
+    /// Settings of tools disabled in this build or added in newer versions, kept so saving profile doesn't lose them
+    #[serde(flatten)]
+    pub other_settings: BTreeMap<String, toml::Value>,
+}
+
+impl Default for ToolSettings {
+    fn default() -> Self {
+        Self {
+            duplicate_minimal_file_size: 1024,
+            duplicate_check_method: CheckingMethod::Hash,
+            big_files_number: 50,
+            similar_images_minimal_file_size: 16384,
+            #[cfg(feature = "similar-images")]
+            similar_images_similarity: Similarity::High,
+            similar_images_max_distance: None,
+            #[cfg(feature = "similar-images")]
+            similar_images_hash_algorithm: HashAlgorithm::Gradient,
+            similar_images_hash_size: 8,
+            similar_images_use_dct: false,
+            similar_images_use_cache: true,
+            similar_images_check_rotations: false,
+            similar_images_additional_extensions: Vec::new(),
+            similar_images_excluded_extensions: Vec::new(),
+            zeroed_minimal_file_size: 1024,
+            same_music_minimal_file_size: 1024,
+            same_music_similarity: vec!["artist".to_string(), "title".to_string()],
+            other_settings: BTreeMap::new(),
+        }
+    }
+}
+
+#[cfg(feature = "same-music")]
+impl ToolSettings {
+    /// Unknown names of tags are ignored
+    pub fn get_same_music_similarity(&self) -> MusicSimilarity {
+        let mut music_similarity = MusicSimilarity::NONE;
+        for name in &self.same_music_similarity {
+            music_similarity |= match name.trim().to_lowercase().as_str() {
+                "title" => MusicSimilarity::TITLE,
+                "artist" => MusicSimilarity::ARTIST,
+                "album_title" => MusicSimilarity::ALBUM_TITLE,
+                "album_artist" => MusicSimilarity::ALBUM_ARTIST,
+                "year" => MusicSimilarity::YEAR,
+                _ => MusicSimilarity::NONE,
+            };
+        }
+        music_similarity
+    }
+}
+
+/// Decides what happens with found files
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DeletePolicy {
+    /// GUI asks before removing selected files
+    pub confirm_deletion: bool,
+    /// Used by CLI when delete method isn't given in command line
+    pub duplicate_delete_method: DeleteMethod,
+    /// Used by CLI when delete method of similar images isn't given in command line
+    #[cfg(feature = "similar-images")]
+    pub similar_images_delete_method: similar_images::DeleteMethod,
+    /// Delete methods of tools disabled in this build or added in newer versions
+    #[serde(flatten)]
+    pub other_settings: BTreeMap<String, toml::Value>,
+}
+
+impl Default for DeletePolicy {
+    fn default() -> Self {
+        Self {
+            confirm_deletion: true,
+            duplicate_delete_method: DeleteMethod::None,
+            #[cfg(feature = "similar-images")]
+            similar_images_delete_method: similar_images::DeleteMethod::None,
+            other_settings: BTreeMap::new(),
+        }
+    }
+}
+
+/// Content of file with all profiles
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Profiles {
+    /// Profile used last time by GUI
+    pub current_profile: String,
+    /// GUI saves current profile at exit
+    pub save_at_exit: bool,
+    /// GUI loads current profile at start
+    pub load_at_start: bool,
+    pub profiles: BTreeMap<String, Profile>,
+}
+
+impl Default for Profiles {
+    fn default() -> Self {
+        Self {
+            current_profile: DEFAULT_PROFILE_NAME.to_string(),
+            save_at_exit: true,
+            load_at_start: true,
+            profiles: BTreeMap::new(),
+        }
+    }
+}
+
+impl Profiles {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    fn get_config_dir() -> Option<PathBuf> {
+        // Lin: /home/alice/.config/czkawka
+        // Win: C:\Users\Alice\AppData\Roaming\Qarmin\Czkawka\config
+        // Mac: /Users/Alice/Library/Application Support/pl.Qarmin.Czkawka
+        ProjectDirs::from("pl", "Qarmin", "Czkawka").map(|e| e.config_dir().to_path_buf())
+    }
+
+    /// File shared by CLI and GUI
+    pub fn get_default_file() -> Option<PathBuf> {
+        Self::get_config_dir().map(|e| e.join(PROFILES_FILE_NAME))
+    }
+
+    /// Loads profiles from default file, when it doesn't exists yet, configuration of old GUI is migrated to default profile
+    pub fn load() -> Result<Self, String> {
+        let config_dir = Self::get_config_dir().ok_or_else(|| "Failed to get home directory, so can't load profiles.".to_string())?;
+        let profiles_file = config_dir.join(PROFILES_FILE_NAME);
+        if profiles_file.is_file() {
+            return Self::load_from_file(&profiles_file);
+        }
+
+        let old_config_file = config_dir.join(OLD_GUI_CONFIG_FILE_NAME);
+        if !old_config_file.is_file() {
+            return Ok(Self::new());
+        }
+        let content = fs::read_to_string(&old_config_file).map_err(|e| format!("Failed to read data from file {}, reason {}", old_config_file.display(), e))?;
+        let profiles = Self::from_old_gui_config(&content).map_err(|e| format!("{} when migrating file {}", e, old_config_file.display()))?;
+        // Old file is left, so older versions of app still may use it
+        profiles.save()?;
+        Ok(profiles)
+    }
+
+    pub fn load_from_file(file_name: &Path) -> Result<Self, String> {
+        let content = fs::read_to_string(file_name).map_err(|e| format!("Failed to read data from file {}, reason {}", file_name.display(), e))?;
+        toml::from_str(&content).map_err(|e| format!("Failed to parse profiles from file {}, reason {}", file_name.display(), e))
+    }
+
+    /// Returns file to which profiles were saved
+    pub fn save(&self) -> Result<PathBuf, String> {
+        let config_dir = Self::get_config_dir().ok_or_else(|| "Failed to get home directory, so can't save profiles.".to_string())?;
+        fs::create_dir_all(&config_dir).map_err(|e| format!("Failed to create configuration folder {}, reason {}", config_dir.display(), e))?;
+        let profiles_file = config_dir.join(PROFILES_FILE_NAME);
+        self.save_to_file(&profiles_file)?;
+        Ok(profiles_file)
+    }
+
+    pub fn save_to_file(&self, file_name: &Path) -> Result<(), String> {
+        let content = toml::to_string(self).map_err(|e| format!("Failed to serialize profiles, reason {}", e))?;
+        fs::write(file_name, content).map_err(|e| format!("Failed to save profiles to file {}, reason {}", file_name.display(), e))
+    }
+
+    pub fn get_profile(&self, name: &str) -> Result<&Profile, String> {
+        self.profiles.get(name).ok_or_else(|| format!("Profile \"{}\" doesn't exists", name))
+    }
+
+    pub fn set_profile(&mut self, name: &str, profile: Profile) {
+        self.profiles.insert(name.to_string(), profile);
+    }
+
+    /// Returns false when there was no profile with this name
+    pub fn remove_profile(&mut self, name: &str) -> bool {
+        self.profiles.remove(name).is_some()
+    }
+
+    /// Converts text format used by GUI before profiles were added, its content is placed in default profile
+    pub fn from_old_gui_config(content: &str) -> Result<Self, String> {
+        let mut profiles = Self::new();
+        let mut profile = Profile::default();
+
+        let mut current_header: Option<String> = None;
+        for (line_number, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(header) = line.strip_prefix("--") {
+                current_header = Some(header.trim_end_matches(':').to_string());
+                continue;
+            }
+            let header = match &current_header {
+                Some(header) => header.as_str(),
+                None => return Err(format!("Found orphan data in line {} \"{}\"", line_number + 1, line)),
+            };
+            let as_bool = || match line.to_lowercase().as_str() {
+                "1" | "true" => Ok(true),
+                "0" | "false" => Ok(false),
+                _ => Err(format!("Found invalid data in line {} \"{}\" isn't proper value(0/1/true/false)", line_number + 1, line)),
+            };
+            match header {
+                "included_directories" => profile.included_directories.push(line.to_string()),
+                "excluded_directories" => profile.excluded_directories.push(line.to_string()),
+                "excluded_items" => profile.excluded_items.push(line.to_string()),
+                "allowed_extensions" => profile.allowed_extensions.push(line.to_string()),
+                "excluded_extensions" => profile.excluded_extensions.push(line.to_string()),
+                "extension_groups" => profile.extension_groups.push(line.to_string()),
+                "content_types" => profile.content_types.push(line.to_lowercase()),
+                "detect_by_content" => profile.detect_by_content = as_bool()?,
+                "load_at_start" => profiles.load_at_start = as_bool()?,
+                "save_at_exit" => profiles.save_at_exit = as_bool()?,
+                "confirm_deletion" => profile.delete_policy.confirm_deletion = as_bool()?,
+                "low_priority" => profile.low_priority = as_bool()?,
+                // Invalid numbers meant no limit also in old GUI
+                "io_limit" => profile.io_limit = line.parse().ok(),
+                "io_threads" => profile.io_threads = line.parse().ok(),
+                "cpu_threads" => profile.cpu_threads = line.parse().ok(),
+                _ => return Err(format!("Found invalid header \"--{}\"", header)),
+            }
+        }
+
+        profiles.set_profile(DEFAULT_PROFILE_NAME, profile);
+        Ok(profiles)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::common_profile::{Profile, Profiles, DEFAULT_PROFILE_NAME};
+    use crate::duplicate::{CheckingMethod, DeleteMethod};
+    #[cfg(feature = "same-music")]
+    use crate::same_music::MusicSimilarity;
+    #[cfg(feature = "similar-images")]
+    use crate::similar_images::Similarity;
+
+    #[test]
+    fn test_profiles() {
+        let mut profiles = Profiles::new();
+        let mut photos = Profile {
+            included_directories: vec!["/home/rafal/Photos".to_string()],
+            max_depth: Some(2),
+            ..Default::default()
+        };
+        photos.tools.duplicate_check_method = CheckingMethod::HashMB;
+        photos.delete_policy.duplicate_delete_method = DeleteMethod::AllExceptOldest;
+        profiles.set_profile("photos", photos.clone());
+        profiles.set_profile(DEFAULT_PROFILE_NAME, Profile::default());
+
+        let file_name = std::env::temp_dir().join(format!("czkawka_test_profiles_{}.toml", std::process::id()));
+        profiles.save_to_file(&file_name).unwrap();
+        let loaded = Profiles::load_from_file(&file_name).unwrap();
+        std::fs::remove_file(&file_name).unwrap();
+        assert_eq!(loaded, profiles);
+        assert_eq!(loaded.get_profile("photos").unwrap(), &photos);
+        assert!(loaded.get_profile("music").is_err());
+
+        // Missing values are replaced by defaults
+        let partial: Profiles = toml::from_str("[profiles.music]\nincluded_directories = [\"/home/rafal/Music\"]\n[profiles.music.tools]\nsame_music_similarity = [\"title\", \"year\"]\n").unwrap();
+        let music = partial.get_profile("music").unwrap();
+        assert!(music.recursive_search);
+        assert!(partial.load_at_start);
+        assert_eq!(music.tools.duplicate_minimal_file_size, 1024);
+        #[cfg(feature = "same-music")]
+        assert_eq!(music.tools.get_same_music_similarity(), MusicSimilarity::TITLE | MusicSimilarity::YEAR);
+
+        // Settings which this build doesn't know are saved back unchanged
+        let content = "[profiles.images.tools]\nsimilar_images_similarity = \"medium\"\nnewer_tool_setting = 5\n[profiles.images.delete_policy]\nsimilar_images_delete_method = \"all_except_best_quality\"\n";
+        let images: Profiles = toml::from_str(content).unwrap();
+        let saved: Profiles = toml::from_str(&toml::to_string(&images).unwrap()).unwrap();
+        assert_eq!(saved, images);
+        let tools = &saved.get_profile("images").unwrap().tools;
+        assert_eq!(tools.other_settings.get("newer_tool_setting"), Some(&toml::Value::Integer(5)));
+        #[cfg(feature = "similar-images")]
+        assert_eq!(tools.similar_images_similarity, Similarity::Medium);
+        #[cfg(not(feature = "similar-images"))]
+        assert_eq!(tools.other_settings.get("similar_images_similarity"), Some(&toml::Value::String("medium".to_string())));
+        #[cfg(not(feature = "similar-images"))]
+        assert!(saved.get_profile("images").unwrap().delete_policy.other_settings.contains_key("similar_images_delete_method"));
+
+        let migrated = Profiles::from_old_gui_config("--included_directories:\n/home/rafal\n/home/szczekacz\n--excluded_items:\n*/.git/*\n--save_at_exit:\nfalse\n--confirm_deletion:\n0\n--io_limit:\n1000\n").unwrap();
+        let default = migrated.get_profile(DEFAULT_PROFILE_NAME).unwrap();
+        assert_eq!(default.included_directories, vec!["/home/rafal".to_string(), "/home/szczekacz".to_string()]);
+        assert_eq!(default.excluded_items, vec!["*/.git/*".to_string()]);
+        assert!(!default.delete_policy.confirm_deletion);
+        assert_eq!(default.io_limit, Some(1000));
+        assert!(!migrated.save_at_exit);
+        assert!(Profiles::from_old_gui_config("/home/rafal\n").is_err());
+        assert!(Profiles::from_old_gui_config("--low_priority:\nmaybe\n").is_err());
+    }
+}
@@ -0,0 +1,136 @@
+use humansize::{file_size_opts as options, FileSize};
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// Weight of the newest measurement, lower values give more stable but slower reacting speed
+const SPEED_SMOOTHING: f64 = 0.3;
+/// Speed measured in shorter periods is too inaccurate, e.g. when measuring starts after first files were already read
+const MIN_MEASURE_INTERVAL: f64 = 0.1;
+
+/// Amount of data processed in current stage, with speed and estimated time left
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BytesProgress {
+    pub bytes_checked: u64,
+    pub bytes_to_check: u64,
+    pub bytes_per_second: u64,
+    /// None until speed can be measured
+    pub time_left: Option<Duration>,
+}
+
+impl BytesProgress {
+    /// Processed part of data, in range 0..=1
+    pub fn fraction(&self) -> f64 {
+        if self.bytes_to_check == 0 {
+            return 0.0;
+        }
+        (self.bytes_checked as f64 / self.bytes_to_check as f64).min(1.0)
+    }
+}
+
+impl fmt::Display for BytesProgress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}/{}, {}/s",
+            self.bytes_checked.file_size(options::BINARY).unwrap(),
+            self.bytes_to_check.file_size(options::BINARY).unwrap(),
+            self.bytes_per_second.file_size(options::BINARY).unwrap()
+        )?;
+        if let Some(time_left) = self.time_left {
+            write!(f, ", {} left", format_duration(time_left))?;
+        }
+        Ok(())
+    }
+}
+
+/// Measures speed of processing data, used in progress threads of tools
+pub struct BytesProgressMeter {
+    last_time: Instant,
+    last_bytes: u64,
+    bytes_per_second: Option<f64>,
+}
+
+impl Default for BytesProgressMeter {
+    fn default() -> Self {
+        Self {
+            last_time: Instant::now(),
+            last_bytes: 0,
+            bytes_per_second: None,
+        }
+    }
+}
+
+impl BytesProgressMeter {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Speed is smoothed between measurements, so single slow or fast file doesn't change estimated time too much
+    pub fn measure(&mut self, bytes_checked: u64, bytes_to_check: u64) -> BytesProgress {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_time).as_secs_f64();
+        if elapsed >= MIN_MEASURE_INTERVAL {
+            let current_speed = bytes_checked.saturating_sub(self.last_bytes) as f64 / elapsed;
+            self.bytes_per_second = Some(match self.bytes_per_second {
+                Some(speed) => speed * (1.0 - SPEED_SMOOTHING) + current_speed * SPEED_SMOOTHING,
+                None => current_speed,
+            });
+            self.last_time = now;
+            self.last_bytes = bytes_checked;
+        }
+
+        let bytes_per_second = self.bytes_per_second.unwrap_or(0.0);
+        let time_left = if bytes_per_second > 0.0 {
+            Some(Duration::from_secs_f64(bytes_to_check.saturating_sub(bytes_checked) as f64 / bytes_per_second))
+        } else {
+            None
+        };
+        BytesProgress {
+            bytes_checked,
+            bytes_to_check,
+            bytes_per_second: bytes_per_second as u64,
+            time_left,
+        }
+    }
+}
+
+/// Formats duration like 1h 02m 03s, 2m 03s or 3s
+pub fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    if seconds >= 3600 {
+        format!("{}h {:02}m {:02}s", seconds / 3600, seconds % 3600 / 60, seconds % 60)
+    } else if seconds >= 60 {
+        format!("{}m {:02}s", seconds / 60, seconds % 60)
+    } else {
+        format!("{}s", seconds)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::common_progress::{format_duration, BytesProgress, BytesProgressMeter};
+    use std::thread::sleep;
+    use std::time::Duration;
+
+    #[test]
+    fn test_bytes_progress() {
+        assert_eq!(format_duration(Duration::from_secs(3)), "3s");
+        assert_eq!(format_duration(Duration::from_secs(123)), "2m 03s");
+        assert_eq!(format_duration(Duration::from_secs(3723)), "1h 02m 03s");
+
+        let mut meter = BytesProgressMeter::new();
+        sleep(Duration::from_millis(120));
+        let progress = meter.measure(1024 * 1024, 4 * 1024 * 1024);
+        assert!(progress.bytes_per_second > 0);
+        assert!(progress.time_left.is_some());
+        assert_eq!(progress.fraction(), 0.25);
+
+        let progress = BytesProgress {
+            bytes_checked: 1024,
+            bytes_to_check: 2048,
+            bytes_per_second: 512,
+            time_left: Some(Duration::from_secs(2)),
+        };
+        assert_eq!(progress.to_string(), "1 KiB/2 KiB, 512 B/s, 2s left");
+    }
+}
@@ -0,0 +1,140 @@
+use std::sync::Mutex;
+use std::thread::sleep;
+use std::time::{Duration, Instant};
+
+/// Reading isn't limited by time in which nothing was read for longer than this, so throttle can't be bypassed after e.g. pause
+const MAX_IDLE_CREDIT: Duration = Duration::from_secs(1);
+
+/// Limits of system resources which may be used by scan
+#[derive(Clone, Debug, Default)]
+pub struct ResourceLimits {
+    /// Maximum number of bytes read per second by all threads
+    pub io_limit: Option<u64>,
+    /// Lowers CPU and I/O priority of threads which read files, only supported on Linux
+    pub low_priority: bool,
+    /// Number of threads used by parallel stages, by default equal to number of logical CPUs
+    pub threads: Option<usize>,
+}
+
+impl ResourceLimits {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// 0 disables limit
+    pub fn set_io_limit(&mut self, io_limit: Option<u64>) {
+        self.io_limit = io_limit.filter(|e| *e > 0);
+    }
+
+    pub fn set_low_priority(&mut self, low_priority: bool) {
+        self.low_priority = low_priority;
+    }
+
+    /// 0 means number of logical CPUs
+    pub fn set_threads(&mut self, threads: Option<usize>) {
+        self.threads = threads.filter(|e| *e > 0);
+    }
+
+    /// Runs parallel iterators used inside function with given limits, throttle is shared between all threads
+    pub fn install<R: Send>(&self, function: impl FnOnce(&IoThrottle) -> R + Send) -> R {
+        let io_throttle = IoThrottle::new(self.io_limit);
+        // Global pool is used when nothing is changed, so creating threads for every stage is avoided
+        if self.low_priority || self.threads.is_some() {
+            let mut thread_pool_builder = rayon::ThreadPoolBuilder::new().num_threads(self.threads.unwrap_or(0));
+            if self.low_priority {
+                // Threads with lowered priority are dropped together with pool, so later scans run with normal priority
+                thread_pool_builder = thread_pool_builder.start_handler(|_| lower_current_thread_priority());
+            }
+            if let Ok(thread_pool) = thread_pool_builder.build() {
+                return thread_pool.install(|| function(&io_throttle));
+            }
+        }
+        function(&io_throttle)
+    }
+}
+
+struct IoThrottleState {
+    start: Instant,
+    bytes_read: u64,
+}
+
+/// Makes threads which read files wait, when they together read more data than allowed
+pub struct IoThrottle {
+    io_limit: Option<u64>,
+    state: Mutex<IoThrottleState>,
+}
+
+impl IoThrottle {
+    pub fn new(io_limit: Option<u64>) -> Self {
+        Self {
+            io_limit,
+            state: Mutex::new(IoThrottleState { start: Instant::now(), bytes_read: 0 }),
+        }
+    }
+
+    /// Must be called after reading data, waits until average speed drops to limit
+    pub fn consume(&self, bytes: u64) {
+        let io_limit = match self.io_limit {
+            Some(io_limit) => io_limit as f64,
+            None => return,
+        };
+        let time_to_wait = {
+            let mut state = self.state.lock().unwrap();
+            if state.start.elapsed() > Duration::from_secs_f64(state.bytes_read as f64 / io_limit) + MAX_IDLE_CREDIT {
+                state.start = Instant::now();
+                state.bytes_read = 0;
+            }
+            state.bytes_read += bytes;
+            Duration::from_secs_f64(state.bytes_read as f64 / io_limit).checked_sub(state.start.elapsed())
+        };
+        if let Some(time_to_wait) = time_to_wait {
+            sleep(time_to_wait);
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn lower_current_thread_priority() {
+    const IOPRIO_WHO_PROCESS: libc::c_int = 1;
+    const IOPRIO_CLASS_IDLE: libc::c_int = 3;
+    const IOPRIO_CLASS_SHIFT: libc::c_int = 13;
+    // On Linux, 0 as id changes priority only of calling thread
+    // Errors are ignored, because then scan just runs with normal priority
+    unsafe {
+        libc::setpriority(libc::PRIO_PROCESS, 0, 19);
+        libc::syscall(libc::SYS_ioprio_set, IOPRIO_WHO_PROCESS, 0, IOPRIO_CLASS_IDLE << IOPRIO_CLASS_SHIFT);
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn lower_current_thread_priority() {}
+
+#[cfg(test)]
+mod test {
+    use crate::common_resources::{IoThrottle, ResourceLimits};
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_resource_limits() {
+        let io_throttle = IoThrottle::new(None);
+        let start = Instant::now();
+        io_throttle.consume(u64::MAX / 2);
+        assert!(start.elapsed() < Duration::from_millis(100));
+
+        let io_throttle = IoThrottle::new(Some(1000));
+        let start = Instant::now();
+        for _ in 0..3 {
+            io_throttle.consume(100);
+        }
+        assert!(start.elapsed() >= Duration::from_millis(300));
+
+        let mut resource_limits = ResourceLimits::new();
+        resource_limits.set_io_limit(Some(0));
+        assert_eq!(resource_limits.io_limit, None);
+        resource_limits.set_low_priority(true);
+        assert_eq!(resource_limits.install(|_| 2 + 2), 4);
+
+        resource_limits.set_threads(Some(3));
+        assert_eq!(resource_limits.install(|_| rayon::current_num_threads()), 3);
+    }
+}
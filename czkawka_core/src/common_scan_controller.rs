@@ -0,0 +1,137 @@
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+
+const RUNNING: u8 = 0;
+const PAUSED: u8 = 1;
+const CANCELLED: u8 = 2;
+
+struct ScanControllerState {
+    state: AtomicU8,
+    // Used only to block threads while scan is paused, so checking state of running scan stays cheap
+    lock: Mutex<()>,
+    condvar: Condvar,
+}
+
+/// Handle which allows to cancel, pause and resume running scan
+/// Clones share the same state, so one clone may be passed to scan and other used by frontend
+#[derive(Clone)]
+pub struct ScanController {
+    state: Arc<ScanControllerState>,
+}
+
+impl Default for ScanController {
+    fn default() -> Self {
+        Self {
+            state: Arc::new(ScanControllerState {
+                state: AtomicU8::new(RUNNING),
+                lock: Mutex::new(()),
+                condvar: Condvar::new(),
+            }),
+        }
+    }
+}
+
+impl ScanController {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Stops scan, also when it is paused
+    pub fn cancel(&self) {
+        self.set_state(CANCELLED);
+    }
+
+    /// Blocks worker threads at next check until scan is resumed or cancelled
+    pub fn pause(&self) {
+        let _guard = self.state.lock.lock().unwrap();
+        let _ = self.state.state.compare_exchange(RUNNING, PAUSED, Ordering::SeqCst, Ordering::SeqCst);
+    }
+
+    pub fn resume(&self) {
+        let _guard = self.state.lock.lock().unwrap();
+        if self.state.state.compare_exchange(PAUSED, RUNNING, Ordering::SeqCst, Ordering::SeqCst).is_ok() {
+            self.state.condvar.notify_all();
+        }
+    }
+
+    /// Prepares controller to be used by next scan
+    pub fn reset(&self) {
+        self.set_state(RUNNING);
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.state.state.load(Ordering::SeqCst) == PAUSED
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.state.state.load(Ordering::SeqCst) == CANCELLED
+    }
+
+    /// Checked by worker threads, waits while scan is paused and returns true when scan was cancelled
+    pub fn should_stop(&self) -> bool {
+        match self.state.state.load(Ordering::SeqCst) {
+            RUNNING => false,
+            CANCELLED => true,
+            _ => {
+                let mut guard = self.state.lock.lock().unwrap();
+                while self.state.state.load(Ordering::SeqCst) == PAUSED {
+                    guard = self.state.condvar.wait(guard).unwrap();
+                }
+                self.state.state.load(Ordering::SeqCst) == CANCELLED
+            }
+        }
+    }
+
+    fn set_state(&self, state: u8) {
+        let _guard = self.state.lock.lock().unwrap();
+        self.state.state.store(state, Ordering::SeqCst);
+        self.state.condvar.notify_all();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::common_scan_controller::ScanController;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn test_scan_controller() {
+        let scan_controller = ScanController::new();
+        assert!(!scan_controller.should_stop());
+
+        scan_controller.pause();
+        assert!(scan_controller.is_paused());
+        let finished = Arc::new(AtomicBool::new(false));
+        let handle = {
+            let scan_controller = scan_controller.clone();
+            let finished = finished.clone();
+            thread::spawn(move || {
+                let should_stop = scan_controller.should_stop();
+                finished.store(true, Ordering::SeqCst);
+                should_stop
+            })
+        };
+        thread::sleep(Duration::from_millis(50));
+        assert!(!finished.load(Ordering::SeqCst));
+        scan_controller.resume();
+        assert!(!handle.join().unwrap());
+
+        scan_controller.pause();
+        let handle = {
+            let scan_controller = scan_controller.clone();
+            thread::spawn(move || scan_controller.should_stop())
+        };
+        scan_controller.cancel();
+        assert!(handle.join().unwrap());
+        assert!(scan_controller.is_cancelled());
+
+        // Cancelled scan can't be paused
+        scan_controller.pause();
+        assert!(scan_controller.should_stop());
+        scan_controller.reset();
+        assert!(!scan_controller.should_stop());
+    }
+}
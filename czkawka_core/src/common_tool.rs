@@ -0,0 +1,97 @@
+use crate::common_messages::Messages;
+use crate::common_scan_controller::ScanController;
+use crate::common_traits::{DebugPrint, PrintResults, SaveResults};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Found file or folder, in form which doesn't depend on tool which found it
+#[derive(Clone, Debug, PartialEq)]
+pub struct ResultEntry {
+    pub path: PathBuf,
+    pub size: u64,
+    pub modified_date: u64,
+}
+
+/// Entries related to each other e.g. duplicates, tools which don't group results return all entries in one group
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ResultGroup {
+    pub entries: Vec<ResultEntry>,
+}
+
+/// Interface shared by all tools, so frontends may configure, run and show results of any tool in the same way
+pub trait Tool: DebugPrint + SaveResults + PrintResults {
+    type ProgressData;
+
+    /// Name used in messages shown to user, e.g. "empty folders"
+    fn get_name(&self) -> &'static str;
+    /// Short name without spaces, e.g. used in default name of file with results
+    fn get_id(&self) -> &'static str;
+
+    fn set_included_directory(&mut self, included_directory: String) -> bool;
+    fn set_excluded_directory(&mut self, excluded_directory: String);
+    fn set_excluded_items(&mut self, excluded_items: String);
+    fn set_use_ignore_files(&mut self, use_ignore_files: bool);
+    fn set_max_depth(&mut self, max_depth: Option<usize>);
+    fn set_modified_after(&mut self, modified_after: String);
+    fn set_modified_before(&mut self, modified_before: String);
+
+    /// Runs scan, which may be cancelled or paused with controller
+    fn search(&mut self, scan_controller: Option<&ScanController>, progress_sender: Option<&futures::channel::mpsc::Sender<Self::ProgressData>>);
+    fn get_stopped_search(&self) -> bool;
+    fn get_text_messages(&self) -> &Messages;
+
+    fn get_result_groups(&self) -> Vec<ResultGroup>;
+
+    /// Removes entry selected by user from disk, results of scan are not changed, so frontend must hide it by itself
+    /// Tools which find folders must remove them in other way than files
+    fn delete_entry(&self, path: &Path) -> io::Result<()> {
+        fs::remove_file(path)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::common_tool::Tool;
+    use crate::empty_files::EmptyFiles;
+    use crate::empty_folder::EmptyFolder;
+    use std::fs;
+    use std::path::{Path, PathBuf};
+
+    fn search_in<T: Tool<ProgressData = P>, P>(tool: &mut T, root: &Path) -> Vec<PathBuf> {
+        assert!(tool.set_included_directory(root.to_string_lossy().to_string()));
+        tool.search(None, None);
+        assert!(!tool.get_stopped_search());
+        tool.get_result_groups().into_iter().flat_map(|e| e.entries).map(|e| e.path).collect()
+    }
+
+    #[test]
+    fn test_tool() {
+        let root: PathBuf = std::env::temp_dir().join(format!("czkawka_test_tool_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("empty")).unwrap();
+        fs::write(root.join("empty.txt"), "").unwrap();
+        fs::write(root.join("full.txt"), "czkawka").unwrap();
+
+        let mut empty_files = EmptyFiles::new();
+        let found = search_in(&mut empty_files, &root);
+        assert_eq!(found, vec![root.join("empty.txt")]);
+        assert!(empty_files.delete_entry(&found[0]).is_ok());
+        assert!(!root.join("empty.txt").exists());
+        assert!(empty_files.delete_entry(&found[0]).is_err());
+
+        let mut empty_folder = EmptyFolder::new();
+        let found = search_in(&mut empty_folder, &root);
+        assert_eq!(found, vec![root.join("empty")]);
+        // Folder which got files after scan is left
+        fs::create_dir_all(root.join("empty").join("inner")).unwrap();
+        fs::write(root.join("empty").join("inner").join("new.txt"), "czkawka").unwrap();
+        assert!(empty_folder.delete_entry(&found[0]).is_err());
+        assert!(root.join("empty").exists());
+        fs::remove_file(root.join("empty").join("inner").join("new.txt")).unwrap();
+        assert!(empty_folder.delete_entry(&found[0]).is_ok());
+        assert!(!root.join("empty").exists());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
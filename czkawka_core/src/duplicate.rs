@@ -1,24 +1,32 @@
-use crossbeam_channel::Receiver;
+use crate::common_scan_controller::ScanController;
+use crate::common_tool::{ResultEntry, ResultGroup, Tool};
 use humansize::{file_size_opts as options, FileSize};
+use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, HashMap};
-use std::fs::{File, Metadata};
+use std::fs::File;
 use std::io::prelude::*;
-use std::path::PathBuf;
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::time::{Duration, SystemTime};
 use std::{fs, thread};
 
 use crate::common::Common;
+use crate::common_content_type::ContentFilter;
+use crate::common_date_filter::DateFilter;
+use crate::common_dir_traversal::DirTraversal;
+pub use crate::common_dir_traversal::FileEntry;
 use crate::common_directory::Directories;
 use crate::common_extensions::Extensions;
 use crate::common_items::ExcludedItems;
-use crate::common_messages::Messages;
+use crate::common_messages::{Issue, IssueKind, Messages};
+use crate::common_progress::{BytesProgress, BytesProgressMeter};
+use crate::common_resources::ResourceLimits;
 use crate::common_traits::*;
 use rayon::prelude::*;
-use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::thread::sleep;
 
 const HASH_MB_LIMIT_BYTES: u64 = 1024 * 1024; // 1MB
+const PRE_HASH_BYTES: u64 = 1024 * 2; // Size of beginning of file compared in first stage
 
 #[derive(Debug)]
 pub struct ProgressData {
@@ -27,9 +35,12 @@ pub struct ProgressData {
     pub max_stage: u8,
     pub files_checked: usize,
     pub files_to_check: usize,
+    /// Read data of files, known only in stages which are hashing files
+    pub bytes_progress: BytesProgress,
 }
 
-#[derive(PartialEq, Eq, Clone, Debug)]
+#[derive(PartialEq, Eq, Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum CheckingMethod {
     None,
     Name,
@@ -43,7 +54,8 @@ pub enum HashType {
     Blake3,
 }
 
-#[derive(Eq, PartialEq, Clone, Debug)]
+#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum DeleteMethod {
     None,
     AllExceptNewest,
@@ -52,13 +64,6 @@ pub enum DeleteMethod {
     OneNewest,
 }
 
-#[derive(Clone, Debug)]
-pub struct FileEntry {
-    pub path: PathBuf,
-    pub size: u64,
-    pub modified_date: u64,
-}
-
 /// Info struck with helpful information's about results
 #[derive(Default)]
 pub struct Info {
@@ -100,6 +105,11 @@ pub struct DuplicateFinder {
     allowed_extensions: Extensions,
     excluded_items: ExcludedItems,
     recursive_search: bool,
+    max_depth: Option<usize>,
+    use_ignore_files: bool,
+    date_filter: DateFilter,
+    content_filter: ContentFilter,
+    resource_limits: ResourceLimits,
     minimal_file_size: u64,
     check_method: CheckingMethod,
     delete_method: DeleteMethod,
@@ -116,6 +126,11 @@ impl DuplicateFinder {
             files_with_identical_size: Default::default(),
             files_with_identical_hashes: Default::default(),
             recursive_search: true,
+            max_depth: None,
+            use_ignore_files: false,
+            date_filter: DateFilter::new(),
+            content_filter: ContentFilter::new(),
+            resource_limits: ResourceLimits::new(),
             allowed_extensions: Extensions::new(),
             check_method: CheckingMethod::None,
             delete_method: DeleteMethod::None,
@@ -127,28 +142,28 @@ impl DuplicateFinder {
         }
     }
 
-    pub fn find_duplicates(&mut self, stop_receiver: Option<&Receiver<()>>, progress_sender: Option<&futures::channel::mpsc::Sender<ProgressData>>) {
+    pub fn find_duplicates(&mut self, scan_controller: Option<&ScanController>, progress_sender: Option<&futures::channel::mpsc::Sender<ProgressData>>) {
         self.directories.optimize_directories(self.recursive_search, &mut self.text_messages);
 
         match self.check_method {
             CheckingMethod::Name => {
-                if !self.check_files_name(stop_receiver, progress_sender) {
+                if !self.check_files_name(scan_controller, progress_sender) {
                     self.stopped_search = true;
                     return;
                 }
             }
             CheckingMethod::Size => {
-                if !self.check_files_size(stop_receiver, progress_sender) {
+                if !self.check_files_size(scan_controller, progress_sender) {
                     self.stopped_search = true;
                     return;
                 }
             }
             CheckingMethod::HashMB | CheckingMethod::Hash => {
-                if !self.check_files_size(stop_receiver, progress_sender) {
+                if !self.check_files_size(scan_controller, progress_sender) {
                     self.stopped_search = true;
                     return;
                 }
-                if !self.check_files_hash(stop_receiver, progress_sender) {
+                if !self.check_files_hash(scan_controller, progress_sender) {
                     self.stopped_search = true;
                     return;
                 }
@@ -165,10 +180,6 @@ impl DuplicateFinder {
         &self.check_method
     }
 
-    pub fn get_stopped_search(&self) -> bool {
-        self.stopped_search
-    }
-
     pub const fn get_files_sorted_by_names(&self) -> &BTreeMap<String, Vec<FileEntry>> {
         &self.files_with_identical_names
     }
@@ -181,10 +192,6 @@ impl DuplicateFinder {
         &self.files_with_identical_hashes
     }
 
-    pub const fn get_text_messages(&self) -> &Messages {
-        &self.text_messages
-    }
-
     pub const fn get_information(&self) -> &Info {
         &self.information
     }
@@ -208,30 +215,52 @@ impl DuplicateFinder {
         self.recursive_search = recursive_search;
     }
 
-    pub fn set_included_directory(&mut self, included_directory: String) -> bool {
-        self.directories.set_included_directory(included_directory, &mut self.text_messages)
+    /// Only files with these content types(image, audio, video, archive, document) will be checked
+    pub fn set_content_types(&mut self, content_types: String) {
+        self.content_filter.set_content_types(content_types, &mut self.text_messages);
     }
 
-    pub fn set_excluded_directory(&mut self, excluded_directory: String) {
-        self.directories.set_excluded_directory(excluded_directory, &mut self.text_messages);
+    /// Recognizes type of files by their content instead of extension
+    pub fn set_detect_by_content(&mut self, detect_by_content: bool) {
+        self.content_filter.set_detect_by_content(detect_by_content);
     }
+
+    /// Limits speed of reading files in bytes per second, None or 0 disables limit
+    pub fn set_io_limit(&mut self, io_limit: Option<u64>) {
+        self.resource_limits.set_io_limit(io_limit);
+    }
+
+    /// Scan runs with lowered CPU and I/O priority, so it slows down other programs less
+    pub fn set_low_priority(&mut self, low_priority: bool) {
+        self.resource_limits.set_low_priority(low_priority);
+    }
+
+    /// Number of threads used by parallel stages, None or 0 means number of logical CPUs
+    pub fn set_threads(&mut self, threads: Option<usize>) {
+        self.resource_limits.set_threads(threads);
+    }
+
+    /// Files from list(read from file or "-" for standard input) are checked instead of files from included directories
+    pub fn set_file_list(&mut self, file_list: String, null_separated: bool) -> bool {
+        self.directories.set_file_list(file_list, null_separated, &mut self.text_messages)
+    }
+
     pub fn set_allowed_extensions(&mut self, allowed_extensions: String) {
         self.allowed_extensions.set_allowed_extensions(allowed_extensions, &mut self.text_messages);
     }
 
-    pub fn set_excluded_items(&mut self, excluded_items: String) {
-        self.excluded_items.set_excluded_items(excluded_items, &mut self.text_messages);
+    /// Files with these extensions are skipped even if they are allowed
+    pub fn set_excluded_extensions(&mut self, excluded_extensions: String) {
+        self.allowed_extensions.set_excluded_extensions(excluded_extensions, &mut self.text_messages);
     }
 
-    fn check_files_name(&mut self, stop_receiver: Option<&Receiver<()>>, progress_sender: Option<&futures::channel::mpsc::Sender<ProgressData>>) -> bool {
-        let start_time: SystemTime = SystemTime::now();
-        let mut folders_to_check: Vec<PathBuf> = Vec::with_capacity(1024 * 2); // This should be small enough too not see to big difference and big enough to store most of paths without needing to resize vector
+    /// Named groups of extensions which can be used in allowed and excluded extensions
+    pub fn set_extension_groups(&mut self, extension_groups: String) {
+        self.allowed_extensions.set_extension_groups(extension_groups, &mut self.text_messages);
+    }
 
-        // Add root folders for finding
-        for id in &self.directories.included_directories {
-            folders_to_check.push(id.clone());
-        }
-        self.information.number_of_checked_folders += folders_to_check.len();
+    fn check_files_name(&mut self, scan_controller: Option<&ScanController>, progress_sender: Option<&futures::channel::mpsc::Sender<ProgressData>>) -> bool {
+        let start_time: SystemTime = SystemTime::now();
 
         //// PROGRESS THREAD START
         const LOOP_DURATION: u32 = 200; //in ms
@@ -252,6 +281,7 @@ impl DuplicateFinder {
                         max_stage: 0,
                         files_checked: atomic_file_counter.load(Ordering::Relaxed) as usize,
                         files_to_check: 0,
+                        bytes_progress: BytesProgress::default(),
                     })
                     .unwrap();
                 if !progress_thread_run.load(Ordering::Relaxed) {
@@ -265,122 +295,35 @@ impl DuplicateFinder {
 
         //// PROGRESS THREAD END
 
-        while !folders_to_check.is_empty() {
-            if stop_receiver.is_some() && stop_receiver.unwrap().try_recv().is_ok() {
-                // End thread which send info to gui
-                progress_thread_run.store(false, Ordering::Relaxed);
-                progress_thread_handle.join().unwrap();
-                return false;
-            }
-
-            let current_folder = folders_to_check.pop().unwrap();
-
-            // Read current dir, if permission are denied just go to next
-            let read_dir = match fs::read_dir(&current_folder) {
-                Ok(t) => t,
-                Err(_) => {
-                    self.text_messages.warnings.push(format!("Cannot open dir {}", current_folder.display()));
-                    continue;
-                } // Permissions denied
-            };
-
-            // Check every sub folder/file/link etc.
-            'dir: for entry in read_dir {
-                let entry_data = match entry {
-                    Ok(t) => t,
-                    Err(_) => {
-                        self.text_messages.warnings.push(format!("Cannot read entry in dir {}", current_folder.display()));
-                        continue 'dir;
-                    } //Permissions denied
-                };
-                let metadata: Metadata = match entry_data.metadata() {
-                    Ok(t) => t,
-                    Err(_) => {
-                        self.text_messages.warnings.push(format!("Cannot read metadata in dir {}", current_folder.display()));
-                        continue 'dir;
-                    } //Permissions denied
-                };
-                if metadata.is_dir() {
-                    self.information.number_of_checked_folders += 1;
-
-                    if !self.recursive_search {
-                        continue 'dir;
-                    }
-
-                    let next_folder = current_folder.join(entry_data.file_name());
-                    if self.directories.is_excluded(&next_folder) {
-                        continue 'dir;
-                    }
-
-                    if self.excluded_items.is_excluded(&next_folder) {
-                        continue 'dir;
-                    }
-
-                    folders_to_check.push(next_folder);
-                } else if metadata.is_file() {
-                    atomic_file_counter.fetch_add(1, Ordering::Relaxed);
-                    // let mut have_valid_extension: bool;
-                    let file_name_lowercase: String = match entry_data.file_name().into_string() {
-                        Ok(t) => t,
-                        Err(_) => continue 'dir,
-                    }
-                    .to_lowercase();
-
-                    // Checking allowed extensions
-                    if !self.allowed_extensions.file_extensions.is_empty() {
-                        let allowed = self.allowed_extensions.file_extensions.iter().any(|e| file_name_lowercase.ends_with((".".to_string() + e.to_lowercase().as_str()).as_str()));
-                        if !allowed {
-                            // Not an allowed extension, ignore it.
-                            self.information.number_of_ignored_files += 1;
-                            continue 'dir;
-                        }
-                    }
-                    // Checking files
-                    if metadata.len() >= self.minimal_file_size {
-                        let current_file_name = current_folder.join(entry_data.file_name());
-                        if self.excluded_items.is_excluded(&current_file_name) {
-                            continue 'dir;
-                        }
-
-                        // Creating new file entry
-                        let fe: FileEntry = FileEntry {
-                            path: current_file_name.clone(),
-                            size: metadata.len(),
-                            modified_date: match metadata.modified() {
-                                Ok(t) => match t.duration_since(UNIX_EPOCH) {
-                                    Ok(d) => d.as_secs(),
-                                    Err(_) => {
-                                        self.text_messages.warnings.push(format!("File {} seems to be modified before Unix Epoch.", current_file_name.display()));
-                                        0
-                                    }
-                                },
-                                Err(_) => {
-                                    self.text_messages.warnings.push(format!("Unable to get modification date from file {}", current_file_name.display()));
-                                    continue 'dir;
-                                } // Permissions Denied
-                            },
-                        };
-
-                        // Adding files to BTreeMap
-                        self.files_with_identical_names.entry(entry_data.file_name().to_string_lossy().to_string()).or_insert_with(Vec::new);
-                        self.files_with_identical_names.get_mut(&entry_data.file_name().to_string_lossy().to_string()).unwrap().push(fe);
-
-                        self.information.number_of_checked_files += 1;
-                    } else {
-                        // Probably this is symbolic links so we are free to ignore this
-                        self.information.number_of_ignored_files += 1;
-                    }
-                } else {
-                    // Probably this is symbolic links so we are free to ignore this
-                    self.information.number_of_ignored_things += 1;
-                }
-            }
-        }
+        let mut dir_traversal = DirTraversal::new(&self.directories, &self.excluded_items);
+        dir_traversal.set_extensions(&self.allowed_extensions);
+        dir_traversal.set_recursive_search(self.recursive_search);
+        dir_traversal.set_max_depth(self.max_depth);
+        dir_traversal.set_use_ignore_files(self.use_ignore_files);
+        dir_traversal.set_date_filter(self.date_filter);
+        dir_traversal.set_content_filter(&self.content_filter);
+        dir_traversal.set_minimal_file_size(self.minimal_file_size);
+        let traversal_result = dir_traversal.run(scan_controller, &atomic_file_counter, &mut self.text_messages);
 
         // End thread which send info to gui
         progress_thread_run.store(false, Ordering::Relaxed);
         progress_thread_handle.join().unwrap();
 
+        let (files, traversal_information) = match traversal_result {
+            Some(t) => t,
+            None => return false,
+        };
+        self.information.number_of_checked_files += traversal_information.number_of_checked_files;
+        self.information.number_of_checked_folders += traversal_information.number_of_checked_folders;
+        self.information.number_of_ignored_files += traversal_information.number_of_ignored_files;
+        self.information.number_of_ignored_things += traversal_information.number_of_ignored_things;
+
+        // Adding files to BTreeMap
+        for fe in files {
+            let file_name = fe.path.file_name().unwrap().to_string_lossy().to_string();
+            self.files_with_identical_names.entry(file_name).or_insert_with(Vec::new).push(fe);
+        }
+
         // Create new BTreeMap without single size entries(files have not duplicates)
         let mut new_map: BTreeMap<String, Vec<FileEntry>> = Default::default();
 
@@ -395,21 +338,14 @@ impl DuplicateFinder {
         }
         self.files_with_identical_names = new_map;
 
-        Common::print_time(start_time, SystemTime::now(), "check_files_name".to_string());
+        Common::log_phase(self.get_id(), "traversal", start_time, self.information.number_of_checked_files);
         true
     }
 
     /// Read file length and puts it to different boxes(each for different lengths)
     /// If in box is only 1 result, then it is removed
-    fn check_files_size(&mut self, stop_receiver: Option<&Receiver<()>>, progress_sender: Option<&futures::channel::mpsc::Sender<ProgressData>>) -> bool {
+    fn check_files_size(&mut self, scan_controller: Option<&ScanController>, progress_sender: Option<&futures::channel::mpsc::Sender<ProgressData>>) -> bool {
         let start_time: SystemTime = SystemTime::now();
-        let mut folders_to_check: Vec<PathBuf> = Vec::with_capacity(1024 * 2); // This should be small enough too not see to big difference and big enough to store most of paths without needing to resize vector
-
-        // Add root folders for finding
-        for id in &self.directories.included_directories {
-            folders_to_check.push(id.clone());
-        }
-        self.information.number_of_checked_folders += folders_to_check.len();
 
         //// PROGRESS THREAD START
         const LOOP_DURATION: u32 = 200; //in ms
@@ -436,6 +372,7 @@ impl DuplicateFinder {
                         max_stage,
                         files_checked: atomic_file_counter.load(Ordering::Relaxed) as usize,
                         files_to_check: 0,
+                        bytes_progress: BytesProgress::default(),
                     })
                     .unwrap();
                 if !progress_thread_run.load(Ordering::Relaxed) {
@@ -449,121 +386,34 @@ impl DuplicateFinder {
 
         //// PROGRESS THREAD END
 
-        while !folders_to_check.is_empty() {
-            if stop_receiver.is_some() && stop_receiver.unwrap().try_recv().is_ok() {
-                // End thread which send info to gui
-                progress_thread_run.store(false, Ordering::Relaxed);
-                progress_thread_handle.join().unwrap();
-                return false;
-            }
-
-            let current_folder = folders_to_check.pop().unwrap();
-
-            // Read current dir, if permission are denied just go to next
-            let read_dir = match fs::read_dir(&current_folder) {
-                Ok(t) => t,
-                Err(_) => {
-                    self.text_messages.warnings.push(format!("Cannot open dir {}", current_folder.display()));
-                    continue;
-                } // Permissions denied
-            };
-
-            // Check every sub folder/file/link etc.
-            'dir: for entry in read_dir {
-                let entry_data = match entry {
-                    Ok(t) => t,
-                    Err(_) => {
-                        self.text_messages.warnings.push(format!("Cannot read entry in dir {}", current_folder.display()));
-                        continue 'dir;
-                    } //Permissions denied
-                };
-                let metadata: Metadata = match entry_data.metadata() {
-                    Ok(t) => t,
-                    Err(_) => {
-                        self.text_messages.warnings.push(format!("Cannot read metadata in dir {}", current_folder.display()));
-                        continue 'dir;
-                    } //Permissions denied
-                };
-                if metadata.is_dir() {
-                    self.information.number_of_checked_folders += 1;
-
-                    if !self.recursive_search {
-                        continue 'dir;
-                    }
-
-                    let next_folder = current_folder.join(entry_data.file_name());
-                    if self.directories.is_excluded(&next_folder) {
-                        continue 'dir;
-                    }
-
-                    if self.excluded_items.is_excluded(&next_folder) {
-                        continue 'dir;
-                    }
+        let mut dir_traversal = DirTraversal::new(&self.directories, &self.excluded_items);
+        dir_traversal.set_extensions(&self.allowed_extensions);
+        dir_traversal.set_recursive_search(self.recursive_search);
+        dir_traversal.set_max_depth(self.max_depth);
+        dir_traversal.set_use_ignore_files(self.use_ignore_files);
+        dir_traversal.set_date_filter(self.date_filter);
+        dir_traversal.set_content_filter(&self.content_filter);
+        dir_traversal.set_minimal_file_size(self.minimal_file_size);
+        let traversal_result = dir_traversal.run(scan_controller, &atomic_file_counter, &mut self.text_messages);
 
-                    folders_to_check.push(next_folder);
-                } else if metadata.is_file() {
-                    atomic_file_counter.fetch_add(1, Ordering::Relaxed);
-                    // let mut have_valid_extension: bool;
-                    let file_name_lowercase: String = match entry_data.file_name().into_string() {
-                        Ok(t) => t,
-                        Err(_) => continue 'dir,
-                    }
-                    .to_lowercase();
-
-                    // Checking allowed extensions
-                    if !self.allowed_extensions.file_extensions.is_empty() {
-                        let allowed = self.allowed_extensions.file_extensions.iter().any(|e| file_name_lowercase.ends_with((".".to_string() + e.to_lowercase().as_str()).as_str()));
-                        if !allowed {
-                            // Not an allowed extension, ignore it.
-                            self.information.number_of_ignored_files += 1;
-                            continue 'dir;
-                        }
-                    }
-                    // Checking files
-                    if metadata.len() >= self.minimal_file_size {
-                        let current_file_name = current_folder.join(entry_data.file_name());
-                        if self.excluded_items.is_excluded(&current_file_name) {
-                            continue 'dir;
-                        }
-
-                        // Creating new file entry
-                        let fe: FileEntry = FileEntry {
-                            path: current_file_name.clone(),
-                            size: metadata.len(),
-                            modified_date: match metadata.modified() {
-                                Ok(t) => match t.duration_since(UNIX_EPOCH) {
-                                    Ok(d) => d.as_secs(),
-                                    Err(_) => {
-                                        self.text_messages.warnings.push(format!("File {} seems to be modified before Unix Epoch.", current_file_name.display()));
-                                        0
-                                    }
-                                },
-                                Err(_) => {
-                                    self.text_messages.warnings.push(format!("Unable to get modification date from file {}", current_file_name.display()));
-                                    continue 'dir;
-                                } // Permissions Denied
-                            },
-                        };
-
-                        // Adding files to BTreeMap
-                        self.files_with_identical_size.entry(metadata.len()).or_insert_with(Vec::new);
-                        self.files_with_identical_size.get_mut(&metadata.len()).unwrap().push(fe);
-
-                        self.information.number_of_checked_files += 1;
-                    } else {
-                        // Probably this is symbolic links so we are free to ignore this
-                        self.information.number_of_ignored_files += 1;
-                    }
-                } else {
-                    // Probably this is symbolic links so we are free to ignore this
-                    self.information.number_of_ignored_things += 1;
-                }
-            }
-        }
         // End thread which send info to gui
         progress_thread_run.store(false, Ordering::Relaxed);
         progress_thread_handle.join().unwrap();
 
+        let (files, traversal_information) = match traversal_result {
+            Some(t) => t,
+            None => return false,
+        };
+        self.information.number_of_checked_files += traversal_information.number_of_checked_files;
+        self.information.number_of_checked_folders += traversal_information.number_of_checked_folders;
+        self.information.number_of_ignored_files += traversal_information.number_of_ignored_files;
+        self.information.number_of_ignored_things += traversal_information.number_of_ignored_things;
+
+        // Adding files to BTreeMap
+        for fe in files {
+            self.files_with_identical_size.entry(fe.size).or_insert_with(Vec::new).push(fe);
+        }
+
         // Create new BTreeMap without single size entries(files have not duplicates)
         let mut new_map: BTreeMap<u64, Vec<FileEntry>> = Default::default();
 
@@ -577,12 +427,12 @@ impl DuplicateFinder {
         }
         self.files_with_identical_size = new_map;
 
-        Common::print_time(start_time, SystemTime::now(), "check_files_size".to_string());
+        Common::log_phase(self.get_id(), "traversal", start_time, self.information.number_of_checked_files);
         true
     }
 
     /// The slowest checking type, which must be applied after checking for size
-    fn check_files_hash(&mut self, stop_receiver: Option<&Receiver<()>>, progress_sender: Option<&futures::channel::mpsc::Sender<ProgressData>>) -> bool {
+    fn check_files_hash(&mut self, scan_controller: Option<&ScanController>, progress_sender: Option<&futures::channel::mpsc::Sender<ProgressData>>) -> bool {
         if self.hash_type != HashType::Blake3 {
             panic!(); // TODO Add more hash types
         }
@@ -596,28 +446,35 @@ impl DuplicateFinder {
         let progress_thread_run = Arc::new(AtomicBool::new(true));
 
         let atomic_file_counter = Arc::new(AtomicUsize::new(0));
+        let atomic_bytes_counter = Arc::new(AtomicU64::new(0));
 
         let progress_thread_handle;
         if let Some(progress_sender) = progress_sender {
             let mut progress_send = progress_sender.clone();
             let progress_thread_run = progress_thread_run.clone();
             let atomic_file_counter = atomic_file_counter.clone();
+            let atomic_bytes_counter = atomic_bytes_counter.clone();
             let files_to_check = self.files_with_identical_size.iter().map(|e| e.1.len()).sum();
+            let bytes_to_check = self.files_with_identical_size.iter().map(|(size, vec_file_entry)| (*size).min(PRE_HASH_BYTES) * vec_file_entry.len() as u64).sum();
             let checking_method = self.check_method.clone();
-            progress_thread_handle = thread::spawn(move || loop {
-                progress_send
-                    .try_send(ProgressData {
-                        checking_method: checking_method.clone(),
-                        current_stage: 1,
-                        max_stage: 2,
-                        files_checked: atomic_file_counter.load(Ordering::Relaxed) as usize,
-                        files_to_check,
-                    })
-                    .unwrap();
-                if !progress_thread_run.load(Ordering::Relaxed) {
-                    break;
+            progress_thread_handle = thread::spawn(move || {
+                let mut bytes_progress_meter = BytesProgressMeter::new();
+                loop {
+                    progress_send
+                        .try_send(ProgressData {
+                            checking_method: checking_method.clone(),
+                            current_stage: 1,
+                            max_stage: 2,
+                            files_checked: atomic_file_counter.load(Ordering::Relaxed) as usize,
+                            files_to_check,
+                            bytes_progress: bytes_progress_meter.measure(atomic_bytes_counter.load(Ordering::Relaxed), bytes_to_check),
+                        })
+                        .unwrap();
+                    if !progress_thread_run.load(Ordering::Relaxed) {
+                        break;
+                    }
+                    sleep(Duration::from_millis(LOOP_DURATION as u64));
                 }
-                sleep(Duration::from_millis(LOOP_DURATION as u64));
             });
         } else {
             progress_thread_handle = thread::spawn(|| {});
@@ -626,49 +483,52 @@ impl DuplicateFinder {
         //// PROGRESS THREAD END
 
         #[allow(clippy::type_complexity)]
-        let pre_hash_results: Vec<(u64, HashMap<String, Vec<FileEntry>>, Vec<String>, u64)> = self
-            .files_with_identical_size
-            .par_iter()
-            .map(|(size, vec_file_entry)| {
-                let mut hashmap_with_hash: HashMap<String, Vec<FileEntry>> = Default::default();
-                let mut errors: Vec<String> = Vec::new();
-                let mut file_handler: File;
-                let mut bytes_read: u64 = 0;
-                atomic_file_counter.fetch_add(vec_file_entry.len(), Ordering::Relaxed);
-                'fe: for file_entry in vec_file_entry {
-                    if stop_receiver.is_some() && stop_receiver.unwrap().try_recv().is_ok() {
-                        check_was_breaked.store(true, Ordering::Relaxed);
-                        return None;
-                    }
-                    file_handler = match File::open(&file_entry.path) {
-                        Ok(t) => t,
-                        Err(_) => {
-                            errors.push(format!("Unable to check hash of file {}", file_entry.path.display()));
-                            continue 'fe;
+        let pre_hash_results: Vec<(u64, HashMap<String, Vec<FileEntry>>, Vec<Issue>, u64)> = self.resource_limits.install(|io_throttle| {
+            self.files_with_identical_size
+                .par_iter()
+                .map(|(size, vec_file_entry)| {
+                    let mut hashmap_with_hash: HashMap<String, Vec<FileEntry>> = Default::default();
+                    let mut errors: Vec<Issue> = Vec::new();
+                    let mut file_handler: File;
+                    let mut bytes_read: u64 = 0;
+                    atomic_file_counter.fetch_add(vec_file_entry.len(), Ordering::Relaxed);
+                    'fe: for file_entry in vec_file_entry {
+                        if scan_controller.is_some() && scan_controller.unwrap().should_stop() {
+                            check_was_breaked.store(true, Ordering::Relaxed);
+                            return None;
                         }
-                    };
+                        file_handler = match File::open(&file_entry.path) {
+                            Ok(t) => t,
+                            Err(e) => {
+                                errors.push(Issue::new(IssueKind::ReadFailed, "Unable to check hash of file").with_path(&file_entry.path).with_io_error(e));
+                                continue 'fe;
+                            }
+                        };
 
-                    let mut hasher: blake3::Hasher = blake3::Hasher::new();
-                    let mut buffer = [0u8; 1024 * 2];
-                    let n = match file_handler.read(&mut buffer) {
-                        Ok(t) => t,
-                        Err(_) => {
-                            errors.push(format!("Error happened when checking hash of file {}", file_entry.path.display()));
-                            continue 'fe;
-                        }
-                    };
+                        let mut hasher: blake3::Hasher = blake3::Hasher::new();
+                        let mut buffer = [0u8; PRE_HASH_BYTES as usize];
+                        let n = match file_handler.read(&mut buffer) {
+                            Ok(t) => t,
+                            Err(e) => {
+                                errors.push(Issue::new(IssueKind::ReadFailed, "Error happened when checking hash of file").with_path(&file_entry.path).with_io_error(e));
+                                continue 'fe;
+                            }
+                        };
 
-                    bytes_read += n as u64;
-                    hasher.update(&buffer[..n]);
+                        bytes_read += n as u64;
+                        atomic_bytes_counter.fetch_add(n as u64, Ordering::Relaxed);
+                        io_throttle.consume(n as u64);
+                        hasher.update(&buffer[..n]);
 
-                    let hash_string: String = hasher.finalize().to_hex().to_string();
-                    hashmap_with_hash.entry(hash_string.to_string()).or_insert_with(Vec::new);
-                    hashmap_with_hash.get_mut(hash_string.as_str()).unwrap().push(file_entry.to_owned());
-                }
-                Some((*size, hashmap_with_hash, errors, bytes_read))
-            })
-            .while_some()
-            .collect();
+                        let hash_string: String = hasher.finalize().to_hex().to_string();
+                        hashmap_with_hash.entry(hash_string.to_string()).or_insert_with(Vec::new);
+                        hashmap_with_hash.get_mut(hash_string.as_str()).unwrap().push(file_entry.to_owned());
+                    }
+                    Some((*size, hashmap_with_hash, errors, bytes_read))
+                })
+                .while_some()
+                .collect()
+        });
 
         // End thread which send info to gui
         progress_thread_run.store(false, Ordering::Relaxed);
@@ -694,7 +554,7 @@ impl DuplicateFinder {
             }
         }
 
-        Common::print_time(start_time, SystemTime::now(), "check_files_hash - prehash".to_string());
+        Common::log_phase(self.get_id(), "pre_hash", start_time, atomic_file_counter.load(Ordering::Relaxed));
         let start_time: SystemTime = SystemTime::now();
 
         /////////////////////////
@@ -704,28 +564,36 @@ impl DuplicateFinder {
         let progress_thread_run = Arc::new(AtomicBool::new(true));
 
         let atomic_file_counter = Arc::new(AtomicUsize::new(0));
+        let atomic_bytes_counter = Arc::new(AtomicU64::new(0));
 
         let progress_thread_handle;
         if let Some(progress_sender) = progress_sender {
             let mut progress_send = progress_sender.clone();
             let progress_thread_run = progress_thread_run.clone();
             let atomic_file_counter = atomic_file_counter.clone();
+            let atomic_bytes_counter = atomic_bytes_counter.clone();
             let files_to_check = pre_checked_map.iter().map(|e| e.1.len()).sum();
+            let size_limit = if self.check_method == CheckingMethod::HashMB { HASH_MB_LIMIT_BYTES } else { u64::MAX };
+            let bytes_to_check = pre_checked_map.iter().map(|(size, vec_file_entry)| (*size).min(size_limit) * vec_file_entry.len() as u64).sum();
             let checking_method = self.check_method.clone();
-            progress_thread_handle = thread::spawn(move || loop {
-                progress_send
-                    .try_send(ProgressData {
-                        checking_method: checking_method.clone(),
-                        current_stage: 2,
-                        max_stage: 2,
-                        files_checked: atomic_file_counter.load(Ordering::Relaxed) as usize,
-                        files_to_check,
-                    })
-                    .unwrap();
-                if !progress_thread_run.load(Ordering::Relaxed) {
-                    break;
+            progress_thread_handle = thread::spawn(move || {
+                let mut bytes_progress_meter = BytesProgressMeter::new();
+                loop {
+                    progress_send
+                        .try_send(ProgressData {
+                            checking_method: checking_method.clone(),
+                            current_stage: 2,
+                            max_stage: 2,
+                            files_checked: atomic_file_counter.load(Ordering::Relaxed) as usize,
+                            files_to_check,
+                            bytes_progress: bytes_progress_meter.measure(atomic_bytes_counter.load(Ordering::Relaxed), bytes_to_check),
+                        })
+                        .unwrap();
+                    if !progress_thread_run.load(Ordering::Relaxed) {
+                        break;
+                    }
+                    sleep(Duration::from_millis(LOOP_DURATION as u64));
                 }
-                sleep(Duration::from_millis(LOOP_DURATION as u64));
             });
         } else {
             progress_thread_handle = thread::spawn(|| {});
@@ -734,60 +602,64 @@ impl DuplicateFinder {
         //// PROGRESS THREAD END
 
         #[allow(clippy::type_complexity)]
-        let full_hash_results: Vec<(u64, HashMap<String, Vec<FileEntry>>, Vec<String>, u64)> = pre_checked_map
-            .par_iter()
-            .map(|(size, vec_file_entry)| {
-                let mut hashmap_with_hash: HashMap<String, Vec<FileEntry>> = Default::default();
-                let mut errors: Vec<String> = Vec::new();
-                let mut file_handler: File;
-                let mut bytes_read: u64 = 0;
-                atomic_file_counter.fetch_add(vec_file_entry.len(), Ordering::Relaxed);
-                'fe: for file_entry in vec_file_entry {
-                    if stop_receiver.is_some() && stop_receiver.unwrap().try_recv().is_ok() {
-                        check_was_breaked.store(true, Ordering::Relaxed);
-                        return None;
-                    }
-                    file_handler = match File::open(&file_entry.path) {
-                        Ok(t) => t,
-                        Err(_) => {
-                            errors.push(format!("Unable to check hash of file {}", file_entry.path.display()));
-                            continue 'fe;
+        let full_hash_results: Vec<(u64, HashMap<String, Vec<FileEntry>>, Vec<Issue>, u64)> = self.resource_limits.install(|io_throttle| {
+            pre_checked_map
+                .par_iter()
+                .map(|(size, vec_file_entry)| {
+                    let mut hashmap_with_hash: HashMap<String, Vec<FileEntry>> = Default::default();
+                    let mut errors: Vec<Issue> = Vec::new();
+                    let mut file_handler: File;
+                    let mut bytes_read: u64 = 0;
+                    atomic_file_counter.fetch_add(vec_file_entry.len(), Ordering::Relaxed);
+                    'fe: for file_entry in vec_file_entry {
+                        if scan_controller.is_some() && scan_controller.unwrap().should_stop() {
+                            check_was_breaked.store(true, Ordering::Relaxed);
+                            return None;
                         }
-                    };
-
-                    let mut hasher: blake3::Hasher = blake3::Hasher::new();
-                    let mut buffer = [0u8; 1024 * 32];
-                    let mut current_file_read_bytes: u64 = 0;
-
-                    loop {
-                        let n = match file_handler.read(&mut buffer) {
+                        file_handler = match File::open(&file_entry.path) {
                             Ok(t) => t,
-                            Err(_) => {
-                                errors.push(format!("Error happened when checking hash of file {}", file_entry.path.display()));
+                            Err(e) => {
+                                errors.push(Issue::new(IssueKind::ReadFailed, "Unable to check hash of file").with_path(&file_entry.path).with_io_error(e));
                                 continue 'fe;
                             }
                         };
-                        if n == 0 {
-                            break;
-                        }
 
-                        current_file_read_bytes += n as u64;
-                        bytes_read += n as u64;
-                        hasher.update(&buffer[..n]);
+                        let mut hasher: blake3::Hasher = blake3::Hasher::new();
+                        let mut buffer = [0u8; 1024 * 32];
+                        let mut current_file_read_bytes: u64 = 0;
+
+                        loop {
+                            let n = match file_handler.read(&mut buffer) {
+                                Ok(t) => t,
+                                Err(e) => {
+                                    errors.push(Issue::new(IssueKind::ReadFailed, "Error happened when checking hash of file").with_path(&file_entry.path).with_io_error(e));
+                                    continue 'fe;
+                                }
+                            };
+                            if n == 0 {
+                                break;
+                            }
+
+                            current_file_read_bytes += n as u64;
+                            bytes_read += n as u64;
+                            atomic_bytes_counter.fetch_add(n as u64, Ordering::Relaxed);
+                            io_throttle.consume(n as u64);
+                            hasher.update(&buffer[..n]);
 
-                        if self.check_method == CheckingMethod::HashMB && current_file_read_bytes >= HASH_MB_LIMIT_BYTES {
-                            break;
+                            if self.check_method == CheckingMethod::HashMB && current_file_read_bytes >= HASH_MB_LIMIT_BYTES {
+                                break;
+                            }
                         }
-                    }
 
-                    let hash_string: String = hasher.finalize().to_hex().to_string();
-                    hashmap_with_hash.entry(hash_string.to_string()).or_insert_with(Vec::new);
-                    hashmap_with_hash.get_mut(hash_string.as_str()).unwrap().push(file_entry.to_owned());
-                }
-                Some((*size, hashmap_with_hash, errors, bytes_read))
-            })
-            .while_some()
-            .collect();
+                        let hash_string: String = hasher.finalize().to_hex().to_string();
+                        hashmap_with_hash.entry(hash_string.to_string()).or_insert_with(Vec::new);
+                        hashmap_with_hash.get_mut(hash_string.as_str()).unwrap().push(file_entry.to_owned());
+                    }
+                    Some((*size, hashmap_with_hash, errors, bytes_read))
+                })
+                .while_some()
+                .collect()
+        });
 
         // End thread which send info to gui
         progress_thread_run.store(false, Ordering::Relaxed);
@@ -822,7 +694,7 @@ impl DuplicateFinder {
             }
         }
 
-        Common::print_time(start_time, SystemTime::now(), "check_files_hash - full hash".to_string());
+        Common::log_phase(self.get_id(), "full_hash", start_time, atomic_file_counter.load(Ordering::Relaxed));
         true
     }
 
@@ -868,9 +740,93 @@ impl DuplicateFinder {
             }
         }
 
-        Common::print_time(start_time, SystemTime::now(), "delete_files".to_string());
+        Common::log_phase(self.get_id(), "deletion", start_time, self.information.number_of_removed_files);
+    }
+}
+impl Tool for DuplicateFinder {
+    type ProgressData = ProgressData;
+
+    fn get_name(&self) -> &'static str {
+        "duplicated files"
+    }
+
+    fn get_id(&self) -> &'static str {
+        "duplicates"
+    }
+
+    fn set_included_directory(&mut self, included_directory: String) -> bool {
+        self.directories.set_included_directory(included_directory, &mut self.text_messages)
+    }
+
+    fn set_excluded_directory(&mut self, excluded_directory: String) {
+        self.directories.set_excluded_directory(excluded_directory, &mut self.text_messages);
+    }
+
+    fn set_excluded_items(&mut self, excluded_items: String) {
+        self.excluded_items.set_excluded_items(excluded_items, &mut self.text_messages);
+    }
+
+    /// Uses rules from .gitignore, .ignore and .czkawkaignore files found in scanned folders
+    fn set_use_ignore_files(&mut self, use_ignore_files: bool) {
+        self.use_ignore_files = use_ignore_files;
+    }
+
+    /// Limits number of levels of subfolders which are checked, 0 means that only included directories are checked
+    fn set_max_depth(&mut self, max_depth: Option<usize>) {
+        self.max_depth = max_depth;
+    }
+
+    fn set_modified_after(&mut self, modified_after: String) {
+        self.date_filter.set_modified_after(modified_after, &mut self.text_messages);
+    }
+
+    fn set_modified_before(&mut self, modified_before: String) {
+        self.date_filter.set_modified_before(modified_before, &mut self.text_messages);
+    }
+
+    fn get_stopped_search(&self) -> bool {
+        self.stopped_search
+    }
+
+    fn get_text_messages(&self) -> &Messages {
+        &self.text_messages
+    }
+
+    fn search(&mut self, scan_controller: Option<&ScanController>, progress_sender: Option<&futures::channel::mpsc::Sender<ProgressData>>) {
+        self.find_duplicates(scan_controller, progress_sender);
+    }
+
+    fn get_result_groups(&self) -> Vec<ResultGroup> {
+        match self.check_method {
+            CheckingMethod::Name => self
+                .files_with_identical_names
+                .values()
+                .map(|vec_file_entry| ResultGroup {
+                    entries: vec_file_entry.iter().map(ResultEntry::from).collect(),
+                })
+                .collect(),
+            CheckingMethod::Size => self
+                .files_with_identical_size
+                .values()
+                .rev()
+                .map(|vec_file_entry| ResultGroup {
+                    entries: vec_file_entry.iter().map(ResultEntry::from).collect(),
+                })
+                .collect(),
+            CheckingMethod::Hash | CheckingMethod::HashMB => self
+                .files_with_identical_hashes
+                .values()
+                .rev()
+                .flatten()
+                .map(|vec_file_entry| ResultGroup {
+                    entries: vec_file_entry.iter().map(ResultEntry::from).collect(),
+                })
+                .collect(),
+            CheckingMethod::None => Vec::new(),
+        }
     }
 }
+
 impl Default for DuplicateFinder {
     fn default() -> Self {
         Self::new()
@@ -937,10 +893,17 @@ impl DebugPrint for DuplicateFinder {
         println!("Files list size - {}", self.files_with_identical_size.len());
         println!("Hashed Files list size - {}", self.files_with_identical_hashes.len());
         println!("Allowed extensions - {:?}", self.allowed_extensions.file_extensions);
+        println!("Excluded extensions - {:?}", self.allowed_extensions.excluded_extensions);
         println!("Excluded items - {:?}", self.excluded_items.items);
         println!("Included directories - {:?}", self.directories.included_directories);
+        println!("Number of files in file list - {:?}", self.directories.file_list.as_ref().map(|e| e.len()));
         println!("Excluded directories - {:?}", self.directories.excluded_directories);
         println!("Recursive search - {}", self.recursive_search.to_string());
+        println!("Max depth - {:?}", self.max_depth);
+        println!("Resource limits - {:?}", self.resource_limits);
+        println!("Use ignore files - {}", self.use_ignore_files);
+        println!("Date filter - {:?}", self.date_filter);
+        println!("Content filter - {:?}", self.content_filter);
         println!("Minimum file size - {:?}", self.minimal_file_size);
         println!("Checking Method - {:?}", self.check_method);
         println!("Delete Method - {:?}", self.delete_method);
@@ -957,8 +920,8 @@ impl SaveResults for DuplicateFinder {
 
         let mut file = match File::create(&file_name) {
             Ok(t) => t,
-            Err(_) => {
-                self.text_messages.errors.push(format!("Failed to create file {}", file_name));
+            Err(e) => {
+                self.text_messages.errors.push(Issue::new(IssueKind::SaveFailed, "Failed to create file").with_path(&file_name).with_io_error(e));
                 return false;
             }
         };
@@ -970,7 +933,7 @@ impl SaveResults for DuplicateFinder {
         )
         .is_err()
         {
-            self.text_messages.errors.push(format!("Failed to save results to file {}", file_name));
+            self.text_messages.errors.push(Issue::new(IssueKind::SaveFailed, "Failed to save results to file").with_path(&file_name));
             return false;
         }
         match self.check_method {
@@ -1042,7 +1005,7 @@ impl SaveResults for DuplicateFinder {
                 panic!();
             }
         }
-        Common::print_time(start_time, SystemTime::now(), "save_results_to_file".to_string());
+        Common::log_time(start_time, "save_results_to_file");
         true
     }
 }
@@ -1116,13 +1079,13 @@ impl PrintResults for DuplicateFinder {
                 panic!("Checking Method shouldn't be ever set to None");
             }
         }
-        Common::print_time(start_time, SystemTime::now(), "print_entries".to_string());
+        Common::log_time(start_time, "print_entries");
     }
 }
 
 /// Functions to remove slice(vector) of files with provided method
 /// Returns size of removed elements, number of deleted and failed to delete files and modified warning list
-fn delete_files(vector: &[FileEntry], delete_method: &DeleteMethod, warnings: &mut Vec<String>) -> (u64, usize, usize) {
+fn delete_files(vector: &[FileEntry], delete_method: &DeleteMethod, warnings: &mut Vec<Issue>) -> (u64, usize, usize) {
     assert!(vector.len() > 1, "Vector length must be bigger than 1(This should be done in previous steps).");
     let mut q_index: usize = 0;
     let mut q_time: u64 = 0;
@@ -1144,9 +1107,9 @@ fn delete_files(vector: &[FileEntry], delete_method: &DeleteMethod, warnings: &m
                     removed_files += 1;
                     gained_space += vector[q_index].size;
                 }
-                Err(_) => {
+                Err(e) => {
                     failed_to_remove_files += 1;
-                    warnings.push(format!("Failed to delete {}", vector[q_index].path.display()));
+                    warnings.push(Issue::new(IssueKind::DeleteFailed, "Failed to delete").with_path(&vector[q_index].path).with_io_error(e));
                 }
             };
         }
@@ -1162,9 +1125,9 @@ fn delete_files(vector: &[FileEntry], delete_method: &DeleteMethod, warnings: &m
                     removed_files += 1;
                     gained_space += vector[q_index].size;
                 }
-                Err(_) => {
+                Err(e) => {
                     failed_to_remove_files += 1;
-                    warnings.push(format!("Failed to delete {}", vector[q_index].path.display()));
+                    warnings.push(Issue::new(IssueKind::DeleteFailed, "Failed to delete").with_path(&vector[q_index].path).with_io_error(e));
                 }
             };
         }
@@ -1182,9 +1145,9 @@ fn delete_files(vector: &[FileEntry], delete_method: &DeleteMethod, warnings: &m
                             removed_files += 1;
                             gained_space += file.size;
                         }
-                        Err(_) => {
+                        Err(e) => {
                             failed_to_remove_files += 1;
-                            warnings.push(format!("Failed to delete {}", file.path.display()));
+                            warnings.push(Issue::new(IssueKind::DeleteFailed, "Failed to delete").with_path(&file.path).with_io_error(e));
                         }
                     };
                 }
@@ -1204,9 +1167,9 @@ fn delete_files(vector: &[FileEntry], delete_method: &DeleteMethod, warnings: &m
                             removed_files += 1;
                             gained_space += file.size;
                         }
-                        Err(_) => {
+                        Err(e) => {
                             failed_to_remove_files += 1;
-                            warnings.push(format!("Failed to delete {}", file.path.display()));
+                            warnings.push(Issue::new(IssueKind::DeleteFailed, "Failed to delete").with_path(&file.path).with_io_error(e));
                         }
                     };
                 }
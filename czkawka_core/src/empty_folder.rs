@@ -1,13 +1,17 @@
 use crate::common::Common;
+use crate::common_date_filter::DateFilter;
+use crate::common_dir_traversal::{is_too_deep, IgnoreRules};
 use crate::common_directory::Directories;
 use crate::common_items::ExcludedItems;
-use crate::common_messages::Messages;
+use crate::common_messages::{Issue, IssueKind, Messages};
+use crate::common_scan_controller::ScanController;
+use crate::common_tool::{ResultEntry, ResultGroup, Tool};
 use crate::common_traits::{DebugPrint, PrintResults, SaveResults};
-use crossbeam_channel::Receiver;
 use std::collections::BTreeMap;
-use std::fs::{File, Metadata};
-use std::io::Write;
-use std::path::PathBuf;
+use std::fs::{DirEntry, File, Metadata};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::thread::sleep;
@@ -43,6 +47,9 @@ pub struct EmptyFolder {
     delete_folders: bool,
     text_messages: Messages,
     excluded_items: ExcludedItems,
+    use_ignore_files: bool,
+    max_depth: Option<usize>,
+    date_filter: DateFilter,
     empty_folder_list: BTreeMap<PathBuf, FolderEntry>, // Path, FolderEntry
     directories: Directories,
     stopped_search: bool,
@@ -69,38 +76,27 @@ impl EmptyFolder {
             delete_folders: false,
             text_messages: Messages::new(),
             excluded_items: Default::default(),
+            use_ignore_files: false,
+            max_depth: None,
+            date_filter: DateFilter::new(),
             empty_folder_list: Default::default(),
             directories: Directories::new(),
             stopped_search: false,
         }
     }
 
-    pub fn get_stopped_search(&self) -> bool {
-        self.stopped_search
-    }
-
     pub const fn get_empty_folder_list(&self) -> &BTreeMap<PathBuf, FolderEntry> {
         &self.empty_folder_list
     }
 
-    pub const fn get_text_messages(&self) -> &Messages {
-        &self.text_messages
-    }
     pub const fn get_information(&self) -> &Info {
         &self.information
     }
 
-    pub fn set_excluded_items(&mut self, excluded_items: String) {
-        self.excluded_items.set_excluded_items(excluded_items, &mut self.text_messages);
-    }
-
-    pub fn set_excluded_directory(&mut self, excluded_directory: String) {
-        self.directories.set_excluded_directory(excluded_directory, &mut self.text_messages);
-    }
     /// Public function used by CLI to search for empty folders
-    pub fn find_empty_folders(&mut self, stop_receiver: Option<&Receiver<()>>, progress_sender: Option<&futures::channel::mpsc::Sender<ProgressData>>) {
+    pub fn find_empty_folders(&mut self, scan_controller: Option<&ScanController>, progress_sender: Option<&futures::channel::mpsc::Sender<ProgressData>>) {
         self.directories.optimize_directories(true, &mut self.text_messages);
-        if !self.check_for_empty_folders(stop_receiver, progress_sender) {
+        if !self.check_for_empty_folders(scan_controller, progress_sender) {
             self.stopped_search = true;
             return;
         }
@@ -138,9 +134,9 @@ impl EmptyFolder {
 
     /// Function to check if folder are empty.
     /// Parameter initial_checking for second check before deleting to be sure that checked folder is still empty
-    fn check_for_empty_folders(&mut self, stop_receiver: Option<&Receiver<()>>, progress_sender: Option<&futures::channel::mpsc::Sender<ProgressData>>) -> bool {
+    fn check_for_empty_folders(&mut self, scan_controller: Option<&ScanController>, progress_sender: Option<&futures::channel::mpsc::Sender<ProgressData>>) -> bool {
         let start_time: SystemTime = SystemTime::now();
-        let mut folders_to_check: Vec<PathBuf> = Vec::with_capacity(1024 * 2); // This should be small enough too not see to big difference and big enough to store most of paths without needing to resize vector
+        let mut folders_to_check: Vec<(PathBuf, Option<Rc<IgnoreRules>>, usize)> = Vec::with_capacity(1024 * 2); // This should be small enough too not see to big difference and big enough to store most of paths without needing to resize vector
         let mut folders_checked: BTreeMap<PathBuf, FolderEntry> = Default::default();
 
         //// PROGRESS THREAD START
@@ -182,18 +178,17 @@ impl EmptyFolder {
                     modified_date: 0,
                 },
             );
-            folders_to_check.push(id.clone());
+            folders_to_check.push((id.clone(), None, 0));
         }
 
-        while !folders_to_check.is_empty() {
-            if stop_receiver.is_some() && stop_receiver.unwrap().try_recv().is_ok() {
+        while let Some((current_folder, parent_rules, depth)) = folders_to_check.pop() {
+            if scan_controller.is_some() && scan_controller.unwrap().should_stop() {
                 // End thread which send info to gui
                 progress_thread_run.store(false, Ordering::Relaxed);
                 progress_thread_handle.join().unwrap();
                 return false;
             }
             self.information.number_of_checked_folders += 1;
-            let current_folder = folders_to_check.pop().unwrap();
             // Checked folder may be deleted or we may not have permissions to open it so we assume that this folder is not be empty
             let read_dir = match fs::read_dir(&current_folder) {
                 Ok(t) => t,
@@ -203,14 +198,20 @@ impl EmptyFolder {
                 }
             };
 
-            'dir: for entry in read_dir {
-                let entry_data = match entry {
-                    Ok(t) => t,
-                    Err(_) => {
-                        set_as_not_empty_folder(&mut folders_checked, &current_folder);
-                        continue 'dir;
-                    } //Permissions denied
-                };
+            let mut entries: Vec<DirEntry> = Vec::new();
+            for entry in read_dir {
+                match entry {
+                    Ok(t) => entries.push(t),
+                    Err(_) => set_as_not_empty_folder(&mut folders_checked, &current_folder), //Permissions denied
+                }
+            }
+
+            let ignore_rules = match self.use_ignore_files {
+                true => IgnoreRules::load(&current_folder, &entries, parent_rules, &mut self.text_messages),
+                false => None,
+            };
+
+            'dir: for entry_data in entries {
                 let metadata: Metadata = match entry_data.metadata() {
                     Ok(t) => t,
                     Err(_) => {
@@ -222,31 +223,41 @@ impl EmptyFolder {
                 if metadata.is_dir() {
                     atomic_folder_counter.fetch_add(1, Ordering::Relaxed);
                     let next_folder = current_folder.join(entry_data.file_name());
-                    if self.excluded_items.is_excluded(&next_folder) || self.directories.is_excluded(&next_folder) {
+                    let is_ignored = match &ignore_rules {
+                        Some(ignore_rules) => ignore_rules.is_ignored(&next_folder, true),
+                        None => false,
+                    };
+                    // Folders below maximum depth are not checked, so they are treated like excluded ones
+                    if self.excluded_items.is_excluded(&next_folder) || self.directories.is_excluded(&next_folder) || is_ignored || is_too_deep(depth, self.max_depth) {
                         set_as_not_empty_folder(&mut folders_checked, &current_folder);
                         continue 'dir;
                     }
-                    folders_to_check.push(next_folder.clone());
+                    let modified_date = match metadata.modified() {
+                        Ok(t) => match t.duration_since(UNIX_EPOCH) {
+                            Ok(d) => d.as_secs(),
+                            Err(_) => {
+                                self.text_messages
+                                    .warnings
+                                    .push(Issue::new(IssueKind::InvalidMetadata, "Modification date is before Unix Epoch in folder").with_path(&current_folder));
+                                0
+                            }
+                        },
+                        Err(e) => {
+                            self.text_messages
+                                .warnings
+                                .push(Issue::new(IssueKind::InvalidMetadata, "Failed to read modification date of folder").with_path(&current_folder).with_io_error(e));
+                            // Can't read data, so assuming that is not empty
+                            set_as_not_empty_folder(&mut folders_checked, &current_folder);
+                            continue 'dir;
+                        }
+                    };
+                    folders_to_check.push((next_folder.clone(), ignore_rules.clone(), depth + 1));
                     folders_checked.insert(
                         next_folder.clone(),
                         FolderEntry {
                             parent_path: Some(current_folder.clone()),
                             is_empty: FolderEmptiness::Maybe,
-                            modified_date: match metadata.modified() {
-                                Ok(t) => match t.duration_since(UNIX_EPOCH) {
-                                    Ok(d) => d.as_secs(),
-                                    Err(_) => {
-                                        self.text_messages.warnings.push(format!("Folder {} seems to be modified before Unix Epoch.", current_folder.display()));
-                                        0
-                                    }
-                                },
-                                Err(_) => {
-                                    self.text_messages.warnings.push(format!("Failed to read modification date of folder {}", current_folder.display()));
-                                    // Can't read data, so assuming that is not empty
-                                    set_as_not_empty_folder(&mut folders_checked, &current_folder);
-                                    continue 'dir;
-                                }
-                            },
+                            modified_date,
                         },
                     );
                 } else {
@@ -261,32 +272,30 @@ impl EmptyFolder {
         // We need to set empty folder list
         #[allow(unused_mut)] // Used is later by Windows build
         for (mut name, folder_entry) in folders_checked {
-            if folder_entry.is_empty != FolderEmptiness::No {
+            // Folders outside of allowed date range are only not shown, so their empty parents are still found
+            let is_allowed_by_date = folder_entry.parent_path.is_none() || self.date_filter.is_allowed(folder_entry.modified_date);
+            if folder_entry.is_empty != FolderEmptiness::No && is_allowed_by_date {
                 self.empty_folder_list.insert(name, folder_entry);
             }
         }
 
-        Common::print_time(start_time, SystemTime::now(), "check_for_empty_folder".to_string());
+        Common::log_phase(self.get_id(), "traversal", start_time, self.empty_folder_list.len());
         true
     }
 
     /// Deletes earlier found empty folders
     fn delete_empty_folders(&mut self) {
         let start_time: SystemTime = SystemTime::now();
+        let mut removed_folders: usize = 0;
         // Folders may be deleted or require too big privileges
         for name in self.empty_folder_list.keys() {
             match fs::remove_dir_all(name) {
-                Ok(_) => (),
-                Err(_) => self.text_messages.warnings.push(format!("Failed to remove folder {}", name.display())),
+                Ok(_) => removed_folders += 1,
+                Err(e) => self.text_messages.warnings.push(Issue::new(IssueKind::DeleteFailed, "Failed to remove folder").with_path(name).with_io_error(e)),
             };
         }
 
-        Common::print_time(start_time, SystemTime::now(), "delete_files".to_string());
-    }
-
-    /// Set included dir which needs to be relative, exists etc.
-    pub fn set_included_directory(&mut self, included_directory: String) {
-        self.directories.set_included_directory(included_directory, &mut self.text_messages);
+        Common::log_phase(self.get_id(), "deletion", start_time, removed_folders);
     }
 }
 
@@ -306,6 +315,93 @@ fn set_as_not_empty_folder(folders_checked: &mut BTreeMap<PathBuf, FolderEntry>,
     }
 }
 
+impl Tool for EmptyFolder {
+    type ProgressData = ProgressData;
+
+    fn get_name(&self) -> &'static str {
+        "empty folders"
+    }
+
+    fn get_id(&self) -> &'static str {
+        "empty_folder"
+    }
+
+    /// Set included dir which needs to be relative, exists etc.
+    fn set_included_directory(&mut self, included_directory: String) -> bool {
+        self.directories.set_included_directory(included_directory, &mut self.text_messages)
+    }
+
+    fn set_excluded_directory(&mut self, excluded_directory: String) {
+        self.directories.set_excluded_directory(excluded_directory, &mut self.text_messages);
+    }
+
+    fn set_excluded_items(&mut self, excluded_items: String) {
+        self.excluded_items.set_excluded_items(excluded_items, &mut self.text_messages);
+    }
+
+    /// Folders ignored by rules from .gitignore, .ignore and .czkawkaignore files are treated like excluded ones
+    fn set_use_ignore_files(&mut self, use_ignore_files: bool) {
+        self.use_ignore_files = use_ignore_files;
+    }
+
+    /// Limits number of levels of subfolders which are checked, 0 means that only included directories are checked
+    fn set_max_depth(&mut self, max_depth: Option<usize>) {
+        self.max_depth = max_depth;
+    }
+
+    fn set_modified_after(&mut self, modified_after: String) {
+        self.date_filter.set_modified_after(modified_after, &mut self.text_messages);
+    }
+
+    fn set_modified_before(&mut self, modified_before: String) {
+        self.date_filter.set_modified_before(modified_before, &mut self.text_messages);
+    }
+
+    fn get_stopped_search(&self) -> bool {
+        self.stopped_search
+    }
+
+    fn get_text_messages(&self) -> &Messages {
+        &self.text_messages
+    }
+
+    fn search(&mut self, scan_controller: Option<&ScanController>, progress_sender: Option<&futures::channel::mpsc::Sender<ProgressData>>) {
+        self.find_empty_folders(scan_controller, progress_sender);
+    }
+
+    fn get_result_groups(&self) -> Vec<ResultGroup> {
+        if self.empty_folder_list.is_empty() {
+            return Vec::new();
+        }
+        vec![ResultGroup {
+            entries: self
+                .empty_folder_list
+                .iter()
+                .map(|(path, entry)| ResultEntry {
+                    path: path.clone(),
+                    size: 0,
+                    modified_date: entry.modified_date,
+                })
+                .collect(),
+        }]
+    }
+
+    /// Files may be added after scan, so folder is removed only when it still contains nothing but other folders
+    fn delete_entry(&self, path: &Path) -> io::Result<()> {
+        let mut folders_to_check: Vec<PathBuf> = vec![path.to_path_buf()];
+        while let Some(current_folder) = folders_to_check.pop() {
+            for entry in fs::read_dir(&current_folder)? {
+                let entry = entry?;
+                if !entry.file_type()?.is_dir() {
+                    return Err(io::Error::new(io::ErrorKind::Other, format!("Folder isn't empty, it contains {}", entry.path().display())));
+                }
+                folders_to_check.push(entry.path());
+            }
+        }
+        fs::remove_dir_all(path)
+    }
+}
+
 impl Default for EmptyFolder {
     fn default() -> Self {
         Self::new()
@@ -338,14 +434,14 @@ impl SaveResults for EmptyFolder {
 
         let mut file = match File::create(&file_name) {
             Ok(t) => t,
-            Err(_) => {
-                self.text_messages.errors.push("Failed to create file ".to_string() + file_name.as_str());
+            Err(e) => {
+                self.text_messages.errors.push(Issue::new(IssueKind::SaveFailed, "Failed to create file").with_path(&file_name).with_io_error(e));
                 return false;
             }
         };
 
         if writeln!(file, "Results of searching {:?} with excluded directories {:?}", self.directories.included_directories, self.directories.excluded_directories).is_err() {
-            self.text_messages.errors.push("Failed to save results to file ".to_string() + file_name.as_str());
+            self.text_messages.errors.push(Issue::new(IssueKind::SaveFailed, "Failed to save results to file").with_path(&file_name));
             return false;
         }
 
@@ -358,7 +454,7 @@ impl SaveResults for EmptyFolder {
         } else {
             write!(file, "Not found any empty folders.").unwrap();
         }
-        Common::print_time(start_time, SystemTime::now(), "save_results_to_file".to_string());
+        Common::log_time(start_time, "save_results_to_file");
         true
     }
 }
@@ -373,3 +469,45 @@ impl PrintResults for EmptyFolder {
         }
     }
 }
+
+// Modification date of folders is set with libc
+#[cfg(all(test, target_os = "linux"))]
+mod test {
+    use crate::common_tool::Tool;
+    use crate::empty_folder::EmptyFolder;
+    use std::ffi::CString;
+    use std::fs;
+    use std::os::unix::ffi::OsStrExt;
+    use std::path::{Path, PathBuf};
+
+    fn set_modified_date(path: &Path, seconds: libc::time_t) {
+        let path = CString::new(path.as_os_str().as_bytes()).unwrap();
+        let times = [libc::timeval { tv_sec: seconds, tv_usec: 0 }; 2];
+        assert_eq!(unsafe { libc::utimes(path.as_ptr(), times.as_ptr()) }, 0);
+    }
+
+    #[test]
+    fn test_empty_folders_outside_of_date_range() {
+        let root = fs::canonicalize(std::env::temp_dir()).unwrap().join(format!("czkawka_test_empty_folders_outside_of_date_range_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("parent").join("new")).unwrap();
+        fs::create_dir_all(root.join("parent").join("old")).unwrap();
+        fs::create_dir_all(root.join("old")).unwrap();
+        fs::write(root.join("file.txt"), "czkawka").unwrap();
+        // 2001-09-09
+        for old_folder in [root.join("parent").join("old"), root.join("old")].iter() {
+            set_modified_date(old_folder, 1_000_000_000);
+        }
+
+        let mut ef = EmptyFolder::new();
+        assert!(ef.set_included_directory(root.to_string_lossy().to_string()));
+        ef.set_modified_after("2010-01-01".to_string());
+        ef.find_empty_folders(None, None);
+
+        // Old folder inside of parent is removed together with it, but alone it is not shown
+        let empty_folders: Vec<PathBuf> = ef.get_empty_folder_list().keys().cloned().collect();
+        assert_eq!(empty_folders, vec![root.join("parent")]);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
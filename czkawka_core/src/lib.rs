@@ -1,3 +1,4 @@
+#[cfg(feature = "same-music")]
 #[macro_use]
 extern crate bitflags;
 
@@ -8,13 +9,27 @@ pub mod empty_folder;
 pub mod temporary;
 
 pub mod common;
+pub mod common_content_type;
+pub mod common_date_filter;
+pub mod common_dir_traversal;
 pub mod common_directory;
 pub mod common_extensions;
 pub mod common_items;
 pub mod common_messages;
+pub mod common_profile;
+pub mod common_progress;
+pub mod common_resources;
+pub mod common_scan_controller;
+pub mod common_tool;
 pub mod common_traits;
+#[cfg(feature = "same-music")]
 pub mod same_music;
+#[cfg(feature = "similar-images")]
 pub mod similar_images;
+#[cfg(feature = "similar-images")]
+pub mod similar_images_cache;
+#[cfg(feature = "similar-images")]
+pub mod similar_images_orientation;
 pub mod zeroed;
 
 pub const CZKAWKA_VERSION: &str = env!("CARGO_PKG_VERSION");
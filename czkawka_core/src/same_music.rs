@@ -1,19 +1,26 @@
-use std::fs::{File, Metadata};
+use std::fs::File;
 use std::io::prelude::*;
-use std::path::PathBuf;
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
-use std::{fs, thread};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
 
 use crate::common::Common;
+use crate::common_content_type::{ContentFilter, ContentType};
+use crate::common_date_filter::DateFilter;
+use crate::common_dir_traversal::DirTraversal;
 use crate::common_directory::Directories;
+use crate::common_extensions::{has_extension, Extensions};
 use crate::common_items::ExcludedItems;
-use crate::common_messages::Messages;
+use crate::common_messages::{Issue, IssueKind, Messages};
+use crate::common_progress::{BytesProgress, BytesProgressMeter};
+use crate::common_resources::ResourceLimits;
+use crate::common_scan_controller::ScanController;
+use crate::common_tool::{ResultEntry, ResultGroup, Tool};
 use crate::common_traits::*;
-use audiotags::Tag;
-use crossbeam_channel::Receiver;
+use audiotags::{Tag, TagType};
 use rayon::prelude::*;
 use std::collections::HashMap;
-use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::thread::sleep;
 
@@ -23,6 +30,8 @@ pub struct ProgressData {
     pub max_stage: u8,
     pub music_checked: usize,
     pub music_to_check: usize,
+    /// Size of files which were checked, known only when reading content of files
+    pub bytes_progress: BytesProgress,
 }
 
 #[derive(Eq, PartialEq, Clone, Debug)]
@@ -63,6 +72,16 @@ pub struct FileEntry {
     // pub time: u32,
 }
 
+impl From<&FileEntry> for ResultEntry {
+    fn from(file_entry: &FileEntry) -> Self {
+        Self {
+            path: file_entry.path.clone(),
+            size: file_entry.size,
+            modified_date: file_entry.modified_date,
+        }
+    }
+}
+
 /// Info struck with helpful information's about results
 #[derive(Default)]
 pub struct Info {
@@ -90,8 +109,14 @@ pub struct SameMusic {
     duplicated_music_entries: Vec<Vec<FileEntry>>,
     directories: Directories,
     excluded_items: ExcludedItems,
+    allowed_extensions: Extensions,
     minimal_file_size: u64,
     recursive_search: bool,
+    max_depth: Option<usize>,
+    use_ignore_files: bool,
+    date_filter: DateFilter,
+    content_filter: ContentFilter,
+    resource_limits: ResourceLimits,
     delete_method: DeleteMethod,
     music_similarity: MusicSimilarity,
     stopped_search: bool,
@@ -103,8 +128,14 @@ impl SameMusic {
             text_messages: Messages::new(),
             information: Info::new(),
             recursive_search: true,
+            max_depth: None,
+            use_ignore_files: false,
+            date_filter: DateFilter::new(),
+            content_filter: ContentFilter::new(),
+            resource_limits: ResourceLimits::new(),
             directories: Directories::new(),
             excluded_items: ExcludedItems::new(),
+            allowed_extensions: Extensions::new(),
             music_entries: Vec::with_capacity(2048),
             delete_method: DeleteMethod::None,
             music_similarity: MusicSimilarity::NONE,
@@ -115,17 +146,17 @@ impl SameMusic {
         }
     }
 
-    pub fn find_same_music(&mut self, stop_receiver: Option<&Receiver<()>>, progress_sender: Option<&futures::channel::mpsc::Sender<ProgressData>>) {
+    pub fn find_same_music(&mut self, scan_controller: Option<&ScanController>, progress_sender: Option<&futures::channel::mpsc::Sender<ProgressData>>) {
         self.directories.optimize_directories(self.recursive_search, &mut self.text_messages);
-        if !self.check_files(stop_receiver, progress_sender) {
+        if !self.check_files(scan_controller, progress_sender) {
             self.stopped_search = true;
             return;
         }
-        if !self.check_records_multithreaded(stop_receiver, progress_sender) {
+        if !self.check_records_multithreaded(scan_controller, progress_sender) {
             self.stopped_search = true;
             return;
         }
-        if !self.check_for_duplicates(stop_receiver, progress_sender) {
+        if !self.check_for_duplicates(scan_controller, progress_sender) {
             self.stopped_search = true;
             return;
         }
@@ -133,10 +164,6 @@ impl SameMusic {
         self.debug_print();
     }
 
-    pub fn get_stopped_search(&self) -> bool {
-        self.stopped_search
-    }
-
     pub const fn get_duplicated_music_entries(&self) -> &Vec<Vec<FileEntry>> {
         &self.duplicated_music_entries
     }
@@ -144,10 +171,6 @@ impl SameMusic {
         &self.music_similarity
     }
 
-    pub const fn get_text_messages(&self) -> &Messages {
-        &self.text_messages
-    }
-
     pub const fn get_information(&self) -> &Info {
         &self.information
     }
@@ -160,16 +183,49 @@ impl SameMusic {
         self.recursive_search = recursive_search;
     }
 
-    pub fn set_included_directory(&mut self, included_directory: String) -> bool {
-        self.directories.set_included_directory(included_directory, &mut self.text_messages)
+    /// Only files with these content types(image, audio, video, archive, document) will be checked
+    pub fn set_content_types(&mut self, content_types: String) {
+        self.content_filter.set_content_types(content_types, &mut self.text_messages);
     }
 
-    pub fn set_excluded_directory(&mut self, excluded_directory: String) {
-        self.directories.set_excluded_directory(excluded_directory, &mut self.text_messages);
+    /// Recognizes type of files by their content instead of extension
+    pub fn set_detect_by_content(&mut self, detect_by_content: bool) {
+        self.content_filter.set_detect_by_content(detect_by_content);
     }
 
-    pub fn set_excluded_items(&mut self, excluded_items: String) {
-        self.excluded_items.set_excluded_items(excluded_items, &mut self.text_messages);
+    /// Limits speed of reading files in bytes per second, None or 0 disables limit
+    pub fn set_io_limit(&mut self, io_limit: Option<u64>) {
+        self.resource_limits.set_io_limit(io_limit);
+    }
+
+    /// Scan runs with lowered CPU and I/O priority, so it slows down other programs less
+    pub fn set_low_priority(&mut self, low_priority: bool) {
+        self.resource_limits.set_low_priority(low_priority);
+    }
+
+    /// Number of threads used by parallel stages, None or 0 means number of logical CPUs
+    pub fn set_threads(&mut self, threads: Option<usize>) {
+        self.resource_limits.set_threads(threads);
+    }
+
+    /// Files from list(read from file or "-" for standard input) are checked instead of files from included directories
+    pub fn set_file_list(&mut self, file_list: String, null_separated: bool) -> bool {
+        self.directories.set_file_list(file_list, null_separated, &mut self.text_messages)
+    }
+
+    /// List of allowed extensions, only files with this extensions will be checked
+    pub fn set_allowed_extensions(&mut self, allowed_extensions: String) {
+        self.allowed_extensions.set_allowed_extensions(allowed_extensions, &mut self.text_messages);
+    }
+
+    /// Files with these extensions are skipped even if they are allowed
+    pub fn set_excluded_extensions(&mut self, excluded_extensions: String) {
+        self.allowed_extensions.set_excluded_extensions(excluded_extensions, &mut self.text_messages);
+    }
+
+    /// Named groups of extensions which can be used in allowed and excluded extensions
+    pub fn set_extension_groups(&mut self, extension_groups: String) {
+        self.allowed_extensions.set_extension_groups(extension_groups, &mut self.text_messages);
     }
 
     pub fn set_music_similarity(&mut self, music_similarity: MusicSimilarity) {
@@ -177,15 +233,8 @@ impl SameMusic {
     }
 
     /// Check files for any with size == 0
-    fn check_files(&mut self, stop_receiver: Option<&Receiver<()>>, progress_sender: Option<&futures::channel::mpsc::Sender<ProgressData>>) -> bool {
+    fn check_files(&mut self, scan_controller: Option<&ScanController>, progress_sender: Option<&futures::channel::mpsc::Sender<ProgressData>>) -> bool {
         let start_time: SystemTime = SystemTime::now();
-        let mut folders_to_check: Vec<PathBuf> = Vec::with_capacity(1024 * 2); // This should be small enough too not see to big difference and big enough to store most of paths without needing to resize vector
-
-        // Add root folders for finding
-        for id in &self.directories.included_directories {
-            folders_to_check.push(id.clone());
-        }
-        self.information.number_of_checked_folders += folders_to_check.len();
 
         //// PROGRESS THREAD START
         const LOOP_DURATION: u32 = 200; //in ms
@@ -205,6 +254,7 @@ impl SameMusic {
                         max_stage: 2,
                         music_checked: atomic_file_counter.load(Ordering::Relaxed) as usize,
                         music_to_check: 0,
+                        bytes_progress: BytesProgress::default(),
                     })
                     .unwrap();
                 if !progress_thread_run.load(Ordering::Relaxed) {
@@ -216,118 +266,57 @@ impl SameMusic {
             progress_thread_handle = thread::spawn(|| {});
         }
         //// PROGRESS THREAD END
-        while !folders_to_check.is_empty() {
-            if stop_receiver.is_some() && stop_receiver.unwrap().try_recv().is_ok() {
-                // End thread which send info to gui
-                progress_thread_run.store(false, Ordering::Relaxed);
-                progress_thread_handle.join().unwrap();
-                return false;
-            }
-            let current_folder = folders_to_check.pop().unwrap();
-
-            // Read current dir, if permission are denied just go to next
-            let read_dir = match fs::read_dir(&current_folder) {
-                Ok(t) => t,
-                Err(_) => {
-                    self.text_messages.warnings.push(format!("Cannot open dir {}", current_folder.display()));
-                    continue;
-                } // Permissions denied
-            };
-
-            // Check every sub folder/file/link etc.
-            'dir: for entry in read_dir {
-                let entry_data = match entry {
-                    Ok(t) => t,
-                    Err(_) => {
-                        self.text_messages.warnings.push(format!("Cannot read entry in dir {}", current_folder.display()));
-                        continue 'dir;
-                    } //Permissions denied
-                };
-                let metadata: Metadata = match entry_data.metadata() {
-                    Ok(t) => t,
-                    Err(_) => {
-                        self.text_messages.warnings.push(format!("Cannot read metadata in dir {}", current_folder.display()));
-                        continue 'dir;
-                    } //Permissions denied
-                };
-                if metadata.is_dir() {
-                    self.information.number_of_checked_folders += 1;
-
-                    if !self.recursive_search {
-                        continue 'dir;
-                    }
-
-                    let next_folder = current_folder.join(entry_data.file_name());
-                    if self.directories.is_excluded(&next_folder) || self.excluded_items.is_excluded(&next_folder) {
-                        continue 'dir;
-                    }
-
-                    folders_to_check.push(next_folder);
-                } else if metadata.is_file() {
-                    atomic_file_counter.fetch_add(1, Ordering::Relaxed);
-                    // Checking files
-                    if metadata.len() >= self.minimal_file_size {
-                        let current_file_name = current_folder.join(entry_data.file_name());
-                        if self.excluded_items.is_excluded(&current_file_name) {
-                            self.information.number_of_ignored_files += 1;
-                            continue 'dir;
-                        }
-
-                        let allowed_extensions = [".mp3", ".flac", ".m4a"];
-
-                        if !allowed_extensions.iter().any(|r| current_file_name.to_string_lossy().ends_with(r)) {
-                            self.information.number_of_ignored_files += 1;
-                            continue 'dir;
-                        }
-
-                        // Creating new file entry
-                        let file_entry: FileEntry = FileEntry {
-                            size: metadata.len(),
-                            path: current_file_name.clone(),
-                            modified_date: match metadata.modified() {
-                                Ok(t) => match t.duration_since(UNIX_EPOCH) {
-                                    Ok(d) => d.as_secs(),
-                                    Err(_) => {
-                                        self.text_messages.warnings.push(format!("File {} seems to be modified before Unix Epoch.", current_file_name.display()));
-                                        0
-                                    }
-                                },
-                                Err(_) => {
-                                    self.text_messages.warnings.push(format!("Unable to get modification date from file {}", current_file_name.display()));
-                                    continue 'dir;
-                                } // Permissions Denied
-                            },
-                            title: "".to_string(),
-
-                            artist: "".to_string(),
-                            album_title: "".to_string(),
-                            album_artist: "".to_string(),
-                            year: 0,
-                        };
-
-                        // Adding files to Vector
-                        self.music_to_check.push(file_entry);
-
-                        self.information.number_of_checked_files += 1;
-                    } else {
-                        self.information.number_of_ignored_files += 1;
-                    }
-                } else {
-                    // Probably this is symbolic links so we are free to ignore this
-                    self.information.number_of_ignored_things += 1;
-                }
-            }
+        // When detecting by content, files with any extension are checked if they really are audio files
+        let mut content_filter = self.content_filter.clone();
+        let mut dir_traversal = DirTraversal::new(&self.directories, &self.excluded_items);
+        if content_filter.detect_by_content {
+            content_filter.required_content_type = Some(ContentType::Audio);
+        } else {
+            dir_traversal.set_file_filter(|file_name_lowercase, _| is_music_file(file_name_lowercase));
         }
+        dir_traversal.set_recursive_search(self.recursive_search);
+        dir_traversal.set_max_depth(self.max_depth);
+        dir_traversal.set_use_ignore_files(self.use_ignore_files);
+        dir_traversal.set_date_filter(self.date_filter);
+        dir_traversal.set_content_filter(&content_filter);
+        dir_traversal.set_extensions(&self.allowed_extensions);
+        dir_traversal.set_minimal_file_size(self.minimal_file_size);
+        let traversal_result = dir_traversal.run(scan_controller, &atomic_file_counter, &mut self.text_messages);
+
         // End thread which send info to gui
         progress_thread_run.store(false, Ordering::Relaxed);
         progress_thread_handle.join().unwrap();
+
+        let (files, traversal_information) = match traversal_result {
+            Some(t) => t,
+            None => return false,
+        };
+        self.information.number_of_checked_files += traversal_information.number_of_checked_files;
+        self.information.number_of_checked_folders += traversal_information.number_of_checked_folders;
+        self.information.number_of_ignored_files += traversal_information.number_of_ignored_files;
+        self.information.number_of_ignored_things += traversal_information.number_of_ignored_things;
+
+        // Adding files to Vector
+        for fe in files {
+            self.music_to_check.push(FileEntry {
+                size: fe.size,
+                path: fe.path,
+                modified_date: fe.modified_date,
+                title: "".to_string(),
+
+                artist: "".to_string(),
+                album_title: "".to_string(),
+                album_artist: "".to_string(),
+                year: 0,
+            });
+        }
         self.information.number_of_music_entries = self.music_entries.len();
 
-        Common::print_time(start_time, SystemTime::now(), "check_files".to_string());
+        Common::log_phase(self.get_id(), "traversal", start_time, self.information.number_of_checked_files);
         true
     }
 
-    fn check_records_multithreaded(&mut self, stop_receiver: Option<&Receiver<()>>, progress_sender: Option<&futures::channel::mpsc::Sender<ProgressData>>) -> bool {
+    fn check_records_multithreaded(&mut self, scan_controller: Option<&ScanController>, progress_sender: Option<&futures::channel::mpsc::Sender<ProgressData>>) -> bool {
         let start_time: SystemTime = SystemTime::now();
 
         let check_was_breaked = AtomicBool::new(false); // Used for breaking from GUI and ending check thread
@@ -337,75 +326,94 @@ impl SameMusic {
         let progress_thread_run = Arc::new(AtomicBool::new(true));
 
         let atomic_file_counter = Arc::new(AtomicUsize::new(0));
+        let atomic_bytes_counter = Arc::new(AtomicU64::new(0));
 
         let progress_thread_handle;
         if let Some(progress_sender) = progress_sender {
             let mut progress_send = progress_sender.clone();
             let progress_thread_run = progress_thread_run.clone();
             let atomic_file_counter = atomic_file_counter.clone();
+            let atomic_bytes_counter = atomic_bytes_counter.clone();
             let music_to_check = self.music_to_check.len();
-            progress_thread_handle = thread::spawn(move || loop {
-                progress_send
-                    .try_send(ProgressData {
-                        current_stage: 1,
-                        max_stage: 2,
-                        music_checked: atomic_file_counter.load(Ordering::Relaxed) as usize,
-                        music_to_check,
-                    })
-                    .unwrap();
-                if !progress_thread_run.load(Ordering::Relaxed) {
-                    break;
+            let bytes_to_check = self.music_to_check.iter().map(|e| e.size).sum();
+            progress_thread_handle = thread::spawn(move || {
+                let mut bytes_progress_meter = BytesProgressMeter::new();
+                loop {
+                    progress_send
+                        .try_send(ProgressData {
+                            current_stage: 1,
+                            max_stage: 2,
+                            music_checked: atomic_file_counter.load(Ordering::Relaxed) as usize,
+                            music_to_check,
+                            bytes_progress: bytes_progress_meter.measure(atomic_bytes_counter.load(Ordering::Relaxed), bytes_to_check),
+                        })
+                        .unwrap();
+                    if !progress_thread_run.load(Ordering::Relaxed) {
+                        break;
+                    }
+                    sleep(Duration::from_millis(LOOP_DURATION as u64));
                 }
-                sleep(Duration::from_millis(LOOP_DURATION as u64));
             });
         } else {
             progress_thread_handle = thread::spawn(|| {});
         }
         //// PROGRESS THREAD END
 
-        let vec_file_entry = self
-            .music_to_check
-            .par_iter()
-            .map(|file_entry| {
-                atomic_file_counter.fetch_add(1, Ordering::Relaxed);
-                if stop_receiver.is_some() && stop_receiver.unwrap().try_recv().is_ok() {
-                    check_was_breaked.store(true, Ordering::Relaxed);
-                    return None;
-                }
-                let mut file_entry = file_entry.clone();
-
-                let tag = match Tag::new().read_from_path(&file_entry.path) {
-                    Ok(t) => t,
-                    Err(_) => return Some(None), // Data not in utf-8, etc.
-                };
-
-                file_entry.title = match tag.title() {
-                    Some(t) => t.to_string(),
-                    None => "".to_string(),
-                };
-                file_entry.artist = match tag.artist() {
-                    Some(t) => t.to_string(),
-                    None => "".to_string(),
-                };
-                file_entry.album_title = match tag.album_title() {
-                    Some(t) => t.to_string(),
-                    None => "".to_string(),
-                };
-                file_entry.album_artist = match tag.album_artist() {
-                    Some(t) => t.to_string(),
-                    None => "".to_string(),
-                };
-                file_entry.year = match tag.year() {
-                    Some(t) => t,
-                    None => 0,
-                };
-
-                Some(Some(file_entry))
-            })
-            .while_some()
-            .filter(|file_entry| file_entry.is_some())
-            .map(|file_entry| file_entry.unwrap())
-            .collect::<Vec<_>>();
+        let detect_by_content = self.content_filter.detect_by_content;
+        let vec_file_entry = self.resource_limits.install(|io_throttle| {
+            self.music_to_check
+                .par_iter()
+                .map(|file_entry| {
+                    atomic_file_counter.fetch_add(1, Ordering::Relaxed);
+                    atomic_bytes_counter.fetch_add(file_entry.size, Ordering::Relaxed);
+                    if scan_controller.is_some() && scan_controller.unwrap().should_stop() {
+                        check_was_breaked.store(true, Ordering::Relaxed);
+                        return None;
+                    }
+                    let mut file_entry = file_entry.clone();
+                    // Tag parser reads only tags at the beginning or end of file, so only estimate of their size is counted instead of whole file
+                    io_throttle.consume(file_entry.size.min(ESTIMATED_TAG_READ_SIZE));
+
+                    let mut tag_reader = Tag::new();
+                    if detect_by_content {
+                        match get_tag_type_from_content(&file_entry.path) {
+                            Some(tag_type) => tag_reader = tag_reader.with_tag_type(tag_type),
+                            None => return Some(None), // Tags of this audio format are not supported
+                        }
+                    }
+                    let tag = match tag_reader.read_from_path(&file_entry.path) {
+                        Ok(t) => t,
+                        Err(_) => return Some(None), // Data not in utf-8, etc.
+                    };
+
+                    file_entry.title = match tag.title() {
+                        Some(t) => t.to_string(),
+                        None => "".to_string(),
+                    };
+                    file_entry.artist = match tag.artist() {
+                        Some(t) => t.to_string(),
+                        None => "".to_string(),
+                    };
+                    file_entry.album_title = match tag.album_title() {
+                        Some(t) => t.to_string(),
+                        None => "".to_string(),
+                    };
+                    file_entry.album_artist = match tag.album_artist() {
+                        Some(t) => t.to_string(),
+                        None => "".to_string(),
+                    };
+                    file_entry.year = match tag.year() {
+                        Some(t) => t,
+                        None => 0,
+                    };
+
+                    Some(Some(file_entry))
+                })
+                .while_some()
+                .filter(|file_entry| file_entry.is_some())
+                .map(|file_entry| file_entry.unwrap())
+                .collect::<Vec<_>>()
+        });
 
         // End thread which send info to gui
         progress_thread_run.store(false, Ordering::Relaxed);
@@ -419,10 +427,10 @@ impl SameMusic {
         // Adding files to Vector
         self.music_entries = vec_file_entry;
 
-        Common::print_time(start_time, SystemTime::now(), "check_records_multithreaded".to_string());
+        Common::log_phase(self.get_id(), "reading_tags", start_time, self.music_entries.len());
         true
     }
-    fn check_for_duplicates(&mut self, stop_receiver: Option<&Receiver<()>>, progress_sender: Option<&futures::channel::mpsc::Sender<ProgressData>>) -> bool {
+    fn check_for_duplicates(&mut self, scan_controller: Option<&ScanController>, progress_sender: Option<&futures::channel::mpsc::Sender<ProgressData>>) -> bool {
         if MusicSimilarity::NONE == self.music_similarity {
             panic!("This can't be none");
         }
@@ -447,6 +455,7 @@ impl SameMusic {
                         max_stage: 2,
                         music_checked: atomic_file_counter.load(Ordering::Relaxed) as usize,
                         music_to_check,
+                        bytes_progress: BytesProgress::default(),
                     })
                     .unwrap();
                 if !progress_thread_run.load(Ordering::Relaxed) {
@@ -465,7 +474,7 @@ impl SameMusic {
         if (self.music_similarity & MusicSimilarity::TITLE) == MusicSimilarity::TITLE {
             for vec_file_entry in old_duplicates {
                 atomic_file_counter.fetch_add(1, Ordering::Relaxed);
-                if stop_receiver.is_some() && stop_receiver.unwrap().try_recv().is_ok() {
+                if scan_controller.is_some() && scan_controller.unwrap().should_stop() {
                     // End thread which send info to gui
                     progress_thread_run.store(false, Ordering::Relaxed);
                     progress_thread_handle.join().unwrap();
@@ -492,7 +501,7 @@ impl SameMusic {
         if (self.music_similarity & MusicSimilarity::ARTIST) == MusicSimilarity::ARTIST {
             for vec_file_entry in old_duplicates {
                 atomic_file_counter.fetch_add(1, Ordering::Relaxed);
-                if stop_receiver.is_some() && stop_receiver.unwrap().try_recv().is_ok() {
+                if scan_controller.is_some() && scan_controller.unwrap().should_stop() {
                     // End thread which send info to gui
                     progress_thread_run.store(false, Ordering::Relaxed);
                     progress_thread_handle.join().unwrap();
@@ -519,7 +528,7 @@ impl SameMusic {
         if (self.music_similarity & MusicSimilarity::ALBUM_TITLE) == MusicSimilarity::ALBUM_TITLE {
             for vec_file_entry in old_duplicates {
                 atomic_file_counter.fetch_add(1, Ordering::Relaxed);
-                if stop_receiver.is_some() && stop_receiver.unwrap().try_recv().is_ok() {
+                if scan_controller.is_some() && scan_controller.unwrap().should_stop() {
                     // End thread which send info to gui
                     progress_thread_run.store(false, Ordering::Relaxed);
                     progress_thread_handle.join().unwrap();
@@ -546,7 +555,7 @@ impl SameMusic {
         if (self.music_similarity & MusicSimilarity::ALBUM_ARTIST) == MusicSimilarity::ALBUM_ARTIST {
             for vec_file_entry in old_duplicates {
                 atomic_file_counter.fetch_add(1, Ordering::Relaxed);
-                if stop_receiver.is_some() && stop_receiver.unwrap().try_recv().is_ok() {
+                if scan_controller.is_some() && scan_controller.unwrap().should_stop() {
                     // End thread which send info to gui
                     progress_thread_run.store(false, Ordering::Relaxed);
                     progress_thread_handle.join().unwrap();
@@ -573,7 +582,7 @@ impl SameMusic {
         if (self.music_similarity & MusicSimilarity::YEAR) == MusicSimilarity::YEAR {
             for vec_file_entry in old_duplicates {
                 atomic_file_counter.fetch_add(1, Ordering::Relaxed);
-                if stop_receiver.is_some() && stop_receiver.unwrap().try_recv().is_ok() {
+                if scan_controller.is_some() && scan_controller.unwrap().should_stop() {
                     // End thread which send info to gui
                     progress_thread_run.store(false, Ordering::Relaxed);
                     progress_thread_handle.join().unwrap();
@@ -606,7 +615,7 @@ impl SameMusic {
         progress_thread_run.store(false, Ordering::Relaxed);
         progress_thread_handle.join().unwrap();
 
-        Common::print_time(start_time, SystemTime::now(), "check_for_duplicates".to_string());
+        Common::log_phase(self.get_id(), "grouping", start_time, self.information.number_of_duplicates_music_files);
         true
     }
 
@@ -634,9 +643,72 @@ impl SameMusic {
         //     }
         // }
 
-        Common::print_time(start_time, SystemTime::now(), "delete_files".to_string());
+        Common::log_phase(self.get_id(), "deletion", start_time, 0);
     }
 }
+impl Tool for SameMusic {
+    type ProgressData = ProgressData;
+
+    fn get_name(&self) -> &'static str {
+        "same music"
+    }
+
+    fn get_id(&self) -> &'static str {
+        "same_music"
+    }
+
+    fn set_included_directory(&mut self, included_directory: String) -> bool {
+        self.directories.set_included_directory(included_directory, &mut self.text_messages)
+    }
+
+    fn set_excluded_directory(&mut self, excluded_directory: String) {
+        self.directories.set_excluded_directory(excluded_directory, &mut self.text_messages);
+    }
+
+    fn set_excluded_items(&mut self, excluded_items: String) {
+        self.excluded_items.set_excluded_items(excluded_items, &mut self.text_messages);
+    }
+
+    /// Uses rules from .gitignore, .ignore and .czkawkaignore files found in scanned folders
+    fn set_use_ignore_files(&mut self, use_ignore_files: bool) {
+        self.use_ignore_files = use_ignore_files;
+    }
+
+    /// Limits number of levels of subfolders which are checked, 0 means that only included directories are checked
+    fn set_max_depth(&mut self, max_depth: Option<usize>) {
+        self.max_depth = max_depth;
+    }
+
+    fn set_modified_after(&mut self, modified_after: String) {
+        self.date_filter.set_modified_after(modified_after, &mut self.text_messages);
+    }
+
+    fn set_modified_before(&mut self, modified_before: String) {
+        self.date_filter.set_modified_before(modified_before, &mut self.text_messages);
+    }
+
+    fn get_stopped_search(&self) -> bool {
+        self.stopped_search
+    }
+
+    fn get_text_messages(&self) -> &Messages {
+        &self.text_messages
+    }
+
+    fn search(&mut self, scan_controller: Option<&ScanController>, progress_sender: Option<&futures::channel::mpsc::Sender<ProgressData>>) {
+        self.find_same_music(scan_controller, progress_sender);
+    }
+
+    fn get_result_groups(&self) -> Vec<ResultGroup> {
+        self.duplicated_music_entries
+            .iter()
+            .map(|vec_file_entry| ResultGroup {
+                entries: vec_file_entry.iter().map(ResultEntry::from).collect(),
+            })
+            .collect()
+    }
+}
+
 impl Default for SameMusic {
     fn default() -> Self {
         Self::new()
@@ -668,13 +740,21 @@ impl DebugPrint for SameMusic {
 
         println!("### Other");
 
+        println!("Allowed extensions - {:?}", self.allowed_extensions.file_extensions);
+        println!("Excluded extensions - {:?}", self.allowed_extensions.excluded_extensions);
         println!("Excluded items - {:?}", self.excluded_items.items);
         println!("Minimum file size - {:?}", self.minimal_file_size);
         println!("Found files music - {}", self.music_entries.len());
         println!("Found duplicated files music - {}", self.duplicated_music_entries.len());
         println!("Included directories - {:?}", self.directories.included_directories);
+        println!("Number of files in file list - {:?}", self.directories.file_list.as_ref().map(|e| e.len()));
         println!("Excluded directories - {:?}", self.directories.excluded_directories);
         println!("Recursive search - {}", self.recursive_search.to_string());
+        println!("Max depth - {:?}", self.max_depth);
+        println!("Resource limits - {:?}", self.resource_limits);
+        println!("Use ignore files - {}", self.use_ignore_files);
+        println!("Date filter - {:?}", self.date_filter);
+        println!("Content filter - {:?}", self.content_filter);
         println!("Delete Method - {:?}", self.delete_method);
         println!("-----------------------------------------");
     }
@@ -689,8 +769,8 @@ impl SaveResults for SameMusic {
 
         let mut file = match File::create(&file_name) {
             Ok(t) => t,
-            Err(_) => {
-                self.text_messages.errors.push(format!("Failed to create file {}", file_name));
+            Err(e) => {
+                self.text_messages.errors.push(Issue::new(IssueKind::SaveFailed, "Failed to create file").with_path(&file_name).with_io_error(e));
                 return false;
             }
         };
@@ -702,7 +782,7 @@ impl SaveResults for SameMusic {
         )
         .is_err()
         {
-            self.text_messages.errors.push(format!("Failed to save results to file {}", file_name));
+            self.text_messages.errors.push(Issue::new(IssueKind::SaveFailed, "Failed to save results to file").with_path(&file_name));
             return false;
         }
 
@@ -714,7 +794,7 @@ impl SaveResults for SameMusic {
         } else {
             write!(file, "Not found any empty files.").unwrap();
         }
-        Common::print_time(start_time, SystemTime::now(), "save_results_to_file".to_string());
+        Common::log_time(start_time, "save_results_to_file");
         true
     }
 }
@@ -739,6 +819,27 @@ impl PrintResults for SameMusic {
             println!();
         }
 
-        Common::print_time(start_time, SystemTime::now(), "print_entries".to_string());
+        Common::log_time(start_time, "print_entries");
+    }
+}
+
+/// Approximate amount of data read by tag parser, used by I/O limit
+/// Most tags with embedded cover fit in it, ID3v1 tag at the end of file has only 128 bytes
+const ESTIMATED_TAG_READ_SIZE: u64 = 64 * 1024;
+
+/// Music formats which tags can be read, user allowed and excluded extensions are checked separately
+const MUSIC_EXTENSIONS: [&str; 3] = ["mp3", "flac", "m4a"];
+
+fn is_music_file(file_name_lowercase: &str) -> bool {
+    MUSIC_EXTENSIONS.iter().any(|e| has_extension(file_name_lowercase, e))
+}
+
+/// Without this, tags would be read depending on extension of file
+fn get_tag_type_from_content(path: &Path) -> Option<TagType> {
+    match infer::get_from_path(path).ok()??.mime_type() {
+        "audio/mpeg" => Some(TagType::Id3v2),
+        "audio/x-flac" => Some(TagType::Flac),
+        "audio/m4a" => Some(TagType::Mp4),
+        _ => None,
     }
 }
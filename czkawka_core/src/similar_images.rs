@@ -1,23 +1,37 @@
 use crate::common::Common;
-use crate::common_directory::Directories;
+use crate::common_content_type::{ContentFilter, ContentType};
+use crate::common_date_filter::DateFilter;
+use crate::common_dir_traversal::DirTraversal;
+use crate::common_directory::{canonicalize_path, Directories};
+use crate::common_extensions::{has_extension, Extensions};
 use crate::common_items::ExcludedItems;
-use crate::common_messages::Messages;
+use crate::common_messages::{Issue, IssueKind, Messages};
+use crate::common_progress::{BytesProgress, BytesProgressMeter};
+use crate::common_resources::ResourceLimits;
+use crate::common_scan_controller::ScanController;
+use crate::common_tool::{ResultEntry, ResultGroup, Tool};
 use crate::common_traits::{DebugPrint, PrintResults, SaveResults};
-use bk_tree::BKTree;
-use crossbeam_channel::Receiver;
+use crate::similar_images_cache::{CachedImage, ImageHashCache};
+use crate::similar_images_orientation::{apply_exif_orientation, get_all_orientations, read_exif_orientation, NUMBER_OF_ORIENTATIONS};
+use bk_tree::{BKTree, Metric};
 use humansize::{file_size_opts as options, FileSize};
-use image::GenericImageView;
-use img_hash::HasherConfig;
+use image::error::UnsupportedErrorKind;
+use image::{GenericImageView, ImageError, ImageFormat};
+use img_hash::{HashAlg, Hasher, HasherConfig};
 use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
 use std::collections::HashMap;
-use std::fs::{File, Metadata};
+use std::fs;
+use std::fs::File;
 use std::io::Write;
-use std::path::PathBuf;
-use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::mem;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
+use std::thread;
 use std::thread::sleep;
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
-use std::{fs, thread};
+use std::time::{Duration, SystemTime};
 
 #[derive(Debug)]
 pub struct ProgressData {
@@ -25,9 +39,12 @@ pub struct ProgressData {
     pub max_stage: u8,
     pub images_checked: usize,
     pub images_to_check: usize,
+    /// Size of files which were checked, known only when reading content of files
+    pub bytes_progress: BytesProgress,
 }
 
-#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Similarity {
     None,
     VerySmall,
@@ -37,40 +54,237 @@ pub enum Similarity {
     VeryHigh,
 }
 
+/// Algorithm used to compute perceptual hash of image
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HashAlgorithm {
+    Mean,
+    Gradient,
+    DoubleGradient,
+    Blockhash,
+}
+
+/// Method used to remove lower quality copies of images, e.g. thumbnails or recompressed files
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeleteMethod {
+    None,
+    AllExceptBestQuality,
+}
+
+impl HashAlgorithm {
+    fn to_hash_alg(self) -> HashAlg {
+        match self {
+            HashAlgorithm::Mean => HashAlg::Mean,
+            HashAlgorithm::Gradient => HashAlg::Gradient,
+            HashAlgorithm::DoubleGradient => HashAlg::DoubleGradient,
+            HashAlgorithm::Blockhash => HashAlg::Blockhash,
+        }
+    }
+}
+
+/// Allowed lengths of side of hash, hash has size * size bits
+pub const HASH_SIZES: [u8; 3] = [8, 16, 32];
+
+/// Number of bits of the smallest hash, for which similarity levels are defined
+const BASE_HASH_BITS: u64 = 64;
+
+/// Maximum hamming distance between hashes of images with given similarity, scaled to number of bits in hash
+pub fn get_max_distance(similarity: &Similarity, hash_size: u8) -> u64 {
+    let level = match similarity {
+        Similarity::VeryHigh => 0,
+        Similarity::High => 1,
+        Similarity::Medium => 2,
+        Similarity::Small => 3,
+        Similarity::VerySmall => 4,
+        Similarity::None => panic!("Similarity level must be set"),
+    };
+    level * get_hash_bits(hash_size) / BASE_HASH_BITS
+}
+
+/// The closest similarity level which includes given hamming distance between hashes
+pub fn get_similarity_from_distance(distance: u64, hash_size: u8) -> Similarity {
+    let scale = get_hash_bits(hash_size) / BASE_HASH_BITS;
+    match (distance + scale - 1) / scale {
+        0 => Similarity::VeryHigh,
+        1 => Similarity::High,
+        2 => Similarity::Medium,
+        3 => Similarity::Small,
+        _ => Similarity::VerySmall,
+    }
+}
+
+fn get_hash_bits(hash_size: u8) -> u64 {
+    hash_size as u64 * hash_size as u64
+}
+
+/// Decodes image and computes its hash as it is displayed, when rotations are checked also hashes of all other orientations are computed
+fn hash_image(hasher: &Hasher, file_entry: &mut FileEntry, check_rotations: bool) -> Result<Vec<Node>, Issue> {
+    // Format is guessed from content, so also images with wrong extension can be opened
+    let image = match image::io::Reader::open(&file_entry.path).and_then(|reader| reader.with_guessed_format()).map(|reader| reader.decode()) {
+        Ok(Ok(t)) => t,
+        Err(e) | Ok(Err(ImageError::IoError(e))) => return Err(Issue::new(IssueKind::ReadFailed, "Failed to read image").with_path(&file_entry.path).with_io_error(e)),
+        Ok(Err(e)) => return Err(Issue::new(IssueKind::ReadFailed, format!("Failed to decode image ({})", e)).with_path(&file_entry.path)),
+    };
+    // Image is hashed as it is displayed, so copies rotated by EXIF tag and by pixels are similar
+    let image = apply_exif_orientation(image, read_exif_orientation(&file_entry.path));
+    let dimensions = image.dimensions();
+
+    file_entry.dimensions = format!("{}x{}", dimensions.0, dimensions.1);
+    if check_rotations {
+        Ok(get_all_orientations(&image).iter().map(|e| hasher.hash_image(e).as_bytes().to_vec()).collect())
+    } else {
+        Ok(vec![hasher.hash_image(&image).as_bytes().to_vec()])
+    }
+}
+
+/// Lossless formats are preferred over other formats and JPEG files, which are usually recompressed copies
+const LOSSLESS_IMAGE_EXTENSIONS: [&str; 9] = ["png", "tif", "tiff", "bmp", "ff", "pbm", "pgm", "ppm", "pam"];
+const LOSSY_IMAGE_EXTENSIONS: [&str; 2] = ["jpg", "jpeg"];
+
+/// Quality of image used to choose which of similar images is kept - the highest resolution, then the biggest file and then the preferred format
+pub fn get_image_quality(dimensions: &str, size: u64, path: &Path) -> (u64, u64, u8) {
+    let number_of_pixels = match dimensions.find('x') {
+        Some(index) => dimensions[..index].parse::<u64>().unwrap_or(0) * dimensions[index + 1..].parse::<u64>().unwrap_or(0),
+        None => 0,
+    };
+    let extension = path.extension().map(|e| e.to_string_lossy().to_lowercase()).unwrap_or_default();
+    let format_rank = if LOSSLESS_IMAGE_EXTENSIONS.contains(&extension.as_str()) {
+        2
+    } else if LOSSY_IMAGE_EXTENSIONS.contains(&extension.as_str()) {
+        0
+    } else {
+        1
+    };
+    (number_of_pixels, size, format_rank)
+}
+
+/// Index of image with the best quality in group, when qualities are equal the first image is chosen
+pub fn get_best_quality_index(vector: &[FileEntry]) -> usize {
+    vector
+        .iter()
+        .enumerate()
+        .max_by_key(|(index, file_entry)| (get_image_quality(&file_entry.dimensions, file_entry.size, &file_entry.path), Reverse(*index)))
+        .map(|(index, _)| index)
+        .unwrap_or(0)
+}
+
 #[derive(Clone)]
 pub struct FileEntry {
     pub path: PathBuf,
     pub size: u64,
     pub dimensions: String,
     pub modified_date: u64,
-    pub similarity: Similarity,
+    /// Hamming distance between hash of this image and hash of reference image of group
+    pub distance: u64,
 }
 
-/// Type to store for each entry in the similarity BK-tree.
-type Node = [u8; 8];
+impl From<&FileEntry> for ResultEntry {
+    fn from(file_entry: &FileEntry) -> Self {
+        Self {
+            path: file_entry.path.clone(),
+            size: file_entry.size,
+            modified_date: file_entry.modified_date,
+        }
+    }
+}
+
+/// Type to store for each entry in the similarity BK-tree, its length depends on hash size.
+type Node = Vec<u8>;
 
 /// Distance metric to use with the BK-tree.
 struct Hamming;
 
-impl bk_tree::Metric<Node> for Hamming {
+impl Metric<Node> for Hamming {
     fn distance(&self, a: &Node, b: &Node) -> u64 {
         hamming::distance_fast(a, b).unwrap()
     }
 }
 
+/// Groups hashes(which must be unique) into connected components, where each hash is within max distance of at least one other hash of group.
+/// Distance between images is the smallest distance from any of hashes of other orientations of first image(given in same order as hashes, may be empty) to hash of second image.
+/// Reference of each group is the hash with the smallest maximal distance to other hashes of group, ties are resolved by position in slice.
+/// Returns for each group indexes of hashes with their distance to reference, or None if search was stopped
+fn cluster_hashes(hashes: &[Node], other_orientations: &[Vec<Node>], bktree: &BKTree<Node, Hamming>, max_distance: u64, scan_controller: Option<&ScanController>) -> Option<Vec<Vec<(usize, u64)>>> {
+    let indexes: HashMap<&Node, usize> = hashes.iter().enumerate().map(|(index, hash)| (hash, index)).collect();
+    let all_orientations = |index: usize| std::iter::once(&hashes[index]).chain(other_orientations[index].iter());
+
+    // Union-find, roots of components are found with path halving
+    fn find_root(parents: &mut [usize], mut index: usize) -> usize {
+        while parents[index] != index {
+            parents[index] = parents[parents[index]];
+            index = parents[index];
+        }
+        index
+    }
+    let mut parents: Vec<usize> = (0..hashes.len()).collect();
+    for index in 0..hashes.len() {
+        if scan_controller.is_some() && scan_controller.unwrap().should_stop() {
+            return None;
+        }
+        for (_distance, similar_hash) in all_orientations(index).flat_map(|hash| bktree.find(hash, max_distance)) {
+            if let Some(&similar_index) = indexes.get(similar_hash) {
+                let root = find_root(&mut parents, index);
+                let similar_root = find_root(&mut parents, similar_index);
+                // Smaller index is always root, so it is also the first hash of component
+                parents[root.max(similar_root)] = root.min(similar_root);
+            }
+        }
+    }
+
+    let mut components: Vec<Vec<usize>> = Vec::new();
+    let mut component_of_root: HashMap<usize, usize> = HashMap::new();
+    for index in 0..hashes.len() {
+        let root = find_root(&mut parents, index);
+        let component = *component_of_root.entry(root).or_insert_with(|| {
+            components.push(Vec::new());
+            components.len() - 1
+        });
+        components[component].push(index);
+    }
+
+    let distance = |a: usize, b: usize| all_orientations(a).map(|hash| Hamming.distance(hash, &hashes[b])).min().unwrap();
+    Some(
+        components
+            .into_iter()
+            .map(|component| {
+                let reference = *component.iter().min_by_key(|&&candidate| (component.iter().map(|&other| distance(candidate, other)).max().unwrap_or(0), candidate)).unwrap();
+                component.iter().map(|&index| (index, distance(reference, index))).collect()
+            })
+            .collect(),
+    )
+}
+
 /// Struct to store most basics info about all folder
 pub struct SimilarImages {
     information: Info,
     text_messages: Messages,
     directories: Directories,
     excluded_items: ExcludedItems,
+    allowed_extensions: Extensions,
     bktree: BKTree<Node, Hamming>,
     similar_vectors: Vec<Vec<FileEntry>>,
     recursive_search: bool,
+    max_depth: Option<usize>,
+    use_ignore_files: bool,
+    date_filter: DateFilter,
+    content_filter: ContentFilter,
+    resource_limits: ResourceLimits,
     minimal_file_size: u64,
-    image_hashes: HashMap<Node, Vec<FileEntry>>, // Hashmap with image hashes and Vector with names of files
+    image_hashes: HashMap<Node, Vec<FileEntry>>,  // Hashmap with image hashes and Vector with names of files
+    other_orientations: HashMap<Node, Vec<Node>>, // Hashes of rotated and mirrored images, used only when checking rotations
     stopped_search: bool,
     similarity: Similarity,
+    max_distance: Option<u64>,
+    delete_method: DeleteMethod,
+    hash_algorithm: HashAlgorithm,
+    hash_size: u8,
+    use_dct: bool,
+    use_cache: bool,
+    check_rotations: bool,
+    additional_image_extensions: Vec<String>,
+    excluded_image_extensions: Vec<String>,
+    reference_images: Vec<PathBuf>,
     images_to_check: Vec<FileEntry>,
 }
 
@@ -102,27 +316,44 @@ impl SimilarImages {
             text_messages: Messages::new(),
             directories: Directories::new(),
             excluded_items: Default::default(),
+            allowed_extensions: Extensions::new(),
             bktree: BKTree::new(Hamming),
             similar_vectors: vec![],
             recursive_search: true,
+            max_depth: None,
+            use_ignore_files: false,
+            date_filter: DateFilter::new(),
+            content_filter: ContentFilter::new(),
+            resource_limits: ResourceLimits::new(),
             minimal_file_size: 1024 * 16, // 16 KB should be enough to exclude too small images from search
             image_hashes: Default::default(),
+            other_orientations: Default::default(),
             stopped_search: false,
             similarity: Similarity::High,
+            max_distance: None,
+            delete_method: DeleteMethod::None,
+            hash_algorithm: HashAlgorithm::Gradient,
+            hash_size: 8,
+            use_dct: false,
+            use_cache: true,
+            check_rotations: false,
+            additional_image_extensions: Vec::new(),
+            excluded_image_extensions: Vec::new(),
+            reference_images: Vec::new(),
             images_to_check: vec![],
         }
     }
 
-    pub fn get_stopped_search(&self) -> bool {
-        self.stopped_search
+    pub const fn get_similar_images(&self) -> &Vec<Vec<FileEntry>> {
+        &self.similar_vectors
     }
 
-    pub const fn get_text_messages(&self) -> &Messages {
-        &self.text_messages
+    pub fn get_reference_images(&self) -> &[PathBuf] {
+        &self.reference_images
     }
 
-    pub const fn get_similar_images(&self) -> &Vec<Vec<FileEntry>> {
-        &self.similar_vectors
+    pub const fn get_hash_size(&self) -> u8 {
+        self.hash_size
     }
 
     pub const fn get_information(&self) -> &Info {
@@ -133,6 +364,31 @@ impl SimilarImages {
         self.recursive_search = recursive_search;
     }
 
+    /// Only files with these content types(image, audio, video, archive, document) will be checked
+    pub fn set_content_types(&mut self, content_types: String) {
+        self.content_filter.set_content_types(content_types, &mut self.text_messages);
+    }
+
+    /// Recognizes type of files by their content instead of extension
+    pub fn set_detect_by_content(&mut self, detect_by_content: bool) {
+        self.content_filter.set_detect_by_content(detect_by_content);
+    }
+
+    /// Limits speed of reading files in bytes per second, None or 0 disables limit
+    pub fn set_io_limit(&mut self, io_limit: Option<u64>) {
+        self.resource_limits.set_io_limit(io_limit);
+    }
+
+    /// Scan runs with lowered CPU and I/O priority, so it slows down other programs less
+    pub fn set_low_priority(&mut self, low_priority: bool) {
+        self.resource_limits.set_low_priority(low_priority);
+    }
+
+    /// Number of threads used by parallel stages, None or 0 means number of logical CPUs
+    pub fn set_threads(&mut self, threads: Option<usize>) {
+        self.resource_limits.set_threads(threads);
+    }
+
     pub fn set_minimal_file_size(&mut self, minimal_file_size: u64) {
         self.minimal_file_size = match minimal_file_size {
             0 => 1,
@@ -143,38 +399,122 @@ impl SimilarImages {
         self.similarity = similarity;
     }
 
+    /// Maximum hamming distance between hashes of similar images, when set it is used instead of similarity level
+    pub fn set_max_distance(&mut self, max_distance: Option<u64>) {
+        self.max_distance = max_distance;
+    }
+
+    fn get_used_max_distance(&self) -> u64 {
+        self.max_distance.unwrap_or_else(|| get_max_distance(&self.similarity, self.hash_size))
+    }
+
+    pub fn set_delete_method(&mut self, delete_method: DeleteMethod) {
+        self.delete_method = delete_method;
+    }
+
+    pub fn set_hash_algorithm(&mut self, hash_algorithm: HashAlgorithm) {
+        self.hash_algorithm = hash_algorithm;
+    }
+
+    /// Bigger hash is more accurate but slower, similarity levels are scaled to its number of bits
+    pub fn set_hash_size(&mut self, hash_size: u8) -> bool {
+        if !HASH_SIZES.contains(&hash_size) {
+            self.text_messages
+                .errors
+                .push(Issue::new(IssueKind::InvalidConfiguration, format!("Hash size {} is not supported, allowed sizes: 8, 16, 32", hash_size)));
+            return false;
+        }
+        self.hash_size = hash_size;
+        true
+    }
+
+    /// Discrete Cosine Transform before hashing makes hash more resistant to edits of image, but is slower
+    pub fn set_use_dct(&mut self, use_dct: bool) {
+        self.use_dct = use_dct;
+    }
+
+    /// Hashes of images are saved to cache, so next scans decode only new or changed images
+    pub fn set_use_cache(&mut self, use_cache: bool) {
+        self.use_cache = use_cache;
+    }
+
+    /// Images are compared also in all orientations created by rotating by 90 degrees and mirroring, which is much slower
+    pub fn set_check_rotations(&mut self, check_rotations: bool) {
+        self.check_rotations = check_rotations;
+    }
+
+    /// Comma separated extensions of images checked in addition to supported formats, e.g. jfif, which are decoded by guessing format from content
+    pub fn set_additional_image_extensions(&mut self, additional_image_extensions: String) {
+        self.additional_image_extensions = parse_image_extensions(&additional_image_extensions);
+    }
+
+    /// Comma separated extensions of supported image formats which are not checked
+    pub fn set_excluded_image_extensions(&mut self, excluded_image_extensions: String) {
+        self.excluded_image_extensions = parse_image_extensions(&excluded_image_extensions);
+    }
+
+    /// When set, only scanned images similar to these images are found instead of all groups of similar images
+    pub fn set_reference_images(&mut self, reference_images: &[PathBuf]) -> bool {
+        self.reference_images.clear();
+        for reference_image in reference_images {
+            if !reference_image.is_file() {
+                self.text_messages
+                    .errors
+                    .push(Issue::new(IssueKind::InvalidConfiguration, format!("Reference image {} must exist and be a file", reference_image.display())));
+                return false;
+            }
+            // Paths of scanned images are canonicalized, so reference image can be recognized between them
+            match canonicalize_path(reference_image) {
+                Ok(t) => self.reference_images.push(t),
+                Err(e) => {
+                    self.text_messages
+                        .errors
+                        .push(Issue::new(IssueKind::InvalidConfiguration, format!("Failed to resolve path of reference image {} - {}", reference_image.display(), e)));
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    fn get_number_of_orientations(&self) -> usize {
+        if self.check_rotations {
+            NUMBER_OF_ORIENTATIONS
+        } else {
+            1
+        }
+    }
+
+    /// Name of settings which change computed hashes, each of them uses separate cache
+    fn get_hash_configuration(&self) -> String {
+        let hash_algorithm = match self.hash_algorithm {
+            HashAlgorithm::Mean => "mean",
+            HashAlgorithm::Gradient => "gradient",
+            HashAlgorithm::DoubleGradient => "double_gradient",
+            HashAlgorithm::Blockhash => "blockhash",
+        };
+        format!("{}_{}{}{}", hash_algorithm, self.hash_size, if self.use_dct { "_dct" } else { "" }, if self.check_rotations { "_rotations" } else { "" })
+    }
+
     /// Public function used by CLI to search for empty folders
-    pub fn find_similar_images(&mut self, stop_receiver: Option<&Receiver<()>>, progress_sender: Option<&futures::channel::mpsc::Sender<ProgressData>>) {
+    pub fn find_similar_images(&mut self, scan_controller: Option<&ScanController>, progress_sender: Option<&futures::channel::mpsc::Sender<ProgressData>>) {
         self.directories.optimize_directories(true, &mut self.text_messages);
-        if !self.check_for_similar_images(stop_receiver, progress_sender) {
+        if !self.check_for_similar_images(scan_controller, progress_sender) {
             self.stopped_search = true;
             return;
         }
-        if !self.sort_images(stop_receiver, progress_sender) {
+        if !self.sort_images(scan_controller, progress_sender) {
             self.stopped_search = true;
             return;
         }
-        // if self.delete_folders {
-        //     self.delete_empty_folders();
-        // }
+        self.delete_files();
         self.debug_print();
     }
 
-    // pub fn set_delete_folder(&mut self, delete_folder: bool) {
-    //     self.delete_folders = delete_folder;
-    // }
-
     /// Function to check if folder are empty.
     /// Parameter initial_checking for second check before deleting to be sure that checked folder is still empty
-    fn check_for_similar_images(&mut self, stop_receiver: Option<&Receiver<()>>, progress_sender: Option<&futures::channel::mpsc::Sender<ProgressData>>) -> bool {
+    fn check_for_similar_images(&mut self, scan_controller: Option<&ScanController>, progress_sender: Option<&futures::channel::mpsc::Sender<ProgressData>>) -> bool {
         let start_time: SystemTime = SystemTime::now();
-        let mut folders_to_check: Vec<PathBuf> = Vec::with_capacity(1024 * 2); // This should be small enough too not see to big difference and big enough to store most of paths without needing to resize vector
-
-        // Add root folders for finding
-        for id in &self.directories.included_directories {
-            folders_to_check.push(id.clone());
-        }
-        self.information.number_of_checked_folders += folders_to_check.len();
 
         //// PROGRESS THREAD START
         const LOOP_DURATION: u32 = 200; //in ms
@@ -194,6 +534,7 @@ impl SimilarImages {
                         max_stage: 1,
                         images_checked: atomic_file_counter.load(Ordering::Relaxed) as usize,
                         images_to_check: 0,
+                        bytes_progress: BytesProgress::default(),
                     })
                     .unwrap();
                 if !progress_thread_run.load(Ordering::Relaxed) {
@@ -206,123 +547,81 @@ impl SimilarImages {
         }
         //// PROGRESS THREAD END
 
-        while !folders_to_check.is_empty() {
-            if stop_receiver.is_some() && stop_receiver.unwrap().try_recv().is_ok() {
-                // End thread which send info to gui
-                progress_thread_run.store(false, Ordering::Relaxed);
-                progress_thread_handle.join().unwrap();
-                return false;
-            }
-            let current_folder = folders_to_check.pop().unwrap();
-
-            // Read current dir, if permission are denied just go to next
-            let read_dir = match fs::read_dir(&current_folder) {
-                Ok(t) => t,
-                Err(_) => {
-                    self.text_messages.warnings.push(format!("Cannot open dir {}", current_folder.display()));
-                    continue;
-                } // Permissions denied
-            };
-
-            // Check every sub folder/file/link etc.
-            'dir: for entry in read_dir {
-                let entry_data = match entry {
-                    Ok(t) => t,
-                    Err(_) => {
-                        self.text_messages.warnings.push(format!("Cannot read entry in dir {}", current_folder.display()));
-                        continue;
-                    } //Permissions denied
-                };
-                let metadata: Metadata = match entry_data.metadata() {
-                    Ok(t) => t,
-                    Err(_) => {
-                        self.text_messages.warnings.push(format!("Cannot read metadata in dir {}", current_folder.display()));
-                        continue;
-                    } //Permissions denied
-                };
-                if metadata.is_dir() {
-                    self.information.number_of_checked_folders += 1;
-
-                    if !self.recursive_search {
-                        continue;
-                    }
-
-                    let next_folder = current_folder.join(entry_data.file_name());
-                    if self.directories.is_excluded(&next_folder) {
-                        continue 'dir;
-                    }
-
-                    if self.excluded_items.is_excluded(&next_folder) {
-                        continue 'dir;
-                    }
-
-                    folders_to_check.push(next_folder);
-                } else if metadata.is_file() {
-                    atomic_file_counter.fetch_add(1, Ordering::Relaxed);
+        // When detecting by content, files with any extension are checked if they really are image files
+        let mut content_filter = self.content_filter.clone();
+        let mut dir_traversal = DirTraversal::new(&self.directories, &self.excluded_items);
+        if content_filter.detect_by_content {
+            content_filter.required_content_type = Some(ContentType::Image);
+        } else {
+            let image_extensions: Vec<String> = get_supported_image_extensions()
+                .into_iter()
+                .map(str::to_string)
+                .chain(self.additional_image_extensions.iter().cloned())
+                .filter(|e| !self.excluded_image_extensions.contains(e))
+                .collect();
+            dir_traversal.set_file_filter(move |file_name_lowercase, _| image_extensions.iter().any(|e| has_extension(file_name_lowercase, e)));
+        }
+        dir_traversal.set_recursive_search(self.recursive_search);
+        dir_traversal.set_max_depth(self.max_depth);
+        dir_traversal.set_use_ignore_files(self.use_ignore_files);
+        dir_traversal.set_date_filter(self.date_filter);
+        dir_traversal.set_content_filter(&content_filter);
+        dir_traversal.set_extensions(&self.allowed_extensions);
+        dir_traversal.set_minimal_file_size(self.minimal_file_size);
+        let traversal_result = dir_traversal.run(scan_controller, &atomic_file_counter, &mut self.text_messages);
 
-                    let file_name_lowercase: String = match entry_data.file_name().into_string() {
-                        Ok(t) => t,
-                        Err(_) => continue,
-                    }
-                    .to_lowercase();
+        // End thread which send info to gui
+        progress_thread_run.store(false, Ordering::Relaxed);
+        progress_thread_handle.join().unwrap();
 
-                    // Checking allowed image extensions
-                    let allowed_image_extensions = ["jpg", "png", "bmp", "ico", "webp", "tiff", "dds"];
-                    if !allowed_image_extensions.iter().any(|e| file_name_lowercase.ends_with(e)) {
-                        self.information.number_of_ignored_files += 1;
-                        continue 'dir;
-                    }
+        let (files, traversal_information) = match traversal_result {
+            Some(t) => t,
+            None => return false,
+        };
+        self.information.number_of_checked_files += traversal_information.number_of_checked_files;
+        self.information.number_of_checked_folders += traversal_information.number_of_checked_folders;
+        self.information.number_of_ignored_files += traversal_information.number_of_ignored_files;
+        self.information.number_of_ignored_things += traversal_information.number_of_ignored_things;
+
+        for fe in files {
+            self.information.size_of_checked_images += fe.size;
+            self.images_to_check.push(FileEntry {
+                path: fe.path,
+                size: fe.size,
+                dimensions: "".to_string(),
+                modified_date: fe.modified_date,
+                distance: 0,
+            });
+        }
+        Common::log_phase(self.get_id(), "traversal", start_time, self.information.number_of_checked_files);
+        true
+    }
 
-                    // Checking files
-                    if metadata.len() >= self.minimal_file_size {
-                        let current_file_name = current_folder.join(entry_data.file_name());
-                        if self.excluded_items.is_excluded(&current_file_name) {
-                            continue 'dir;
-                        }
+    fn sort_images(&mut self, scan_controller: Option<&ScanController>, progress_sender: Option<&futures::channel::mpsc::Sender<ProgressData>>) -> bool {
+        let hash_map_modification = SystemTime::now();
 
-                        let fe: FileEntry = FileEntry {
-                            path: current_file_name.clone(),
-                            size: metadata.len(),
-                            dimensions: "".to_string(),
-                            modified_date: match metadata.modified() {
-                                Ok(t) => match t.duration_since(UNIX_EPOCH) {
-                                    Ok(d) => d.as_secs(),
-                                    Err(_) => {
-                                        self.text_messages.warnings.push(format!("File {} seems to be modified before Unix Epoch.", current_file_name.display()));
-                                        0
-                                    }
-                                },
-                                Err(_) => {
-                                    self.text_messages.warnings.push(format!("Unable to get modification date from file {}", current_file_name.display()));
-                                    continue;
-                                } // Permissions Denied
-                            },
-
-                            similarity: Similarity::None,
-                        };
-
-                        self.images_to_check.push(fe);
-
-                        self.information.size_of_checked_images += metadata.len();
-                        self.information.number_of_checked_files += 1;
-                    } else {
-                        // Probably this is symbolic links so we are free to ignore this
-                        self.information.number_of_ignored_files += 1;
-                    }
-                } else {
-                    // Probably this is symbolic links so we are free to ignore this
-                    self.information.number_of_ignored_things += 1;
+        // Images which weren't changed since previous scan don't need to be decoded again
+        let mut cache = if self.use_cache { Some(ImageHashCache::load(&self.get_hash_configuration())) } else { None };
+        let number_of_orientations = self.get_number_of_orientations();
+        let mut cached_images: Vec<(FileEntry, Vec<Node>)> = Vec::new();
+        let mut images_to_hash: Vec<FileEntry> = Vec::new();
+        for mut file_entry in mem::take(&mut self.images_to_check) {
+            match cache
+                .as_ref()
+                .and_then(|e| e.get(&file_entry.path, file_entry.size, file_entry.modified_date))
+                .filter(|cached_image| cached_image.hash.len() >= number_of_orientations)
+            {
+                Some(cached_image) => {
+                    file_entry.dimensions = cached_image.dimensions.clone();
+                    // Hashes of all orientations are saved one after another
+                    let hash_length = cached_image.hash.len() / number_of_orientations;
+                    cached_images.push((file_entry, cached_image.hash.chunks(hash_length).map(|e| e.to_vec()).collect()));
                 }
+                None => images_to_hash.push(file_entry),
             }
         }
-        // End thread which send info to gui
-        progress_thread_run.store(false, Ordering::Relaxed);
-        progress_thread_handle.join().unwrap();
-        Common::print_time(start_time, SystemTime::now(), "check_for_similar_images".to_string());
-        true
-    }
 
-    fn sort_images(&mut self, stop_receiver: Option<&Receiver<()>>, progress_sender: Option<&futures::channel::mpsc::Sender<ProgressData>>) -> bool {
+        Common::log_phase(self.get_id(), "cache_loading", hash_map_modification, cached_images.len());
         let hash_map_modification = SystemTime::now();
 
         //// PROGRESS THREAD START
@@ -330,182 +629,325 @@ impl SimilarImages {
         let progress_thread_run = Arc::new(AtomicBool::new(true));
 
         let atomic_file_counter = Arc::new(AtomicUsize::new(0));
+        let atomic_bytes_counter = Arc::new(AtomicU64::new(0));
 
         let progress_thread_handle;
         if let Some(progress_sender) = progress_sender {
             let mut progress_send = progress_sender.clone();
             let progress_thread_run = progress_thread_run.clone();
             let atomic_file_counter = atomic_file_counter.clone();
-            let images_to_check = self.images_to_check.len();
-            progress_thread_handle = thread::spawn(move || loop {
-                progress_send
-                    .try_send(ProgressData {
-                        current_stage: 1,
-                        max_stage: 1,
-                        images_checked: atomic_file_counter.load(Ordering::Relaxed) as usize,
-                        images_to_check,
-                    })
-                    .unwrap();
-                if !progress_thread_run.load(Ordering::Relaxed) {
-                    break;
+            let atomic_bytes_counter = atomic_bytes_counter.clone();
+            let images_to_check = images_to_hash.len();
+            let bytes_to_check = images_to_hash.iter().map(|e| e.size).sum();
+            progress_thread_handle = thread::spawn(move || {
+                let mut bytes_progress_meter = BytesProgressMeter::new();
+                loop {
+                    progress_send
+                        .try_send(ProgressData {
+                            current_stage: 1,
+                            max_stage: 1,
+                            images_checked: atomic_file_counter.load(Ordering::Relaxed) as usize,
+                            images_to_check,
+                            bytes_progress: bytes_progress_meter.measure(atomic_bytes_counter.load(Ordering::Relaxed), bytes_to_check),
+                        })
+                        .unwrap();
+                    if !progress_thread_run.load(Ordering::Relaxed) {
+                        break;
+                    }
+                    sleep(Duration::from_millis(LOOP_DURATION as u64));
                 }
-                sleep(Duration::from_millis(LOOP_DURATION as u64));
             });
         } else {
             progress_thread_handle = thread::spawn(|| {});
         }
         //// PROGRESS THREAD END
 
-        let vec_file_entry: Vec<(FileEntry, Node)> = self
-            .images_to_check
-            .par_iter()
-            .map(|file_entry| {
-                atomic_file_counter.fetch_add(1, Ordering::Relaxed);
-                if stop_receiver.is_some() && stop_receiver.unwrap().try_recv().is_ok() {
-                    // This will not break
-                    return None;
-                }
-                let mut file_entry = file_entry.clone();
-
-                let image = match image::open(file_entry.path.clone()) {
-                    Ok(t) => t,
-                    Err(_) => return Some(None), // Something is wrong with image
-                };
-                let dimensions = image.dimensions();
-
-                file_entry.dimensions = format!("{}x{}", dimensions.0, dimensions.1);
-                let hasher = HasherConfig::with_bytes_type::<[u8; 8]>().to_hasher();
-
-                let hash = hasher.hash_image(&image);
-                let mut buf = [0u8; 8];
-                buf.copy_from_slice(&hash.as_bytes());
+        let mut hasher_config = HasherConfig::new().hash_alg(self.hash_algorithm.to_hash_alg()).hash_size(self.hash_size as u32, self.hash_size as u32);
+        if self.use_dct {
+            hasher_config = hasher_config.preproc_dct();
+        }
 
-                Some(Some((file_entry, buf)))
-            })
-            .while_some()
-            .filter(|file_entry| file_entry.is_some())
-            .map(|file_entry| file_entry.unwrap())
-            .collect::<Vec<(FileEntry, Node)>>();
+        let check_rotations = self.check_rotations;
+        let hashing_results: Vec<Result<(FileEntry, Vec<Node>), Issue>> = self.resource_limits.install(|io_throttle| {
+            images_to_hash
+                .par_iter()
+                // Hasher with DCT cannot be shared between threads, so each thread creates its own
+                .map_init(
+                    || hasher_config.to_hasher(),
+                    |hasher, file_entry| {
+                        atomic_file_counter.fetch_add(1, Ordering::Relaxed);
+                        atomic_bytes_counter.fetch_add(file_entry.size, Ordering::Relaxed);
+                        if scan_controller.is_some() && scan_controller.unwrap().should_stop() {
+                            // This will not break
+                            return None;
+                        }
+                        let mut file_entry = file_entry.clone();
+                        // Decoder reads whole image, so its size is counted before opening it
+                        io_throttle.consume(file_entry.size);
+
+                        Some(hash_image(hasher, &mut file_entry, check_rotations).map(|hashes| (file_entry, hashes)))
+                    },
+                )
+                .while_some()
+                .collect()
+        });
+
+        let mut vec_file_entry: Vec<(FileEntry, Vec<Node>)> = Vec::with_capacity(hashing_results.len());
+        for result in hashing_results {
+            match result {
+                Ok(t) => vec_file_entry.push(t),
+                Err(issue) => self.text_messages.warnings.push(issue),
+            }
+        }
 
         // End thread which send info to gui
         progress_thread_run.store(false, Ordering::Relaxed);
         progress_thread_handle.join().unwrap();
 
-        Common::print_time(hash_map_modification, SystemTime::now(), "sort_images - reading data from files in parralell".to_string());
+        Common::log_phase(self.get_id(), "hashing", hash_map_modification, vec_file_entry.len());
         let hash_map_modification = SystemTime::now();
 
-        for (file_entry, buf) in vec_file_entry {
-            self.bktree.add(buf);
-            self.image_hashes.entry(buf).or_insert_with(Vec::<FileEntry>::new);
+        if let Some(cache) = &mut cache {
+            if !vec_file_entry.is_empty() {
+                for (file_entry, hashes) in &vec_file_entry {
+                    cache.insert(
+                        file_entry.path.clone(),
+                        CachedImage {
+                            size: file_entry.size,
+                            modified_date: file_entry.modified_date,
+                            dimensions: file_entry.dimensions.clone(),
+                            hash: hashes.concat(),
+                        },
+                    );
+                }
+                if let Err(e) = cache.save() {
+                    self.text_messages.warnings.push(Issue::new(IssueKind::SaveFailed, e));
+                }
+            }
+        }
+        vec_file_entry.append(&mut cached_images);
+
+        for (file_entry, mut hashes) in vec_file_entry {
+            // Only hash of image as it is displayed is added to tree, other orientations are used to search in it
+            let buf = hashes.remove(0);
+            self.other_orientations.entry(buf.clone()).or_insert(hashes);
+            self.image_hashes.entry(buf.clone()).or_insert_with(Vec::<FileEntry>::new);
             self.image_hashes.get_mut(&buf).unwrap().push(file_entry.clone());
+            self.bktree.add(buf);
         }
 
-        Common::print_time(hash_map_modification, SystemTime::now(), "sort_images - saving data to files".to_string());
+        Common::log_phase(self.get_id(), "tree_building", hash_map_modification, self.image_hashes.len());
         let hash_map_modification = SystemTime::now();
 
-        let similarity: u64 = match self.similarity {
-            Similarity::VeryHigh => 0,
-            Similarity::High => 1,
-            Similarity::Medium => 2,
-            Similarity::Small => 3,
-            Similarity::VerySmall => 4,
-            _ => panic!("0-4 similarity levels are allowed, check if not added more."),
-        };
+        let max_distance: u64 = self.get_used_max_distance();
+
+        if !self.reference_images.is_empty() {
+            self.similar_vectors = match self.group_by_reference_images(&hasher_config.to_hasher(), max_distance, scan_controller) {
+                Some(t) => t,
+                None => return false,
+            };
+            Common::log_phase(self.get_id(), "grouping", hash_map_modification, self.similar_vectors.len());
+            return true;
+        }
 
-        // TODO
-        // Now is A is similar to B with VeryHigh and C with Medium
-        // And D is similar with C with High
-        // And Similarity is set to Medium(or lower)
-        // And A is checked before D
-        // Then C is shown that is similar group A, not D
+        // Hashes are sorted, so groups and their reference images don't depend on order of scanned files
+        let mut hashes: Vec<Node> = self.image_hashes.keys().cloned().collect();
+        hashes.sort_unstable();
 
-        // TODO
-        // Maybe also add here progress report
+        let other_orientations: Vec<Vec<Node>> = hashes.iter().map(|hash| self.other_orientations.get(hash).cloned().unwrap_or_default()).collect();
+
+        let groups = match cluster_hashes(&hashes, &other_orientations, &self.bktree, max_distance, scan_controller) {
+            Some(t) => t,
+            None => return false,
+        };
 
         let mut new_vector: Vec<Vec<FileEntry>> = Vec::new();
-        let mut hashes_to_check = self.image_hashes.clone();
-        for (hash, vec_file_entry) in &self.image_hashes {
-            if stop_receiver.is_some() && stop_receiver.unwrap().try_recv().is_ok() {
-                return false;
+        for group in groups {
+            let mut vector_of_similar_images: Vec<FileEntry> = Vec::new();
+            for (index, distance) in group {
+                vector_of_similar_images.extend(self.image_hashes[&hashes[index]].iter().map(|fe| FileEntry { distance, ..fe.clone() }));
             }
-            if !hashes_to_check.contains_key(hash) {
-                continue;
+            if vector_of_similar_images.len() > 1 {
+                // Reference image is always first, because other hashes have bigger distance to it
+                vector_of_similar_images.sort_by(|a, b| a.distance.cmp(&b.distance).then_with(|| a.path.cmp(&b.path)));
+                new_vector.push(vector_of_similar_images);
             }
-            hashes_to_check.remove(hash);
+        }
 
-            let vector_with_found_similar_hashes = self.bktree.find(hash, similarity).collect::<Vec<_>>();
-            if vector_with_found_similar_hashes.len() == 1 && vec_file_entry.len() == 1 {
-                // This one picture doesn't have similar pictures, so there is no go
-                continue;
-            }
+        self.similar_vectors = new_vector;
 
-            let mut vector_of_similar_images: Vec<FileEntry> = vec_file_entry
-                .iter()
-                .map(|fe| FileEntry {
-                    path: fe.path.clone(),
-                    size: fe.size,
-                    dimensions: fe.dimensions.clone(),
-                    modified_date: fe.modified_date,
-                    similarity: Similarity::VeryHigh,
-                })
-                .collect();
+        Common::log_phase(self.get_id(), "grouping", hash_map_modification, self.similar_vectors.len());
+        true
+    }
 
-            for (similarity, similar_hash) in vector_with_found_similar_hashes.iter() {
-                if *similarity == 0 && hash == *similar_hash {
-                    // This was already read before
+    /// Each scanned image is added to group of the closest reference image, which is the first image in group
+    /// Reference images are hashed with the same settings as scanned images, but aren't saved in cache
+    fn group_by_reference_images(&mut self, hasher: &Hasher, max_distance: u64, scan_controller: Option<&ScanController>) -> Option<Vec<Vec<FileEntry>>> {
+        let mut reference_entries: Vec<(FileEntry, Vec<Node>)> = Vec::new();
+        for path in &self.reference_images {
+            let metadata = match fs::metadata(path) {
+                Ok(t) => t,
+                Err(e) => {
+                    self.text_messages.warnings.push(Issue::new(IssueKind::ReadFailed, "Failed to read metadata of reference image").with_path(path).with_io_error(e));
                     continue;
-                } else if hash == *similar_hash {
-                    panic!("I'm not sure if same hash can have distance > 0");
                 }
+            };
+            let mut file_entry = FileEntry {
+                path: path.clone(),
+                size: metadata.len(),
+                dimensions: "".to_string(),
+                modified_date: metadata.modified().ok().and_then(|e| e.duration_since(SystemTime::UNIX_EPOCH).ok()).map(|e| e.as_secs()).unwrap_or(0),
+                distance: 0,
+            };
+            match hash_image(hasher, &mut file_entry, self.check_rotations) {
+                Ok(hashes) => reference_entries.push((file_entry, hashes)),
+                Err(issue) => self.text_messages.warnings.push(issue),
+            }
+        }
 
-                if let Some(vec_file_entry) = hashes_to_check.get(*similar_hash) {
-                    vector_of_similar_images.append(
-                        &mut (vec_file_entry
-                            .iter()
-                            .map(|fe| FileEntry {
-                                path: fe.path.clone(),
-                                size: fe.size,
-                                dimensions: fe.dimensions.clone(),
-                                modified_date: fe.modified_date,
-                                similarity: match similarity {
-                                    0 => Similarity::VeryHigh,
-                                    1 => Similarity::High,
-                                    2 => Similarity::Medium,
-                                    3 => Similarity::Small,
-                                    4 => Similarity::VerySmall,
-                                    _ => panic!("0-4 similarity levels are allowed, check if not added more."),
-                                },
-                            })
-                            .collect::<Vec<_>>()),
-                    );
-                    hashes_to_check.remove(*similar_hash);
+        // Index of the closest reference image and distance to it for each of found hashes
+        let mut closest_references: HashMap<&Node, (usize, u64)> = HashMap::new();
+        for (reference_index, (_, reference_hashes)) in reference_entries.iter().enumerate() {
+            if scan_controller.is_some() && scan_controller.unwrap().should_stop() {
+                return None;
+            }
+            // Other orientations of reference image are compared with scanned images, so also rotated copies are found
+            for reference_hash in reference_hashes {
+                for (distance, hash) in self.bktree.find(reference_hash, max_distance) {
+                    let closest = closest_references.entry(hash).or_insert((reference_index, distance));
+                    if distance < closest.1 {
+                        *closest = (reference_index, distance);
+                    }
                 }
             }
-            if vector_of_similar_images.len() > 1 {
-                // Not sure why it may happens
-                new_vector.push((*vector_of_similar_images).to_owned());
+        }
+
+        let mut groups: Vec<Vec<FileEntry>> = reference_entries.into_iter().map(|(file_entry, _)| vec![file_entry]).collect();
+        for (hash, (reference_index, distance)) in closest_references {
+            let reference_path = groups[reference_index][0].path.clone();
+            groups[reference_index].extend(self.image_hashes[hash].iter().filter(|fe| fe.path != reference_path).map(|fe| FileEntry { distance, ..fe.clone() }));
+        }
+        for group in &mut groups {
+            group[1..].sort_by(|a, b| a.distance.cmp(&b.distance).then_with(|| a.path.cmp(&b.path)));
+        }
+        groups.retain(|group| group.len() > 1);
+        Some(groups)
+    }
+
+    /// Removes all images of each group except the one with the best quality, reference images given by user are never removed
+    fn delete_files(&mut self) {
+        let start_time: SystemTime = SystemTime::now();
+
+        if self.delete_method == DeleteMethod::None {
+            return;
+        }
+
+        for vector in &self.similar_vectors {
+            let best_quality_index = get_best_quality_index(vector);
+            for (index, file_entry) in vector.iter().enumerate() {
+                if index == best_quality_index || self.reference_images.contains(&file_entry.path) {
+                    continue;
+                }
+                match fs::remove_file(&file_entry.path) {
+                    Ok(_) => {
+                        self.information.number_of_removed_files += 1;
+                        self.information.gained_space += file_entry.size;
+                    }
+                    Err(e) => {
+                        self.information.number_of_failed_to_remove_files += 1;
+                        self.text_messages.warnings.push(Issue::new(IssueKind::DeleteFailed, "Failed to delete").with_path(&file_entry.path).with_io_error(e));
+                    }
+                }
             }
         }
 
-        self.similar_vectors = new_vector;
+        Common::log_phase(self.get_id(), "deletion", start_time, self.information.number_of_removed_files);
+    }
 
-        Common::print_time(hash_map_modification, SystemTime::now(), "sort_images - selecting data from BtreeMap".to_string());
-        true
+    /// Files from list(read from file or "-" for standard input) are checked instead of files from included directories
+    pub fn set_file_list(&mut self, file_list: String, null_separated: bool) -> bool {
+        self.directories.set_file_list(file_list, null_separated, &mut self.text_messages)
+    }
+
+    /// List of allowed extensions, only files with this extensions will be checked
+    pub fn set_allowed_extensions(&mut self, allowed_extensions: String) {
+        self.allowed_extensions.set_allowed_extensions(allowed_extensions, &mut self.text_messages);
+    }
+
+    /// Files with these extensions are skipped even if they are allowed
+    pub fn set_excluded_extensions(&mut self, excluded_extensions: String) {
+        self.allowed_extensions.set_excluded_extensions(excluded_extensions, &mut self.text_messages);
+    }
+
+    /// Named groups of extensions which can be used in allowed and excluded extensions
+    pub fn set_extension_groups(&mut self, extension_groups: String) {
+        self.allowed_extensions.set_extension_groups(extension_groups, &mut self.text_messages);
+    }
+}
+impl Tool for SimilarImages {
+    type ProgressData = ProgressData;
+
+    fn get_name(&self) -> &'static str {
+        "similar images"
+    }
+
+    fn get_id(&self) -> &'static str {
+        "similar_images"
     }
 
     /// Set included dir which needs to be relative, exists etc.
-    pub fn set_included_directory(&mut self, included_directory: String) {
-        self.directories.set_included_directory(included_directory, &mut self.text_messages);
+    fn set_included_directory(&mut self, included_directory: String) -> bool {
+        self.directories.set_included_directory(included_directory, &mut self.text_messages)
     }
 
-    pub fn set_excluded_directory(&mut self, excluded_directory: String) {
+    fn set_excluded_directory(&mut self, excluded_directory: String) {
         self.directories.set_excluded_directory(excluded_directory, &mut self.text_messages);
     }
 
-    pub fn set_excluded_items(&mut self, excluded_items: String) {
+    fn set_excluded_items(&mut self, excluded_items: String) {
         self.excluded_items.set_excluded_items(excluded_items, &mut self.text_messages);
     }
+
+    /// Uses rules from .gitignore, .ignore and .czkawkaignore files found in scanned folders
+    fn set_use_ignore_files(&mut self, use_ignore_files: bool) {
+        self.use_ignore_files = use_ignore_files;
+    }
+
+    /// Limits number of levels of subfolders which are checked, 0 means that only included directories are checked
+    fn set_max_depth(&mut self, max_depth: Option<usize>) {
+        self.max_depth = max_depth;
+    }
+
+    fn set_modified_after(&mut self, modified_after: String) {
+        self.date_filter.set_modified_after(modified_after, &mut self.text_messages);
+    }
+
+    fn set_modified_before(&mut self, modified_before: String) {
+        self.date_filter.set_modified_before(modified_before, &mut self.text_messages);
+    }
+
+    fn get_stopped_search(&self) -> bool {
+        self.stopped_search
+    }
+
+    fn get_text_messages(&self) -> &Messages {
+        &self.text_messages
+    }
+
+    fn search(&mut self, scan_controller: Option<&ScanController>, progress_sender: Option<&futures::channel::mpsc::Sender<ProgressData>>) {
+        self.find_similar_images(scan_controller, progress_sender);
+    }
+
+    fn get_result_groups(&self) -> Vec<ResultGroup> {
+        self.similar_vectors
+            .iter()
+            .map(|vec_file_entry| ResultGroup {
+                entries: vec_file_entry.iter().map(ResultEntry::from).collect(),
+            })
+            .collect()
+    }
 }
+
 impl Default for SimilarImages {
     fn default() -> Self {
         Self::new()
@@ -524,6 +966,19 @@ impl DebugPrint for SimilarImages {
         println!("---------------DEBUG PRINT---------------");
         println!("Number of all checked folders - {}", self.information.number_of_checked_folders);
         println!("Included directories - {:?}", self.directories.included_directories);
+        println!("Number of files in file list - {:?}", self.directories.file_list.as_ref().map(|e| e.len()));
+        println!("Resource limits - {:?}", self.resource_limits);
+        println!(
+            "Hash algorithm - {:?}, hash size - {}, DCT - {}, use cache - {}, check rotations - {}",
+            self.hash_algorithm, self.hash_size, self.use_dct, self.use_cache, self.check_rotations
+        );
+        println!("Similarity - {:?}, max distance - {}", self.similarity, self.get_used_max_distance());
+        println!("Delete method - {:?}", self.delete_method);
+        println!("Reference images - {:?}", self.reference_images);
+        println!(
+            "Number of removed files - {}, failed to remove - {}, gained space - {}",
+            self.information.number_of_removed_files, self.information.number_of_failed_to_remove_files, self.information.gained_space
+        );
         println!("Checked images {} / Different photos {}", self.information.number_of_checked_files, self.image_hashes.len());
         println!(
             "Size of checked images {} ({} Bytes)",
@@ -543,8 +998,8 @@ impl SaveResults for SimilarImages {
 
         let mut file = match File::create(&file_name) {
             Ok(t) => t,
-            Err(_) => {
-                self.text_messages.errors.push("Failed to create file ".to_string() + file_name.as_str());
+            Err(e) => {
+                self.text_messages.errors.push(Issue::new(IssueKind::SaveFailed, "Failed to create file").with_path(&file_name).with_io_error(e));
                 return false;
             }
         };
@@ -556,24 +1011,24 @@ impl SaveResults for SimilarImages {
         )
         .is_err()
         {
-            self.text_messages.errors.push(format!("Failed to save results to file {}", file_name));
+            self.text_messages.errors.push(Issue::new(IssueKind::SaveFailed, "Failed to save results to file").with_path(&file_name));
             return false;
         }
 
         if !self.similar_vectors.is_empty() {
             write!(file, "{} images which have similar friends\n\n", self.similar_vectors.len()).unwrap();
 
-        // for struct_similar in self.similar_vectors.iter() {
-        //     writeln!(file, "Image {:?} have {} similar images", struct_similar.base_image.path, struct_similar.similar_images.len()).unwrap();
-        //     for similar_picture in struct_similar.similar_images.iter() {
-        //         writeln!(file, "{:?} - Similarity Level: {}", similar_picture.path, get_string_from_similarity(&similar_picture.similarity)).unwrap();
-        //     }
-        //     writeln!(file).unwrap();
-        // }
+            for vec_file_entry in &self.similar_vectors {
+                writeln!(file, "Image {:?} have {} similar images", vec_file_entry[0].path, vec_file_entry.len() - 1).unwrap();
+                for file_entry in vec_file_entry.iter().skip(1) {
+                    writeln!(file, "{:?} - Distance: {}", file_entry.path, file_entry.distance).unwrap();
+                }
+                writeln!(file).unwrap();
+            }
         } else {
             write!(file, "Not found any similar images.").unwrap();
         }
-        Common::print_time(start_time, SystemTime::now(), "save_results_to_file".to_string());
+        Common::log_time(start_time, "save_results_to_file");
         true
     }
 }
@@ -586,11 +1041,12 @@ impl PrintResults for SimilarImages {
             for vec_file_entry in &self.similar_vectors {
                 for file_entry in vec_file_entry {
                     println!(
-                        "{} - {} - {} - {}",
+                        "{} - {} - {} - {} (distance {})",
                         file_entry.path.display(),
                         file_entry.dimensions,
                         file_entry.size.file_size(options::BINARY).unwrap(),
-                        get_string_from_similarity(&file_entry.similarity)
+                        get_string_from_similarity(&get_similarity_from_distance(file_entry.distance, self.hash_size)),
+                        file_entry.distance
                     );
                 }
                 println!();
@@ -599,6 +1055,40 @@ impl PrintResults for SimilarImages {
     }
 }
 
+/// All formats known to image crate, only these which decoders are compiled in are checked
+const ALL_IMAGE_FORMATS: [ImageFormat; 13] = [
+    ImageFormat::Png,
+    ImageFormat::Jpeg,
+    ImageFormat::Gif,
+    ImageFormat::WebP,
+    ImageFormat::Pnm,
+    ImageFormat::Tiff,
+    ImageFormat::Tga,
+    ImageFormat::Dds,
+    ImageFormat::Bmp,
+    ImageFormat::Ico,
+    ImageFormat::Hdr,
+    ImageFormat::Farbfeld,
+    ImageFormat::Avif,
+];
+
+/// Extensions of image formats which can be decoded and hashed, user allowed and excluded extensions are checked separately
+pub fn get_supported_image_extensions() -> Vec<&'static str> {
+    ALL_IMAGE_FORMATS.iter().filter(|format| can_decode(**format)).flat_map(|format| format.extensions_str().iter().copied()).collect()
+}
+
+/// Missing decoder is reported before reading any data, so it can be checked by decoding empty image
+fn can_decode(format: ImageFormat) -> bool {
+    match image::load_from_memory_with_format(&[], format) {
+        Err(ImageError::Unsupported(e)) => !matches!(e.kind(), UnsupportedErrorKind::Format(_)),
+        _ => true,
+    }
+}
+
+fn parse_image_extensions(extensions: &str) -> Vec<String> {
+    extensions.split(',').map(|e| e.trim().trim_start_matches('.').to_lowercase()).filter(|e| !e.is_empty()).collect()
+}
+
 fn get_string_from_similarity(similarity: &Similarity) -> &str {
     match similarity {
         Similarity::VerySmall => "Very Small",
@@ -609,3 +1099,132 @@ fn get_string_from_similarity(similarity: &Similarity) -> &str {
         Similarity::None => panic!(),
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::common_tool::Tool;
+    use crate::similar_images::{
+        cluster_hashes, get_best_quality_index, get_image_quality, get_max_distance, get_similarity_from_distance, get_supported_image_extensions, parse_image_extensions, DeleteMethod, FileEntry, Hamming, Node, SimilarImages, Similarity,
+    };
+    use bk_tree::BKTree;
+    use image::{Rgb, RgbImage};
+    use std::fs;
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    fn test_hash_size_scaling() {
+        assert_eq!(get_max_distance(&Similarity::VeryHigh, 8), 0);
+        assert_eq!(get_max_distance(&Similarity::High, 8), 1);
+        assert_eq!(get_max_distance(&Similarity::VerySmall, 8), 4);
+        assert_eq!(get_max_distance(&Similarity::High, 16), 4);
+        assert_eq!(get_max_distance(&Similarity::Medium, 32), 32);
+
+        assert_eq!(get_similarity_from_distance(0, 16), Similarity::VeryHigh);
+        assert_eq!(get_similarity_from_distance(4, 16), Similarity::High);
+        assert_eq!(get_similarity_from_distance(5, 16), Similarity::Medium);
+        assert_eq!(get_similarity_from_distance(64, 32), Similarity::VerySmall);
+    }
+
+    #[test]
+    fn test_supported_image_extensions() {
+        let extensions = get_supported_image_extensions();
+        for extension in ["jpg", "jpeg", "png", "gif", "tif", "tiff", "tga", "pbm", "ff", "hdr", "webp", "bmp", "ico", "dds"].iter() {
+            assert!(extensions.contains(extension), "{} is not supported", extension);
+        }
+        // Only encoder of AVIF is available
+        assert!(!extensions.contains(&"avif"));
+
+        assert_eq!(parse_image_extensions(" .JFIF,heic,, "), vec!["jfif".to_string(), "heic".to_string()]);
+    }
+
+    #[test]
+    fn test_max_distance_overrides_similarity() {
+        let mut sf = SimilarImages::new();
+        sf.set_similarity(Similarity::Medium);
+        sf.set_hash_size(16);
+        assert_eq!(sf.get_used_max_distance(), 8);
+        sf.set_max_distance(Some(6));
+        assert_eq!(sf.get_used_max_distance(), 6);
+    }
+
+    #[test]
+    fn test_cluster_hashes() {
+        // Distances: 0-1 is 2, 1-2 is 2, 0-2 is 4, 3 is far from everything
+        let hashes: Vec<Node> = vec![vec![0b0000_0000], vec![0b0000_0011], vec![0b0000_1111], vec![0b1111_0000]];
+        let mut bktree = BKTree::new(Hamming);
+        for hash in hashes.iter().rev() {
+            bktree.add(hash.clone());
+        }
+
+        let no_other_orientations = vec![Vec::new(); hashes.len()];
+        let groups = cluster_hashes(&hashes, &no_other_orientations, &bktree, 2, None).unwrap();
+        // Chained images are in one group, with middle one as reference
+        assert_eq!(groups, vec![vec![(0, 2), (1, 0), (2, 2)], vec![(3, 0)]]);
+
+        let groups = cluster_hashes(&hashes, &no_other_orientations, &bktree, 1, None).unwrap();
+        assert_eq!(groups.len(), 4);
+
+        // Last hash is inverted first one, which makes it similar when other orientations are checked
+        let mut other_orientations = no_other_orientations;
+        other_orientations[3] = vec![vec![0b0000_1111], vec![0b1111_1111]];
+        let groups = cluster_hashes(&hashes, &other_orientations, &bktree, 0, None).unwrap();
+        assert_eq!(groups, vec![vec![(0, 0)], vec![(1, 0)], vec![(2, 0), (3, 0)]]);
+    }
+
+    #[test]
+    fn test_best_quality_image() {
+        assert!(get_image_quality("100x100", 10, Path::new("a.jpg")) > get_image_quality("50x50", 1000, Path::new("a.png")));
+        assert!(get_image_quality("100x100", 10, Path::new("a.jpg")) > get_image_quality("100x100", 5, Path::new("a.png")));
+        assert!(get_image_quality("100x100", 10, Path::new("a.PNG")) > get_image_quality("100x100", 10, Path::new("a.gif")));
+        assert!(get_image_quality("100x100", 10, Path::new("a.gif")) > get_image_quality("100x100", 10, Path::new("a.jpeg")));
+
+        let file_entry = |path: &str, dimensions: &str, size: u64| FileEntry {
+            path: PathBuf::from(path),
+            size,
+            dimensions: dimensions.to_string(),
+            modified_date: 0,
+            distance: 0,
+        };
+        let vector = vec![file_entry("thumbnail.jpg", "160x120", 5000), file_entry("photo.jpg", "1600x1200", 500_000), file_entry("photo.png", "1600x1200", 500_000)];
+        assert_eq!(get_best_quality_index(&vector), 2);
+        assert_eq!(get_best_quality_index(&vector[..2]), 1);
+        // Equal images - reference image is kept
+        assert_eq!(get_best_quality_index(&[file_entry("a.png", "10x10", 1), file_entry("b.png", "10x10", 1)]), 0);
+    }
+
+    #[test]
+    fn test_reference_images() {
+        let root = fs::canonicalize(std::env::temp_dir()).unwrap().join(format!("czkawka_test_reference_images_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        // Horizontal gradients in different resolutions are similar, vertical gradient is completely different
+        let gradient = |size: u32| RgbImage::from_fn(size, size, |x, _| Rgb([(x * 255 / size) as u8; 3]));
+        gradient(32).save(root.join("reference, with comma.png")).unwrap();
+        gradient(32).save(root.join("copy.png")).unwrap();
+        gradient(64).save(root.join("big.png")).unwrap();
+        RgbImage::from_fn(32, 32, |_, y| Rgb([(y * 8) as u8; 3])).save(root.join("other.png")).unwrap();
+
+        let mut sf = SimilarImages::new();
+        assert!(sf.set_included_directory(root.to_string_lossy().to_string()));
+        assert!(!sf.set_reference_images(&[root.join("missing.png")]));
+        // Commas are allowed in names of reference images
+        assert!(sf.set_reference_images(&[root.join("reference, with comma.png")]));
+        sf.set_minimal_file_size(1);
+        sf.set_use_cache(false);
+        sf.set_similarity(Similarity::VeryHigh);
+        sf.set_delete_method(DeleteMethod::AllExceptBestQuality);
+        sf.find_similar_images(None, None);
+
+        // Reference image is first and isn't repeated between found images
+        let paths: Vec<PathBuf> = sf.get_similar_images().iter().flatten().map(|e| e.path.clone()).collect();
+        assert_eq!(paths, vec![root.join("reference, with comma.png"), root.join("big.png"), root.join("copy.png")]);
+
+        // Reference image is kept even if found image has better quality
+        assert_eq!(sf.get_information().number_of_removed_files, 1);
+        assert!(root.join("reference, with comma.png").exists());
+        assert!(root.join("big.png").exists());
+        assert!(!root.join("copy.png").exists());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,202 @@
+use directories_next::ProjectDirs;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Each hash configuration(algorithm, size, DCT) has its own file, because hashes computed with different settings cannot be compared
+const CACHE_FILE_PREFIX: &str = "cache_similar_images_";
+const CACHE_FILE_EXTENSION: &str = "txt";
+
+/// Hash and dimensions of image, valid only while size and modification date of file are unchanged
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CachedImage {
+    pub size: u64,
+    pub modified_date: u64,
+    pub dimensions: String,
+    pub hash: Vec<u8>,
+}
+
+/// Perceptual hashes of images saved between scans, so only new or changed images need to be decoded
+pub struct ImageHashCache {
+    file: Option<PathBuf>,
+    entries: HashMap<PathBuf, CachedImage>,
+}
+
+impl ImageHashCache {
+    /// Loads cache of given hash configuration from default cache folder, missing or broken file gives empty cache
+    pub fn load(hash_configuration: &str) -> Self {
+        match Self::get_cache_dir() {
+            Some(cache_dir) => Self::load_from_file(cache_dir.join(format!("{}{}.{}", CACHE_FILE_PREFIX, hash_configuration, CACHE_FILE_EXTENSION))),
+            None => Self { file: None, entries: Default::default() },
+        }
+    }
+
+    pub fn load_from_file(file: PathBuf) -> Self {
+        let entries = match fs::read_to_string(&file) {
+            Ok(content) => content.lines().filter_map(parse_line).collect(),
+            Err(_) => Default::default(),
+        };
+        Self { file: Some(file), entries }
+    }
+
+    pub fn get_cache_dir() -> Option<PathBuf> {
+        // Lin: /home/alice/.cache/czkawka
+        // Win: C:\Users\Alice\AppData\Local\Qarmin\Czkawka\cache
+        // Mac: /Users/Alice/Library/Caches/pl.Qarmin.Czkawka
+        ProjectDirs::from("pl", "Qarmin", "Czkawka").map(|e| e.cache_dir().to_path_buf())
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns cached image only if file wasn't changed since it was hashed
+    pub fn get(&self, path: &Path, size: u64, modified_date: u64) -> Option<&CachedImage> {
+        self.entries.get(path).filter(|e| e.size == size && e.modified_date == modified_date)
+    }
+
+    pub fn insert(&mut self, path: PathBuf, cached_image: CachedImage) {
+        self.entries.insert(path, cached_image);
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let file = match &self.file {
+            Some(t) => t,
+            None => return Err("Cannot find folder for cache".to_string()),
+        };
+        if let Some(parent) = file.parent() {
+            if let Err(e) = fs::create_dir_all(parent) {
+                return Err(format!("Cannot create folder {}, reason {}", parent.display(), e));
+            }
+        }
+        let mut content = String::with_capacity(self.entries.len() * 128);
+        for (path, cached_image) in &self.entries {
+            let path = path.to_string_lossy();
+            // Such paths cannot be saved in line based format, so they are just hashed again in next scan
+            if path.contains('\n') || path.contains('\t') {
+                continue;
+            }
+            content.push_str(&format!(
+                "{}\t{}\t{}\t{}\t{}\n",
+                path,
+                cached_image.size,
+                cached_image.modified_date,
+                cached_image.dimensions,
+                cached_image.hash.iter().map(|e| format!("{:02x}", e)).collect::<String>()
+            ));
+        }
+        fs::write(file, content).map_err(|e| format!("Cannot save cache to file {}, reason {}", file.display(), e))
+    }
+
+    /// Removes entries of files which don't exist anymore, returns number of removed entries
+    fn prune_missing(&mut self) -> usize {
+        let number_before = self.entries.len();
+        self.entries.retain(|path, _| path.is_file());
+        number_before - self.entries.len()
+    }
+
+    fn get_cache_files() -> Result<Vec<PathBuf>, String> {
+        let cache_dir = match Self::get_cache_dir() {
+            Some(t) => t,
+            None => return Err("Cannot find folder for cache".to_string()),
+        };
+        let read_dir = match fs::read_dir(&cache_dir) {
+            Ok(t) => t,
+            Err(_) => return Ok(Vec::new()), // Nothing was cached yet
+        };
+        Ok(read_dir
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|e| {
+                let file_name = e.file_name().map(|e| e.to_string_lossy().to_string()).unwrap_or_default();
+                file_name.starts_with(CACHE_FILE_PREFIX) && file_name.ends_with(CACHE_FILE_EXTENSION)
+            })
+            .collect())
+    }
+
+    /// Removes entries of missing files from caches of all hash configurations, returns number of removed entries
+    pub fn prune_all() -> Result<usize, String> {
+        let mut removed_entries = 0;
+        for file in Self::get_cache_files()? {
+            let mut cache = Self::load_from_file(file);
+            removed_entries += cache.prune_missing();
+            cache.save()?;
+        }
+        Ok(removed_entries)
+    }
+
+    /// Removes caches of all hash configurations, returns number of removed files
+    pub fn clear_all() -> Result<usize, String> {
+        let files = Self::get_cache_files()?;
+        for file in &files {
+            if let Err(e) = fs::remove_file(file) {
+                return Err(format!("Cannot remove cache file {}, reason {}", file.display(), e));
+            }
+        }
+        Ok(files.len())
+    }
+}
+
+fn parse_line(line: &str) -> Option<(PathBuf, CachedImage)> {
+    let parts: Vec<&str> = line.split('\t').collect();
+    if parts.len() != 5 {
+        return None;
+    }
+    // Each byte of hash is saved as two hex digits
+    let hash = parts[4]
+        .as_bytes()
+        .chunks(2)
+        .map(|e| if e.len() == 2 { u8::from_str_radix(std::str::from_utf8(e).ok()?, 16).ok() } else { None })
+        .collect::<Option<Vec<u8>>>()?;
+    Some((
+        PathBuf::from(parts[0]),
+        CachedImage {
+            size: parts[1].parse().ok()?,
+            modified_date: parts[2].parse().ok()?,
+            dimensions: parts[3].to_string(),
+            hash,
+        },
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::similar_images_cache::{CachedImage, ImageHashCache};
+    use std::fs;
+
+    #[test]
+    fn test_image_hash_cache() {
+        let root = std::env::temp_dir().join(format!("czkawka_test_image_hash_cache_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        let image = root.join("image.png");
+        fs::write(&image, "").unwrap();
+        let cached_image = CachedImage {
+            size: 100,
+            modified_date: 1_600_000_000,
+            dimensions: "20x10".to_string(),
+            hash: vec![0, 15, 255, 16],
+        };
+
+        let mut cache = ImageHashCache::load_from_file(root.join("cache.txt"));
+        assert!(cache.is_empty());
+        cache.insert(image.clone(), cached_image.clone());
+        cache.insert(root.join("missing.png"), cached_image.clone());
+        cache.save().unwrap();
+
+        let mut cache = ImageHashCache::load_from_file(root.join("cache.txt"));
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get(&image, 100, 1_600_000_000), Some(&cached_image));
+        assert_eq!(cache.get(&image, 101, 1_600_000_000), None);
+        assert_eq!(cache.get(&image, 100, 1_600_000_001), None);
+
+        assert_eq!(cache.prune_missing(), 1);
+        assert_eq!(cache.len(), 1);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
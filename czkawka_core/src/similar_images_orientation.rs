@@ -0,0 +1,137 @@
+use image::DynamicImage;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+/// EXIF is saved at the beginning of JPEG, so there is no need to read whole file
+const MAX_EXIF_SEARCH_SIZE: u64 = 128 * 1024;
+
+const EXIF_ORIENTATION_TAG: u16 = 0x0112;
+const JPEG_START_OF_IMAGE: [u8; 2] = [0xFF, 0xD8];
+const JPEG_START_OF_SCAN: u8 = 0xDA;
+const JPEG_APP1: u8 = 0xE1;
+
+/// Number of orientations of image which can be created with rotations by 90 degrees and mirroring
+pub const NUMBER_OF_ORIENTATIONS: usize = 8;
+
+/// Reads EXIF orientation(1-8) from JPEG or TIFF file, images without it are treated as not rotated.
+/// Only beginning of file is read, so TIFF files with metadata saved at the end are also treated as not rotated
+pub fn read_exif_orientation(path: &Path) -> u16 {
+    let mut content = Vec::new();
+    if File::open(path).and_then(|file| file.take(MAX_EXIF_SEARCH_SIZE).read_to_end(&mut content)).is_err() {
+        return 1;
+    }
+    get_orientation(&content).unwrap_or(1)
+}
+
+fn get_orientation(content: &[u8]) -> Option<u16> {
+    if !content.starts_with(&JPEG_START_OF_IMAGE) {
+        return get_tiff_orientation(content);
+    }
+    // Each JPEG segment starts with 0xFF, marker and length which includes also 2 bytes of length
+    let mut position = JPEG_START_OF_IMAGE.len();
+    while position + 4 <= content.len() {
+        if content[position] != 0xFF || content[position + 1] == JPEG_START_OF_SCAN {
+            return None;
+        }
+        let length = u16::from_be_bytes([content[position + 2], content[position + 3]]) as usize;
+        let segment = content.get(position + 4..position + 2 + length)?;
+        if content[position + 1] == JPEG_APP1 && segment.starts_with(b"Exif\0\0") {
+            return get_tiff_orientation(&segment[6..]);
+        }
+        position += 2 + length;
+    }
+    None
+}
+
+fn get_tiff_orientation(tiff: &[u8]) -> Option<u16> {
+    let big_endian = match tiff.get(0..4)? {
+        [b'I', b'I', 42, 0] => false,
+        [b'M', b'M', 0, 42] => true,
+        _ => return None,
+    };
+    let read_u16 = |offset: usize| -> Option<u16> {
+        let bytes = [*tiff.get(offset)?, *tiff.get(offset + 1)?];
+        Some(if big_endian { u16::from_be_bytes(bytes) } else { u16::from_le_bytes(bytes) })
+    };
+    let read_u32 = |offset: usize| -> Option<u32> {
+        let bytes = [*tiff.get(offset)?, *tiff.get(offset + 1)?, *tiff.get(offset + 2)?, *tiff.get(offset + 3)?];
+        Some(if big_endian { u32::from_be_bytes(bytes) } else { u32::from_le_bytes(bytes) })
+    };
+
+    // Orientation is saved in first IFD, each of its entries has 12 bytes - tag, type, count and value
+    let ifd_offset = read_u32(4)? as usize;
+    let number_of_entries = read_u16(ifd_offset)? as usize;
+    (0..number_of_entries)
+        .map(|index| ifd_offset + 2 + index * 12)
+        .find(|&entry| read_u16(entry) == Some(EXIF_ORIENTATION_TAG))
+        .and_then(|entry| read_u16(entry + 8))
+        .filter(|orientation| (1..=8).contains(orientation))
+}
+
+/// Rotates and mirrors image, so it looks like in image viewers which respect EXIF orientation
+pub fn apply_exif_orientation(image: DynamicImage, orientation: u16) -> DynamicImage {
+    match orientation {
+        2 => image.fliph(),
+        3 => image.rotate180(),
+        4 => image.flipv(),
+        5 => image.rotate90().fliph(),
+        6 => image.rotate90(),
+        7 => image.rotate270().fliph(),
+        8 => image.rotate270(),
+        _ => image,
+    }
+}
+
+/// All rotations by 90 degrees of image and of its mirror, the first one is the unchanged image
+pub fn get_all_orientations(image: &DynamicImage) -> Vec<DynamicImage> {
+    let mirrored = image.fliph();
+    vec![image.clone(), image.rotate90(), image.rotate180(), image.rotate270(), mirrored.rotate90(), mirrored.rotate180(), mirrored.rotate270(), mirrored]
+}
+
+#[cfg(test)]
+mod test {
+    use crate::similar_images_orientation::{apply_exif_orientation, get_all_orientations, get_orientation, NUMBER_OF_ORIENTATIONS};
+    use image::{DynamicImage, GenericImageView, Rgb, RgbImage};
+
+    #[test]
+    fn test_exif_orientation() {
+        // Little endian TIFF header and IFD with only orientation entry
+        let tiff_le: Vec<u8> = vec![b'I', b'I', 42, 0, 8, 0, 0, 0, 1, 0, 0x12, 0x01, 3, 0, 1, 0, 0, 0, 6, 0, 0, 0, 0, 0, 0, 0];
+        assert_eq!(get_orientation(&tiff_le), Some(6));
+        let tiff_be: Vec<u8> = vec![b'M', b'M', 0, 42, 0, 0, 0, 8, 0, 1, 0x01, 0x12, 0, 3, 0, 0, 0, 1, 0, 8, 0, 0, 0, 0, 0, 0];
+        assert_eq!(get_orientation(&tiff_be), Some(8));
+
+        // JPEG with APP0 segment before APP1 with EXIF
+        let mut jpeg: Vec<u8> = vec![0xFF, 0xD8, 0xFF, 0xE0, 0, 4, 0, 0, 0xFF, 0xE1];
+        jpeg.extend_from_slice(&((2 + 6 + tiff_le.len()) as u16).to_be_bytes());
+        jpeg.extend_from_slice(b"Exif\0\0");
+        jpeg.extend_from_slice(&tiff_le);
+        jpeg.extend_from_slice(&[0xFF, 0xDA]);
+        assert_eq!(get_orientation(&jpeg), Some(6));
+
+        assert_eq!(get_orientation(&[0xFF, 0xD8, 0xFF, 0xDA, 0, 2]), None);
+        assert_eq!(get_orientation(b"not an image"), None);
+    }
+
+    #[test]
+    fn test_orientations_of_image() {
+        // Red pixel on the left, blue on the right
+        let mut image = RgbImage::new(2, 1);
+        image.put_pixel(0, 0, Rgb([255, 0, 0]));
+        image.put_pixel(1, 0, Rgb([0, 0, 255]));
+        let image = DynamicImage::ImageRgb8(image);
+
+        // Orientation 6 means that image must be rotated clockwise to be displayed properly
+        let rotated = apply_exif_orientation(image.clone(), 6).to_rgb8();
+        assert_eq!(rotated.dimensions(), (1, 2));
+        assert_eq!(rotated.get_pixel(0, 0), &Rgb([255, 0, 0]));
+
+        let mirrored = apply_exif_orientation(image.clone(), 2).to_rgb8();
+        assert_eq!(mirrored.get_pixel(0, 0), &Rgb([0, 0, 255]));
+
+        let orientations = get_all_orientations(&image);
+        assert_eq!(orientations.len(), NUMBER_OF_ORIENTATIONS);
+        assert_eq!(orientations[0].dimensions(), image.dimensions());
+    }
+}
@@ -1,18 +1,24 @@
-use std::fs::{File, Metadata};
+use std::fs::File;
 use std::io::prelude::*;
-use std::path::PathBuf;
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::time::{Duration, SystemTime};
 use std::{fs, thread};
 
 use crate::common::Common;
+use crate::common_content_type::ContentFilter;
+use crate::common_date_filter::DateFilter;
+use crate::common_dir_traversal::DirTraversal;
+pub use crate::common_dir_traversal::FileEntry;
 use crate::common_directory::Directories;
 use crate::common_extensions::Extensions;
 use crate::common_items::ExcludedItems;
-use crate::common_messages::Messages;
+use crate::common_messages::{Issue, IssueKind, Messages};
+use crate::common_progress::{BytesProgress, BytesProgressMeter};
+use crate::common_resources::ResourceLimits;
+use crate::common_scan_controller::ScanController;
+use crate::common_tool::{ResultEntry, ResultGroup, Tool};
 use crate::common_traits::*;
-use crossbeam_channel::Receiver;
 use rayon::prelude::*;
-use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::thread::sleep;
 
@@ -22,6 +28,8 @@ pub struct ProgressData {
     pub max_stage: u8,
     pub files_checked: usize,
     pub files_to_check: usize,
+    /// Size of files which were checked, known only when checking content of files
+    pub bytes_progress: BytesProgress,
 }
 
 #[derive(Eq, PartialEq, Clone, Debug)]
@@ -30,13 +38,6 @@ pub enum DeleteMethod {
     Delete,
 }
 
-#[derive(Clone)]
-pub struct FileEntry {
-    pub path: PathBuf,
-    pub size: u64,
-    pub modified_date: u64,
-}
-
 /// Info struck with helpful information's about results
 #[derive(Default)]
 pub struct Info {
@@ -63,6 +64,11 @@ pub struct ZeroedFiles {
     allowed_extensions: Extensions,
     excluded_items: ExcludedItems,
     recursive_search: bool,
+    max_depth: Option<usize>,
+    use_ignore_files: bool,
+    date_filter: DateFilter,
+    content_filter: ContentFilter,
+    resource_limits: ResourceLimits,
     delete_method: DeleteMethod,
     stopped_search: bool,
     minimal_file_size: u64,
@@ -75,6 +81,11 @@ impl ZeroedFiles {
             text_messages: Messages::new(),
             information: Info::new(),
             recursive_search: true,
+            max_depth: None,
+            use_ignore_files: false,
+            date_filter: DateFilter::new(),
+            content_filter: ContentFilter::new(),
+            resource_limits: ResourceLimits::new(),
             allowed_extensions: Extensions::new(),
             directories: Directories::new(),
             excluded_items: ExcludedItems::new(),
@@ -86,13 +97,13 @@ impl ZeroedFiles {
         }
     }
 
-    pub fn find_zeroed_files(&mut self, stop_receiver: Option<&Receiver<()>>, progress_sender: Option<&futures::channel::mpsc::Sender<ProgressData>>) {
+    pub fn find_zeroed_files(&mut self, scan_controller: Option<&ScanController>, progress_sender: Option<&futures::channel::mpsc::Sender<ProgressData>>) {
         self.directories.optimize_directories(self.recursive_search, &mut self.text_messages);
-        if !self.check_files(stop_receiver, progress_sender) {
+        if !self.check_files(scan_controller, progress_sender) {
             self.stopped_search = true;
             return;
         }
-        if !self.check_for_zeroed_files(stop_receiver, progress_sender) {
+        if !self.check_for_zeroed_files(scan_controller, progress_sender) {
             self.stopped_search = true;
             return;
         }
@@ -100,18 +111,10 @@ impl ZeroedFiles {
         self.debug_print();
     }
 
-    pub fn get_stopped_search(&self) -> bool {
-        self.stopped_search
-    }
-
     pub const fn get_zeroed_files(&self) -> &Vec<FileEntry> {
         &self.zeroed_files
     }
 
-    pub const fn get_text_messages(&self) -> &Messages {
-        &self.text_messages
-    }
-
     pub const fn get_information(&self) -> &Info {
         &self.information
     }
@@ -131,31 +134,53 @@ impl ZeroedFiles {
         self.recursive_search = recursive_search;
     }
 
-    pub fn set_included_directory(&mut self, included_directory: String) -> bool {
-        self.directories.set_included_directory(included_directory, &mut self.text_messages)
+    /// Only files with these content types(image, audio, video, archive, document) will be checked
+    pub fn set_content_types(&mut self, content_types: String) {
+        self.content_filter.set_content_types(content_types, &mut self.text_messages);
     }
 
-    pub fn set_excluded_directory(&mut self, excluded_directory: String) {
-        self.directories.set_excluded_directory(excluded_directory, &mut self.text_messages);
+    /// Recognizes type of files by their content instead of extension
+    pub fn set_detect_by_content(&mut self, detect_by_content: bool) {
+        self.content_filter.set_detect_by_content(detect_by_content);
+    }
+
+    /// Limits speed of reading files in bytes per second, None or 0 disables limit
+    pub fn set_io_limit(&mut self, io_limit: Option<u64>) {
+        self.resource_limits.set_io_limit(io_limit);
+    }
+
+    /// Scan runs with lowered CPU and I/O priority, so it slows down other programs less
+    pub fn set_low_priority(&mut self, low_priority: bool) {
+        self.resource_limits.set_low_priority(low_priority);
+    }
+
+    /// Number of threads used by parallel stages, None or 0 means number of logical CPUs
+    pub fn set_threads(&mut self, threads: Option<usize>) {
+        self.resource_limits.set_threads(threads);
+    }
+
+    /// Files from list(read from file or "-" for standard input) are checked instead of files from included directories
+    pub fn set_file_list(&mut self, file_list: String, null_separated: bool) -> bool {
+        self.directories.set_file_list(file_list, null_separated, &mut self.text_messages)
     }
+
     pub fn set_allowed_extensions(&mut self, allowed_extensions: String) {
         self.allowed_extensions.set_allowed_extensions(allowed_extensions, &mut self.text_messages);
     }
 
-    pub fn set_excluded_items(&mut self, excluded_items: String) {
-        self.excluded_items.set_excluded_items(excluded_items, &mut self.text_messages);
+    /// Files with these extensions are skipped even if they are allowed
+    pub fn set_excluded_extensions(&mut self, excluded_extensions: String) {
+        self.allowed_extensions.set_excluded_extensions(excluded_extensions, &mut self.text_messages);
+    }
+
+    /// Named groups of extensions which can be used in allowed and excluded extensions
+    pub fn set_extension_groups(&mut self, extension_groups: String) {
+        self.allowed_extensions.set_extension_groups(extension_groups, &mut self.text_messages);
     }
 
     /// Check files for files which have 0
-    fn check_files(&mut self, stop_receiver: Option<&Receiver<()>>, progress_sender: Option<&futures::channel::mpsc::Sender<ProgressData>>) -> bool {
+    fn check_files(&mut self, scan_controller: Option<&ScanController>, progress_sender: Option<&futures::channel::mpsc::Sender<ProgressData>>) -> bool {
         let start_time: SystemTime = SystemTime::now();
-        let mut folders_to_check: Vec<PathBuf> = Vec::with_capacity(1024 * 2); // This should be small enough too not see to big difference and big enough to store most of paths without needing to resize vector
-
-        // Add root folders for finding
-        for id in &self.directories.included_directories {
-            folders_to_check.push(id.clone());
-        }
-        self.information.number_of_checked_folders += folders_to_check.len();
 
         //// PROGRESS THREAD START
         const LOOP_DURATION: u32 = 200; //in ms
@@ -175,6 +200,7 @@ impl ZeroedFiles {
                         max_stage: 1,
                         files_checked: atomic_file_counter.load(Ordering::Relaxed) as usize,
                         files_to_check: 0,
+                        bytes_progress: BytesProgress::default(),
                     })
                     .unwrap();
                 if !progress_thread_run.load(Ordering::Relaxed) {
@@ -186,120 +212,39 @@ impl ZeroedFiles {
             progress_thread_handle = thread::spawn(|| {});
         }
         //// PROGRESS THREAD END
-        while !folders_to_check.is_empty() {
-            if stop_receiver.is_some() && stop_receiver.unwrap().try_recv().is_ok() {
-                // End thread which send info to gui
-                progress_thread_run.store(false, Ordering::Relaxed);
-                progress_thread_handle.join().unwrap();
-                return false;
-            }
-            let current_folder = folders_to_check.pop().unwrap();
-
-            // Read current dir, if permission are denied just go to next
-            let read_dir = match fs::read_dir(&current_folder) {
-                Ok(t) => t,
-                Err(_) => {
-                    self.text_messages.warnings.push(format!("Cannot open dir {}", current_folder.display()));
-                    continue;
-                } // Permissions denied
-            };
-
-            // Check every sub folder/file/link etc.
-            'dir: for entry in read_dir {
-                let entry_data = match entry {
-                    Ok(t) => t,
-                    Err(_) => {
-                        self.text_messages.warnings.push(format!("Cannot read entry in dir {}", current_folder.display()));
-                        continue;
-                    } //Permissions denied
-                };
-                let metadata: Metadata = match entry_data.metadata() {
-                    Ok(t) => t,
-                    Err(_) => {
-                        self.text_messages.warnings.push(format!("Cannot read metadata in dir {}", current_folder.display()));
-                        continue;
-                    } //Permissions denied
-                };
-                if metadata.is_dir() {
-                    self.information.number_of_checked_folders += 1;
-
-                    if !self.recursive_search {
-                        continue;
-                    }
-
-                    let next_folder = current_folder.join(entry_data.file_name());
-                    if self.directories.is_excluded(&next_folder) || self.excluded_items.is_excluded(&next_folder) {
-                        continue 'dir;
-                    }
-
-                    folders_to_check.push(next_folder);
-                } else if metadata.is_file() {
-                    atomic_file_counter.fetch_add(1, Ordering::Relaxed);
-                    if metadata.len() == 0 || metadata.len() < self.minimal_file_size {
-                        self.information.number_of_ignored_files += 1;
-                        continue 'dir;
-                    }
-
-                    let file_name_lowercase: String = match entry_data.file_name().into_string() {
-                        Ok(t) => t,
-                        Err(_) => continue,
-                    }
-                    .to_lowercase();
-
-                    // Checking allowed extensions
-                    if !self.allowed_extensions.file_extensions.is_empty() {
-                        let allowed = self.allowed_extensions.file_extensions.iter().any(|e| file_name_lowercase.ends_with((".".to_string() + e.to_lowercase().as_str()).as_str()));
-                        if !allowed {
-                            // Not an allowed extension, ignore it.
-                            self.information.number_of_ignored_files += 1;
-                            continue 'dir;
-                        }
-                    }
-                    // Checking files
-                    let current_file_name = current_folder.join(entry_data.file_name());
-                    if self.excluded_items.is_excluded(&current_file_name) {
-                        continue 'dir;
-                    }
+        let mut dir_traversal = DirTraversal::new(&self.directories, &self.excluded_items);
+        dir_traversal.set_extensions(&self.allowed_extensions);
+        dir_traversal.set_recursive_search(self.recursive_search);
+        dir_traversal.set_max_depth(self.max_depth);
+        dir_traversal.set_use_ignore_files(self.use_ignore_files);
+        dir_traversal.set_date_filter(self.date_filter);
+        dir_traversal.set_content_filter(&self.content_filter);
+        dir_traversal.set_minimal_file_size(self.minimal_file_size);
+        dir_traversal.set_file_filter(|_, metadata| metadata.len() > 0);
+        let traversal_result = dir_traversal.run(scan_controller, &atomic_file_counter, &mut self.text_messages);
 
-                    // Creating new file entry
-                    let fe: FileEntry = FileEntry {
-                        path: current_file_name.clone(),
-                        size: metadata.len(),
-                        modified_date: match metadata.modified() {
-                            Ok(t) => match t.duration_since(UNIX_EPOCH) {
-                                Ok(d) => d.as_secs(),
-                                Err(_) => {
-                                    self.text_messages.warnings.push(format!("File {} seems to be modified before Unix Epoch.", current_file_name.display()));
-                                    0
-                                }
-                            },
-                            Err(_) => {
-                                self.text_messages.warnings.push(format!("Unable to get modification date from file {}", current_file_name.display()));
-                                continue;
-                            } // Permissions Denied
-                        },
-                    };
-
-                    // Adding files to Vector
-                    self.files_to_check.push(fe);
-
-                    self.information.number_of_checked_files += 1;
-                } else {
-                    // Probably this is symbolic links so we are free to ignore this
-                    self.information.number_of_ignored_things += 1;
-                }
-            }
-        }
         // End thread which send info to gui
         progress_thread_run.store(false, Ordering::Relaxed);
         progress_thread_handle.join().unwrap();
 
-        Common::print_time(start_time, SystemTime::now(), "check_files".to_string());
+        let (files, traversal_information) = match traversal_result {
+            Some(t) => t,
+            None => return false,
+        };
+        self.information.number_of_checked_files += traversal_information.number_of_checked_files;
+        self.information.number_of_checked_folders += traversal_information.number_of_checked_folders;
+        self.information.number_of_ignored_files += traversal_information.number_of_ignored_files;
+        self.information.number_of_ignored_things += traversal_information.number_of_ignored_things;
+
+        // Adding files to Vector
+        self.files_to_check = files;
+
+        Common::log_phase(self.get_id(), "traversal", start_time, self.information.number_of_checked_files);
         true
     }
 
     /// Check files for files which have 0
-    fn check_for_zeroed_files(&mut self, stop_receiver: Option<&Receiver<()>>, progress_sender: Option<&futures::channel::mpsc::Sender<ProgressData>>) -> bool {
+    fn check_for_zeroed_files(&mut self, scan_controller: Option<&ScanController>, progress_sender: Option<&futures::channel::mpsc::Sender<ProgressData>>) -> bool {
         let start_time: SystemTime = SystemTime::now();
 
         //// PROGRESS THREAD START
@@ -307,89 +252,102 @@ impl ZeroedFiles {
         let progress_thread_run = Arc::new(AtomicBool::new(true));
 
         let atomic_file_counter = Arc::new(AtomicUsize::new(0));
+        let atomic_bytes_counter = Arc::new(AtomicU64::new(0));
 
         let progress_thread_handle;
         if let Some(progress_sender) = progress_sender {
             let mut progress_send = progress_sender.clone();
             let progress_thread_run = progress_thread_run.clone();
             let atomic_file_counter = atomic_file_counter.clone();
+            let atomic_bytes_counter = atomic_bytes_counter.clone();
             let files_to_check = self.files_to_check.len();
-            progress_thread_handle = thread::spawn(move || loop {
-                progress_send
-                    .try_send(ProgressData {
-                        current_stage: 1,
-                        max_stage: 1,
-                        files_checked: atomic_file_counter.load(Ordering::Relaxed) as usize,
-                        files_to_check,
-                    })
-                    .unwrap();
-                if !progress_thread_run.load(Ordering::Relaxed) {
-                    break;
+            let bytes_to_check = self.files_to_check.iter().map(|e| e.size).sum();
+            progress_thread_handle = thread::spawn(move || {
+                let mut bytes_progress_meter = BytesProgressMeter::new();
+                loop {
+                    progress_send
+                        .try_send(ProgressData {
+                            current_stage: 1,
+                            max_stage: 1,
+                            files_checked: atomic_file_counter.load(Ordering::Relaxed) as usize,
+                            files_to_check,
+                            bytes_progress: bytes_progress_meter.measure(atomic_bytes_counter.load(Ordering::Relaxed), bytes_to_check),
+                        })
+                        .unwrap();
+                    if !progress_thread_run.load(Ordering::Relaxed) {
+                        break;
+                    }
+                    sleep(Duration::from_millis(LOOP_DURATION as u64));
                 }
-                sleep(Duration::from_millis(LOOP_DURATION as u64));
             });
         } else {
             progress_thread_handle = thread::spawn(|| {});
         }
         //// PROGRESS THREAD END
 
-        self.zeroed_files = self
-            .files_to_check
-            .par_iter()
-            .map(|file_entry| {
-                atomic_file_counter.fetch_add(1, Ordering::Relaxed);
-                if stop_receiver.is_some() && stop_receiver.unwrap().try_recv().is_ok() {
-                    // This will not break
-                    return None;
-                }
-
-                let file_entry = file_entry.clone();
-                let mut n;
-                let mut file_handler: File = match File::open(&file_entry.path) {
-                    Ok(t) => t,
-                    Err(_) => {
-                        return Some(None);
-                    }
-                };
-
-                // First search
-                let mut buffer = [0u8; 64];
-                n = match file_handler.read(&mut buffer) {
-                    Ok(t) => t,
-                    Err(_) => {
-                        return Some(None);
-                    }
-                };
-                for i in buffer[0..n].iter() {
-                    if *i != 0 {
-                        return Some(None);
+        let files_to_check = &self.files_to_check;
+        self.zeroed_files = self.resource_limits.install(|io_throttle| {
+            files_to_check
+                .par_iter()
+                .map(|file_entry| {
+                    atomic_file_counter.fetch_add(1, Ordering::Relaxed);
+                    // Most of files contain non zero bytes at the beginning, so whole file is counted as checked here
+                    atomic_bytes_counter.fetch_add(file_entry.size, Ordering::Relaxed);
+                    if scan_controller.is_some() && scan_controller.unwrap().should_stop() {
+                        // This will not break
+                        return None;
                     }
-                }
-                // Second search
-                loop {
-                    let mut buffer = [0u8; 1024 * 32];
+
+                    let file_entry = file_entry.clone();
+                    let mut n;
+                    let mut file_handler: File = match File::open(&file_entry.path) {
+                        Ok(t) => t,
+                        Err(_) => {
+                            return Some(None);
+                        }
+                    };
+
+                    // First search
+                    let mut buffer = [0u8; 64];
                     n = match file_handler.read(&mut buffer) {
                         Ok(t) => t,
                         Err(_) => {
                             return Some(None);
                         }
                     };
+                    io_throttle.consume(n as u64);
                     for i in buffer[0..n].iter() {
                         if *i != 0 {
                             return Some(None);
                         }
                     }
-                    if n == 0 {
-                        break;
+                    // Second search
+                    loop {
+                        let mut buffer = [0u8; 1024 * 32];
+                        n = match file_handler.read(&mut buffer) {
+                            Ok(t) => t,
+                            Err(_) => {
+                                return Some(None);
+                            }
+                        };
+                        io_throttle.consume(n as u64);
+                        for i in buffer[0..n].iter() {
+                            if *i != 0 {
+                                return Some(None);
+                            }
+                        }
+                        if n == 0 {
+                            break;
+                        }
                     }
-                }
 
-                Some(Some(file_entry))
-            })
-            .while_some()
-            .filter(|file_entry| file_entry.is_some())
-            .map(|file_entry| file_entry.unwrap())
-            .collect::<Vec<_>>();
+                    Some(Some(file_entry))
+                })
+                .while_some()
+                .filter(|file_entry| file_entry.is_some())
+                .map(|file_entry| file_entry.unwrap())
+                .collect::<Vec<_>>()
+        });
 
         // End thread which send info to gui
         progress_thread_run.store(false, Ordering::Relaxed);
@@ -397,7 +355,7 @@ impl ZeroedFiles {
 
         self.information.number_of_zeroed_files = self.zeroed_files.len();
 
-        Common::print_time(start_time, SystemTime::now(), "search for zeroed_files".to_string());
+        Common::log_phase(self.get_id(), "reading_content", start_time, atomic_file_counter.load(Ordering::Relaxed));
         true
     }
 
@@ -405,22 +363,86 @@ impl ZeroedFiles {
     fn delete_files(&mut self) {
         let start_time: SystemTime = SystemTime::now();
 
-        match self.delete_method {
-            DeleteMethod::Delete => {
-                for file_entry in &self.zeroed_files {
-                    if fs::remove_file(file_entry.path.clone()).is_err() {
-                        self.text_messages.warnings.push(file_entry.path.display().to_string());
-                    }
+        if self.delete_method == DeleteMethod::None {
+            return;
+        }
+
+        for file_entry in &self.zeroed_files {
+            match fs::remove_file(&file_entry.path) {
+                Ok(_) => self.information.number_of_removed_files += 1,
+                Err(e) => {
+                    self.information.number_of_failed_to_remove_files += 1;
+                    self.text_messages.warnings.push(Issue::new(IssueKind::DeleteFailed, "Failed to delete").with_path(&file_entry.path).with_io_error(e));
                 }
             }
-            DeleteMethod::None => {
-                //Just do nothing
-            }
         }
 
-        Common::print_time(start_time, SystemTime::now(), "delete_files".to_string());
+        Common::log_phase(self.get_id(), "deletion", start_time, self.information.number_of_removed_files);
     }
 }
+impl Tool for ZeroedFiles {
+    type ProgressData = ProgressData;
+
+    fn get_name(&self) -> &'static str {
+        "zeroed files"
+    }
+
+    fn get_id(&self) -> &'static str {
+        "zeroed_files"
+    }
+
+    fn set_included_directory(&mut self, included_directory: String) -> bool {
+        self.directories.set_included_directory(included_directory, &mut self.text_messages)
+    }
+
+    fn set_excluded_directory(&mut self, excluded_directory: String) {
+        self.directories.set_excluded_directory(excluded_directory, &mut self.text_messages);
+    }
+
+    fn set_excluded_items(&mut self, excluded_items: String) {
+        self.excluded_items.set_excluded_items(excluded_items, &mut self.text_messages);
+    }
+
+    /// Uses rules from .gitignore, .ignore and .czkawkaignore files found in scanned folders
+    fn set_use_ignore_files(&mut self, use_ignore_files: bool) {
+        self.use_ignore_files = use_ignore_files;
+    }
+
+    /// Limits number of levels of subfolders which are checked, 0 means that only included directories are checked
+    fn set_max_depth(&mut self, max_depth: Option<usize>) {
+        self.max_depth = max_depth;
+    }
+
+    fn set_modified_after(&mut self, modified_after: String) {
+        self.date_filter.set_modified_after(modified_after, &mut self.text_messages);
+    }
+
+    fn set_modified_before(&mut self, modified_before: String) {
+        self.date_filter.set_modified_before(modified_before, &mut self.text_messages);
+    }
+
+    fn get_stopped_search(&self) -> bool {
+        self.stopped_search
+    }
+
+    fn get_text_messages(&self) -> &Messages {
+        &self.text_messages
+    }
+
+    fn search(&mut self, scan_controller: Option<&ScanController>, progress_sender: Option<&futures::channel::mpsc::Sender<ProgressData>>) {
+        self.find_zeroed_files(scan_controller, progress_sender);
+    }
+
+    fn get_result_groups(&self) -> Vec<ResultGroup> {
+        if self.zeroed_files.is_empty() {
+            return Vec::new();
+        }
+        vec![ResultGroup {
+            entries: self.zeroed_files.iter().map(ResultEntry::from).collect(),
+        }]
+    }
+}
+
 impl Default for ZeroedFiles {
     fn default() -> Self {
         Self::new()
@@ -453,10 +475,17 @@ impl DebugPrint for ZeroedFiles {
 
         println!("Zeroed list size - {}", self.zeroed_files.len());
         println!("Allowed extensions - {:?}", self.allowed_extensions.file_extensions);
+        println!("Excluded extensions - {:?}", self.allowed_extensions.excluded_extensions);
         println!("Excluded items - {:?}", self.excluded_items.items);
         println!("Included directories - {:?}", self.directories.included_directories);
+        println!("Number of files in file list - {:?}", self.directories.file_list.as_ref().map(|e| e.len()));
         println!("Excluded directories - {:?}", self.directories.excluded_directories);
         println!("Recursive search - {}", self.recursive_search.to_string());
+        println!("Max depth - {:?}", self.max_depth);
+        println!("Resource limits - {:?}", self.resource_limits);
+        println!("Use ignore files - {}", self.use_ignore_files);
+        println!("Date filter - {:?}", self.date_filter);
+        println!("Content filter - {:?}", self.content_filter);
         println!("Delete Method - {:?}", self.delete_method);
         println!("Minimal File Size - {:?}", self.minimal_file_size);
         println!("-----------------------------------------");
@@ -472,8 +501,8 @@ impl SaveResults for ZeroedFiles {
 
         let mut file = match File::create(&file_name) {
             Ok(t) => t,
-            Err(_) => {
-                self.text_messages.errors.push(format!("Failed to create file {}", file_name));
+            Err(e) => {
+                self.text_messages.errors.push(Issue::new(IssueKind::SaveFailed, "Failed to create file").with_path(&file_name).with_io_error(e));
                 return false;
             }
         };
@@ -485,7 +514,7 @@ impl SaveResults for ZeroedFiles {
         )
         .is_err()
         {
-            self.text_messages.errors.push(format!("Failed to save results to file {}", file_name));
+            self.text_messages.errors.push(Issue::new(IssueKind::SaveFailed, "Failed to save results to file").with_path(&file_name));
             return false;
         }
 
@@ -497,7 +526,7 @@ impl SaveResults for ZeroedFiles {
         } else {
             write!(file, "Not found any zeroed files.").unwrap();
         }
-        Common::print_time(start_time, SystemTime::now(), "save_results_to_file".to_string());
+        Common::log_time(start_time, "save_results_to_file");
         true
     }
 }
@@ -511,6 +540,6 @@ impl PrintResults for ZeroedFiles {
             println!("{}", file_entry.path.display());
         }
 
-        Common::print_time(start_time, SystemTime::now(), "print_entries".to_string());
+        Common::log_time(start_time, "print_entries");
     }
 }
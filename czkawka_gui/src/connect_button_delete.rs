@@ -1,10 +1,10 @@
 extern crate gtk;
 use crate::gui_data::GuiData;
 use crate::help_functions::*;
+use czkawka_core::common_tool::Tool;
 use gtk::prelude::*;
 use std::collections::BTreeMap;
-use std::fs;
-use std::fs::Metadata;
+use std::path::Path;
 
 pub fn connect_button_delete(gui_data: &GuiData) {
     let gui_data = gui_data.clone();
@@ -17,10 +17,22 @@ pub fn connect_button_delete(gui_data: &GuiData) {
     let scrolled_window_big_files_finder = gui_data.scrolled_window_big_files_finder.clone();
     let scrolled_window_main_empty_files_finder = gui_data.scrolled_window_main_empty_files_finder.clone();
     let scrolled_window_main_temporary_files_finder = gui_data.scrolled_window_main_temporary_files_finder.clone();
+    #[cfg(feature = "similar-images")]
     let scrolled_window_similar_images_finder = gui_data.scrolled_window_similar_images_finder.clone();
     let scrolled_window_zeroed_files_finder = gui_data.scrolled_window_zeroed_files_finder.clone();
+    #[cfg(feature = "same-music")]
     let scrolled_window_same_music_finder = gui_data.scrolled_window_same_music_finder.clone();
     let check_button_settings_confirm_deletion = gui_data.check_button_settings_confirm_deletion.clone();
+    let shared_duplication_state = gui_data.shared_duplication_state.clone();
+    let shared_empty_folders_state = gui_data.shared_empty_folders_state.clone();
+    let shared_big_files_state = gui_data.shared_big_files_state.clone();
+    let shared_temporary_files_state = gui_data.shared_temporary_files_state.clone();
+    let shared_empty_files_state = gui_data.shared_empty_files_state.clone();
+    #[cfg(feature = "similar-images")]
+    let shared_similar_images_state = gui_data.shared_similar_images_state.clone();
+    #[cfg(feature = "same-music")]
+    let shared_same_music_state = gui_data.shared_same_music_state.clone();
+    let shared_zeroed_files_state = gui_data.shared_zeroed_files_state.clone();
 
     buttons_delete.connect_clicked(move |_| {
         if check_button_settings_confirm_deletion.get_active() {
@@ -51,22 +63,49 @@ pub fn connect_button_delete(gui_data: &GuiData) {
 
         match notebook_main_children_names.get(notebook_main.get_current_page().unwrap() as usize).unwrap().as_str() {
             "notebook_main_duplicate_finder_label" => {
-                tree_remove(scrolled_window_duplicate_finder.clone(), ColumnsDuplicates::Name as i32, ColumnsDuplicates::Path as i32, ColumnsDuplicates::Color as i32, &gui_data);
+                tree_remove(
+                    &*shared_duplication_state.borrow(),
+                    scrolled_window_duplicate_finder.clone(),
+                    ColumnsDuplicates::Name as i32,
+                    ColumnsDuplicates::Path as i32,
+                    ColumnsDuplicates::Color as i32,
+                    &gui_data,
+                );
             }
             "scrolled_window_main_empty_folder_finder" => {
-                empty_folder_remover(scrolled_window_main_empty_folder_finder.clone(), ColumnsEmptyFolders::Name as i32, ColumnsEmptyFolders::Path as i32, &gui_data);
+                basic_remove(
+                    &*shared_empty_folders_state.borrow(),
+                    scrolled_window_main_empty_folder_finder.clone(),
+                    ColumnsEmptyFolders::Name as i32,
+                    ColumnsEmptyFolders::Path as i32,
+                    &gui_data,
+                );
             }
             "scrolled_window_main_empty_files_finder" => {
-                basic_remove(scrolled_window_main_empty_files_finder.clone(), ColumnsEmptyFiles::Name as i32, ColumnsEmptyFiles::Path as i32, &gui_data);
+                basic_remove(
+                    &*shared_empty_files_state.borrow(),
+                    scrolled_window_main_empty_files_finder.clone(),
+                    ColumnsEmptyFiles::Name as i32,
+                    ColumnsEmptyFiles::Path as i32,
+                    &gui_data,
+                );
             }
             "scrolled_window_main_temporary_files_finder" => {
-                basic_remove(scrolled_window_main_temporary_files_finder.clone(), ColumnsTemporaryFiles::Name as i32, ColumnsTemporaryFiles::Path as i32, &gui_data);
+                basic_remove(
+                    &*shared_temporary_files_state.borrow(),
+                    scrolled_window_main_temporary_files_finder.clone(),
+                    ColumnsTemporaryFiles::Name as i32,
+                    ColumnsTemporaryFiles::Path as i32,
+                    &gui_data,
+                );
             }
             "notebook_big_main_file_finder" => {
-                basic_remove(scrolled_window_big_files_finder.clone(), ColumnsBigFiles::Name as i32, ColumnsBigFiles::Path as i32, &gui_data);
+                basic_remove(&*shared_big_files_state.borrow(), scrolled_window_big_files_finder.clone(), ColumnsBigFiles::Name as i32, ColumnsBigFiles::Path as i32, &gui_data);
             }
+            #[cfg(feature = "similar-images")]
             "notebook_main_similar_images_finder_label" => {
                 tree_remove(
+                    &*shared_similar_images_state.borrow(),
                     scrolled_window_similar_images_finder.clone(),
                     ColumnsSimilarImages::Name as i32,
                     ColumnsSimilarImages::Path as i32,
@@ -75,103 +114,32 @@ pub fn connect_button_delete(gui_data: &GuiData) {
                 );
             }
             "notebook_main_zeroed_files_finder" => {
-                basic_remove(scrolled_window_zeroed_files_finder.clone(), ColumnsZeroedFiles::Name as i32, ColumnsZeroedFiles::Path as i32, &gui_data);
+                basic_remove(
+                    &*shared_zeroed_files_state.borrow(),
+                    scrolled_window_zeroed_files_finder.clone(),
+                    ColumnsZeroedFiles::Name as i32,
+                    ColumnsZeroedFiles::Path as i32,
+                    &gui_data,
+                );
             }
+            #[cfg(feature = "same-music")]
             "notebook_main_same_music_finder" => {
-                tree_remove(scrolled_window_same_music_finder.clone(), ColumnsSameMusic::Name as i32, ColumnsSameMusic::Path as i32, ColumnsSameMusic::Color as i32, &gui_data);
+                tree_remove(
+                    &*shared_same_music_state.borrow(),
+                    scrolled_window_same_music_finder.clone(),
+                    ColumnsSameMusic::Name as i32,
+                    ColumnsSameMusic::Path as i32,
+                    ColumnsSameMusic::Color as i32,
+                    &gui_data,
+                );
             }
             e => panic!("Not existent {}", e),
         }
     });
 }
 
-fn empty_folder_remover(scrolled_window: gtk::ScrolledWindow, column_file_name: i32, column_path: i32, gui_data: &GuiData) {
-    let text_view_errors = gui_data.text_view_errors.clone();
-
-    let tree_view = scrolled_window.get_children().get(0).unwrap().clone().downcast::<gtk::TreeView>().unwrap();
-    let selection = tree_view.get_selection();
-
-    let (selection_rows, tree_model) = selection.get_selected_rows();
-    if selection_rows.is_empty() {
-        return;
-    }
-    let list_store = tree_model.clone().downcast::<gtk::ListStore>().unwrap();
-
-    // let new_tree_model = TreeModel::new(); // TODO - maybe create new model when inserting a new data, because this seems to be not optimal when using thousands of rows
-
-    let mut messages: String = "".to_string();
-
-    // Must be deleted from end to start, because when deleting entries, TreePath(and also TreeIter) will points to invalid data
-    for tree_path in selection_rows.iter().rev() {
-        let name = tree_model.get_value(&tree_model.get_iter(tree_path).unwrap(), column_file_name).get::<String>().unwrap().unwrap();
-        let path = tree_model.get_value(&tree_model.get_iter(tree_path).unwrap(), column_path).get::<String>().unwrap().unwrap();
-
-        // We must check if folder is really empty or contains only other empty folders
-        let mut error_happened = false;
-        let mut folders_to_check: Vec<String> = vec![format!("{}/{}", path, name)];
-        let mut current_folder: String;
-        let mut next_folder: String;
-        'dir: while !folders_to_check.is_empty() {
-            current_folder = folders_to_check.pop().unwrap();
-            let read_dir = match fs::read_dir(&current_folder) {
-                Ok(t) => t,
-                Err(_) => {
-                    error_happened = true;
-                    break 'dir;
-                }
-            };
-
-            for entry in read_dir {
-                let entry_data = match entry {
-                    Ok(t) => t,
-                    Err(_) => {
-                        error_happened = true;
-                        break 'dir;
-                    }
-                };
-                let metadata: Metadata = match entry_data.metadata() {
-                    Ok(t) => t,
-                    Err(_) => {
-                        error_happened = true;
-                        break 'dir;
-                    }
-                };
-                if metadata.is_dir() {
-                    next_folder = "".to_owned()
-                        + &current_folder
-                        + "/"
-                        + match &entry_data.file_name().into_string() {
-                            Ok(t) => t,
-                            Err(_) => {
-                                error_happened = true;
-                                break 'dir;
-                            }
-                        };
-                    folders_to_check.push(next_folder.clone());
-                } else {
-                    error_happened = true;
-                }
-            }
-        }
-
-        if !error_happened {
-            match fs::remove_dir_all(format!("{}/{}", path, name)) {
-                Ok(_) => {
-                    list_store.remove(&list_store.get_iter(tree_path).unwrap());
-                }
-                Err(_) => error_happened = true,
-            }
-        }
-        if error_happened {
-            messages += format!("Failed to remove folder {}/{} because folder doesn't exists, you don't have permissions or isn't empty.\n", path, name).as_str()
-        }
-    }
-
-    text_view_errors.get_buffer().unwrap().set_text(messages.as_str());
-    selection.unselect_all();
-}
-
-fn basic_remove(scrolled_window: gtk::ScrolledWindow, column_file_name: i32, column_path: i32, gui_data: &GuiData) {
+/// Entries are removed by tool which found them, e.g. empty folders are removed only when they are still empty
+fn basic_remove<T: Tool>(tool: &T, scrolled_window: gtk::ScrolledWindow, column_file_name: i32, column_path: i32, gui_data: &GuiData) {
     let text_view_errors = gui_data.text_view_errors.clone();
 
     let tree_view = scrolled_window.get_children().get(0).unwrap().clone().downcast::<gtk::TreeView>().unwrap();
@@ -192,11 +160,11 @@ fn basic_remove(scrolled_window: gtk::ScrolledWindow, column_file_name: i32, col
         let name = tree_model.get_value(&tree_model.get_iter(tree_path).unwrap(), column_file_name).get::<String>().unwrap().unwrap();
         let path = tree_model.get_value(&tree_model.get_iter(tree_path).unwrap(), column_path).get::<String>().unwrap().unwrap();
 
-        match fs::remove_file(format!("{}/{}", path, name)) {
+        match tool.delete_entry(&Path::new(&path).join(&name)) {
             Ok(_) => {
                 list_store.remove(&list_store.get_iter(tree_path).unwrap());
             }
-            Err(_) => messages += format!("Failed to remove file {}/{} because file doesn't exists or you don't have permissions.\n", path, name).as_str(),
+            Err(e) => messages += format!("Failed to remove {}/{}, reason {}\n", path, name, e).as_str(),
         }
     }
 
@@ -206,7 +174,7 @@ fn basic_remove(scrolled_window: gtk::ScrolledWindow, column_file_name: i32, col
 
 // Remove all occurrences - remove every element which have same path and name as even non selected ones
 //
-fn tree_remove(scrolled_window: gtk::ScrolledWindow, column_file_name: i32, column_path: i32, column_color: i32, gui_data: &GuiData) {
+fn tree_remove<T: Tool>(tool: &T, scrolled_window: gtk::ScrolledWindow, column_file_name: i32, column_path: i32, column_color: i32, gui_data: &GuiData) {
     let text_view_errors = gui_data.text_view_errors.clone();
 
     let tree_view = scrolled_window.get_children().get(0).unwrap().clone().downcast::<gtk::TreeView>().unwrap();
@@ -242,7 +210,7 @@ fn tree_remove(scrolled_window: gtk::ScrolledWindow, column_file_name: i32, colu
         vec_file_name.sort();
         vec_file_name.dedup();
         for file_name in vec_file_name {
-            if fs::remove_file(format!("{}/{}", path.clone(), file_name.clone())).is_err() {
+            if tool.delete_entry(&Path::new(&path).join(&file_name)).is_err() {
                 messages += format!(
                     "Failed to remove file {}/{}. It is possible that you already deleted it, because similar images shows all possible file doesn't exists or you don't have permissions.\n",
                     path, file_name
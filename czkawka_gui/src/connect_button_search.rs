@@ -4,16 +4,21 @@ extern crate gtk;
 use crate::gui_data::GuiData;
 use crate::help_functions::*;
 use czkawka_core::big_file::BigFile;
+use czkawka_core::common_tool::Tool;
 use czkawka_core::duplicate::DuplicateFinder;
 use czkawka_core::empty_files::EmptyFiles;
 use czkawka_core::empty_folder::EmptyFolder;
+#[cfg(feature = "same-music")]
 use czkawka_core::same_music::{MusicSimilarity, SameMusic};
+#[cfg(feature = "similar-images")]
 use czkawka_core::similar_images::SimilarImages;
 use czkawka_core::temporary::Temporary;
 use czkawka_core::zeroed::ZeroedFiles;
 use glib::Sender;
 use gtk::prelude::*;
 use gtk::WindowPosition;
+#[cfg(feature = "similar-images")]
+use std::mem;
 use std::thread;
 
 #[allow(clippy::too_many_arguments)]
@@ -24,8 +29,8 @@ pub fn connect_button_search(
     futures_sender_empty_files: futures::channel::mpsc::Sender<empty_files::ProgressData>,
     futures_sender_empty_folder: futures::channel::mpsc::Sender<empty_folder::ProgressData>,
     futures_sender_big_file: futures::channel::mpsc::Sender<big_file::ProgressData>,
-    futures_sender_same_music: futures::channel::mpsc::Sender<same_music::ProgressData>,
-    futures_sender_similar_images: futures::channel::mpsc::Sender<similar_images::ProgressData>,
+    #[cfg(feature = "same-music")] futures_sender_same_music: futures::channel::mpsc::Sender<same_music::ProgressData>,
+    #[cfg(feature = "similar-images")] futures_sender_similar_images: futures::channel::mpsc::Sender<similar_images::ProgressData>,
     futures_sender_temporary: futures::channel::mpsc::Sender<temporary::ProgressData>,
     futures_sender_zeroed: futures::channel::mpsc::Sender<zeroed::ProgressData>,
 ) {
@@ -37,35 +42,89 @@ pub fn connect_button_search(
     let buttons_search_clone = gui_data.buttons_search.clone();
     let buttons_array = gui_data.buttons_array.clone();
     let check_button_recursive = gui_data.check_button_recursive.clone();
+    let entry_max_depth = gui_data.entry_max_depth.clone();
+    let check_button_use_ignore_files = gui_data.check_button_use_ignore_files.clone();
+    let check_button_content_type_image = gui_data.check_button_content_type_image.clone();
+    let check_button_content_type_audio = gui_data.check_button_content_type_audio.clone();
+    let check_button_content_type_video = gui_data.check_button_content_type_video.clone();
+    let check_button_content_type_archive = gui_data.check_button_content_type_archive.clone();
+    let check_button_content_type_document = gui_data.check_button_content_type_document.clone();
+    let check_button_detect_by_content = gui_data.check_button_detect_by_content.clone();
+    let check_button_settings_low_priority = gui_data.check_button_settings_low_priority.clone();
+    let entry_settings_io_limit = gui_data.entry_settings_io_limit.clone();
+    let entry_settings_io_threads = gui_data.entry_settings_io_threads.clone();
+    #[cfg(feature = "similar-images")]
+    let entry_settings_cpu_threads = gui_data.entry_settings_cpu_threads.clone();
     let entry_excluded_items = gui_data.entry_excluded_items.clone();
+    #[cfg(feature = "same-music")]
     let entry_same_music_minimal_size = gui_data.entry_same_music_minimal_size.clone();
     let entry_allowed_extensions = gui_data.entry_allowed_extensions.clone();
+    let entry_excluded_extensions = gui_data.entry_excluded_extensions.clone();
+    let entry_extension_groups = gui_data.entry_extension_groups.clone();
+    let entry_modified_after = gui_data.entry_modified_after.clone();
+    let entry_modified_before = gui_data.entry_modified_before.clone();
+    #[cfg(feature = "same-music")]
     let buttons_names = gui_data.buttons_names.clone();
     let radio_button_duplicates_name = gui_data.radio_button_duplicates_name.clone();
     let radio_button_duplicates_size = gui_data.radio_button_duplicates_size.clone();
     let radio_button_duplicates_hashmb = gui_data.radio_button_duplicates_hashmb.clone();
     let radio_button_duplicates_hash = gui_data.radio_button_duplicates_hash.clone();
+    #[cfg(feature = "similar-images")]
     let radio_button_similar_images_very_small = gui_data.radio_button_similar_images_very_small.clone();
+    #[cfg(feature = "similar-images")]
     let radio_button_similar_images_small = gui_data.radio_button_similar_images_small.clone();
+    #[cfg(feature = "similar-images")]
     let radio_button_similar_images_medium = gui_data.radio_button_similar_images_medium.clone();
+    #[cfg(feature = "similar-images")]
     let radio_button_similar_images_high = gui_data.radio_button_similar_images_high.clone();
+    #[cfg(feature = "similar-images")]
     let radio_button_similar_images_very_high = gui_data.radio_button_similar_images_very_high.clone();
+    #[cfg(feature = "similar-images")]
+    let combo_box_text_similar_images_hash_alg = gui_data.combo_box_text_similar_images_hash_alg.clone();
+    #[cfg(feature = "similar-images")]
+    let combo_box_text_similar_images_hash_size = gui_data.combo_box_text_similar_images_hash_size.clone();
+    #[cfg(feature = "similar-images")]
+    let check_button_similar_images_use_dct = gui_data.check_button_similar_images_use_dct.clone();
+    #[cfg(feature = "similar-images")]
+    let check_button_similar_images_max_distance = gui_data.check_button_similar_images_max_distance.clone();
+    #[cfg(feature = "similar-images")]
+    let scale_similar_images_max_distance = gui_data.scale_similar_images_max_distance.clone();
+    #[cfg(feature = "similar-images")]
+    let check_button_similar_images_use_cache = gui_data.check_button_similar_images_use_cache.clone();
+    #[cfg(feature = "similar-images")]
+    let check_button_similar_images_check_rotations = gui_data.check_button_similar_images_check_rotations.clone();
+    #[cfg(feature = "similar-images")]
+    let entry_similar_images_additional_extensions = gui_data.entry_similar_images_additional_extensions.clone();
+    #[cfg(feature = "similar-images")]
+    let entry_similar_images_excluded_extensions = gui_data.entry_similar_images_excluded_extensions.clone();
+    #[cfg(feature = "similar-images")]
+    let shared_similar_images_reference_images = gui_data.shared_similar_images_reference_images.clone();
     let entry_duplicate_minimal_size = gui_data.entry_duplicate_minimal_size.clone();
-    let stop_receiver = gui_data.stop_receiver.clone();
+    let scan_controller = gui_data.scan_controller.clone();
+    let label_pause_in_dialog = gui_data.label_pause_in_dialog.clone();
     let entry_big_files_number = gui_data.entry_big_files_number.clone();
+    #[cfg(feature = "similar-images")]
     let entry_similar_images_minimal_size = gui_data.entry_similar_images_minimal_size.clone();
+    #[cfg(feature = "same-music")]
     let check_button_music_title: gtk::CheckButton = gui_data.check_button_music_title.clone();
+    #[cfg(feature = "same-music")]
     let check_button_music_artist: gtk::CheckButton = gui_data.check_button_music_artist.clone();
+    #[cfg(feature = "same-music")]
     let check_button_music_album_title: gtk::CheckButton = gui_data.check_button_music_album_title.clone();
+    #[cfg(feature = "same-music")]
     let check_button_music_album_artist: gtk::CheckButton = gui_data.check_button_music_album_artist.clone();
+    #[cfg(feature = "same-music")]
     let check_button_music_year: gtk::CheckButton = gui_data.check_button_music_year.clone();
+    #[cfg(feature = "same-music")]
     let shared_buttons = gui_data.shared_buttons.clone();
     let scrolled_window_main_empty_folder_finder = gui_data.scrolled_window_main_empty_folder_finder.clone();
     let scrolled_window_main_empty_files_finder = gui_data.scrolled_window_main_empty_files_finder.clone();
     let scrolled_window_big_files_finder = gui_data.scrolled_window_big_files_finder.clone();
     let scrolled_window_duplicate_finder = gui_data.scrolled_window_duplicate_finder.clone();
     let scrolled_window_main_temporary_files_finder = gui_data.scrolled_window_main_temporary_files_finder.clone();
+    #[cfg(feature = "same-music")]
     let scrolled_window_same_music_finder = gui_data.scrolled_window_same_music_finder.clone();
+    #[cfg(feature = "similar-images")]
     let scrolled_window_similar_images_finder = gui_data.scrolled_window_similar_images_finder.clone();
     let scrolled_window_zeroed_files_finder = gui_data.scrolled_window_zeroed_files_finder.clone();
     let text_view_errors = gui_data.text_view_errors.clone();
@@ -74,17 +133,52 @@ pub fn connect_button_search(
     let grid_progress_stages = gui_data.grid_progress_stages.clone();
     let progress_bar_current_stage = gui_data.progress_bar_current_stage.clone();
     let progress_bar_all_stages = gui_data.progress_bar_all_stages.clone();
+    #[cfg(feature = "similar-images")]
     let image_preview_similar_images = gui_data.image_preview_similar_images.clone();
 
     buttons_search_clone.connect_clicked(move |_| {
         let included_directories = get_string_from_list_store(&scrolled_window_included_directories);
         let excluded_directories = get_string_from_list_store(&scrolled_window_excluded_directories);
         let recursive_search = check_button_recursive.get_active();
+        let max_depth = entry_max_depth.get_text().as_str().trim().parse::<usize>().ok(); // Empty or invalid value means no limit
+        let use_ignore_files = check_button_use_ignore_files.get_active();
         let excluded_items = entry_excluded_items.get_text().as_str().to_string();
         let allowed_extensions = entry_allowed_extensions.get_text().as_str().to_string();
+        let excluded_extensions = entry_excluded_extensions.get_text().as_str().to_string();
+        let extension_groups = entry_extension_groups.get_text().as_str().to_string();
+        let modified_after = entry_modified_after.get_text().as_str().to_string();
+        let modified_before = entry_modified_before.get_text().as_str().to_string();
+        let mut content_types: Vec<&str> = Vec::new();
+        if check_button_content_type_image.get_active() {
+            content_types.push("image");
+        }
+        if check_button_content_type_audio.get_active() {
+            content_types.push("audio");
+        }
+        if check_button_content_type_video.get_active() {
+            content_types.push("video");
+        }
+        if check_button_content_type_archive.get_active() {
+            content_types.push("archive");
+        }
+        if check_button_content_type_document.get_active() {
+            content_types.push("document");
+        }
+        let content_types = content_types.join(",");
+        let detect_by_content = check_button_detect_by_content.get_active();
+        let low_priority = check_button_settings_low_priority.get_active();
+        let io_limit = entry_settings_io_limit.get_text().as_str().trim().parse::<u64>().ok(); // Empty or invalid value means no limit
+                                                                                               // Reading files mostly waits for disk and decoding images mostly computes, so they may use different number of threads
+        let io_threads = entry_settings_io_threads.get_text().as_str().trim().parse::<usize>().ok();
+        #[cfg(feature = "similar-images")]
+        let cpu_threads = entry_settings_cpu_threads.get_text().as_str().trim().parse::<usize>().ok();
 
         hide_all_buttons(&buttons_array);
 
+        // Scan may be stopped or paused again only by buttons in progress dialog opened below
+        scan_controller.reset();
+        label_pause_in_dialog.set_text("Pause");
+
         // Disable main notebook from any iteration until search will end
         notebook_main.set_sensitive(false);
 
@@ -124,7 +218,7 @@ pub fn connect_button_search(
                 };
 
                 let glib_stop_sender = glib_stop_sender.clone();
-                let stop_receiver = stop_receiver.clone();
+                let scan_controller = scan_controller.clone();
 
                 let futures_sender_duplicate_files = futures_sender_duplicate_files.clone();
                 // Find duplicates
@@ -135,10 +229,21 @@ pub fn connect_button_search(
                     df.set_recursive_search(recursive_search);
                     df.set_excluded_items(excluded_items);
                     df.set_allowed_extensions(allowed_extensions);
+                    df.set_excluded_extensions(excluded_extensions);
+                    df.set_extension_groups(extension_groups);
+                    df.set_use_ignore_files(use_ignore_files);
+                    df.set_max_depth(max_depth);
+                    df.set_modified_after(modified_after);
+                    df.set_modified_before(modified_before);
+                    df.set_content_types(content_types);
+                    df.set_detect_by_content(detect_by_content);
+                    df.set_io_limit(io_limit);
+                    df.set_low_priority(low_priority);
+                    df.set_threads(io_threads);
                     df.set_minimal_file_size(minimal_file_size);
                     df.set_check_method(check_method);
-                    df.find_duplicates(Some(&stop_receiver), Some(&futures_sender_duplicate_files));
-                    let _ = glib_stop_sender.send(Message::Duplicates(df));
+                    df.find_duplicates(Some(&scan_controller), Some(&futures_sender_duplicate_files));
+                    let _ = glib_stop_sender.send(create_message(df));
                 });
             }
             "scrolled_window_main_empty_files_finder" => {
@@ -150,7 +255,7 @@ pub fn connect_button_search(
                 text_view_errors.get_buffer().unwrap().set_text("");
 
                 let glib_stop_sender = glib_stop_sender.clone();
-                let stop_receiver = stop_receiver.clone();
+                let scan_controller = scan_controller.clone();
 
                 let futures_sender_empty_files = futures_sender_empty_files.clone();
                 // Find empty files
@@ -162,8 +267,16 @@ pub fn connect_button_search(
                     vf.set_recursive_search(recursive_search);
                     vf.set_excluded_items(excluded_items);
                     vf.set_allowed_extensions(allowed_extensions);
-                    vf.find_empty_files(Some(&stop_receiver), Some(&futures_sender_empty_files));
-                    let _ = glib_stop_sender.send(Message::EmptyFiles(vf));
+                    vf.set_excluded_extensions(excluded_extensions);
+                    vf.set_extension_groups(extension_groups);
+                    vf.set_use_ignore_files(use_ignore_files);
+                    vf.set_max_depth(max_depth);
+                    vf.set_modified_after(modified_after);
+                    vf.set_modified_before(modified_before);
+                    vf.set_content_types(content_types);
+                    vf.set_detect_by_content(detect_by_content);
+                    vf.find_empty_files(Some(&scan_controller), Some(&futures_sender_empty_files));
+                    let _ = glib_stop_sender.send(create_message(vf));
                 });
             }
             "scrolled_window_main_empty_folder_finder" => {
@@ -175,7 +288,7 @@ pub fn connect_button_search(
                 text_view_errors.get_buffer().unwrap().set_text("");
 
                 let glib_stop_sender = glib_stop_sender.clone();
-                let stop_receiver = stop_receiver.clone();
+                let scan_controller = scan_controller.clone();
 
                 let futures_sender_empty_folder = futures_sender_empty_folder.clone();
                 // Find empty folders
@@ -184,8 +297,12 @@ pub fn connect_button_search(
                     ef.set_included_directory(included_directories);
                     ef.set_excluded_directory(excluded_directories);
                     ef.set_excluded_items(excluded_items);
-                    ef.find_empty_folders(Some(&stop_receiver), Some(&futures_sender_empty_folder));
-                    let _ = glib_stop_sender.send(Message::EmptyFolders(ef));
+                    ef.set_use_ignore_files(use_ignore_files);
+                    ef.set_max_depth(max_depth);
+                    ef.set_modified_after(modified_after);
+                    ef.set_modified_before(modified_before);
+                    ef.find_empty_folders(Some(&scan_controller), Some(&futures_sender_empty_folder));
+                    let _ = glib_stop_sender.send(create_message(ef));
                 });
             }
             "notebook_big_main_file_finder" => {
@@ -202,7 +319,7 @@ pub fn connect_button_search(
                 };
 
                 let glib_stop_sender = glib_stop_sender.clone();
-                let stop_receiver = stop_receiver.clone();
+                let scan_controller = scan_controller.clone();
                 let futures_sender_big_file = futures_sender_big_file.clone();
                 // Find big files
                 thread::spawn(move || {
@@ -212,9 +329,18 @@ pub fn connect_button_search(
                     bf.set_excluded_directory(excluded_directories);
                     bf.set_recursive_search(recursive_search);
                     bf.set_excluded_items(excluded_items);
+                    bf.set_allowed_extensions(allowed_extensions);
+                    bf.set_excluded_extensions(excluded_extensions);
+                    bf.set_extension_groups(extension_groups);
+                    bf.set_use_ignore_files(use_ignore_files);
+                    bf.set_max_depth(max_depth);
+                    bf.set_modified_after(modified_after);
+                    bf.set_modified_before(modified_before);
+                    bf.set_content_types(content_types);
+                    bf.set_detect_by_content(detect_by_content);
                     bf.set_number_of_files_to_check(numbers_of_files_to_check);
-                    bf.find_big_files(Some(&stop_receiver), Some(&futures_sender_big_file));
-                    let _ = glib_stop_sender.send(Message::BigFiles(bf));
+                    bf.find_big_files(Some(&scan_controller), Some(&futures_sender_big_file));
+                    let _ = glib_stop_sender.send(create_message(bf));
                 });
             }
             "scrolled_window_main_temporary_files_finder" => {
@@ -226,7 +352,7 @@ pub fn connect_button_search(
                 text_view_errors.get_buffer().unwrap().set_text("");
 
                 let glib_stop_sender = glib_stop_sender.clone();
-                let stop_receiver = stop_receiver.clone();
+                let scan_controller = scan_controller.clone();
 
                 let futures_sender_temporary = futures_sender_temporary.clone();
                 // Find temporary files
@@ -237,10 +363,20 @@ pub fn connect_button_search(
                     tf.set_excluded_directory(excluded_directories);
                     tf.set_recursive_search(recursive_search);
                     tf.set_excluded_items(excluded_items);
-                    tf.find_temporary_files(Some(&stop_receiver), Some(&futures_sender_temporary));
-                    let _ = glib_stop_sender.send(Message::Temporary(tf));
+                    tf.set_allowed_extensions(allowed_extensions);
+                    tf.set_excluded_extensions(excluded_extensions);
+                    tf.set_extension_groups(extension_groups);
+                    tf.set_use_ignore_files(use_ignore_files);
+                    tf.set_max_depth(max_depth);
+                    tf.set_modified_after(modified_after);
+                    tf.set_modified_before(modified_before);
+                    tf.set_content_types(content_types);
+                    tf.set_detect_by_content(detect_by_content);
+                    tf.find_temporary_files(Some(&scan_controller), Some(&futures_sender_temporary));
+                    let _ = glib_stop_sender.send(create_message(tf));
                 });
             }
+            #[cfg(feature = "similar-images")]
             "notebook_main_similar_images_finder_label" => {
                 image_preview_similar_images.hide();
 
@@ -252,7 +388,7 @@ pub fn connect_button_search(
                 text_view_errors.get_buffer().unwrap().set_text("");
 
                 let glib_stop_sender = glib_stop_sender.clone();
-                let stop_receiver = stop_receiver.clone();
+                let scan_controller = scan_controller.clone();
 
                 let minimal_file_size = match entry_similar_images_minimal_size.get_text().as_str().parse::<u64>() {
                     Ok(t) => t,
@@ -273,6 +409,20 @@ pub fn connect_button_search(
                 } else {
                     panic!("No radio button is pressed");
                 }
+                let hash_algorithm = get_hash_algorithm_from_id(combo_box_text_similar_images_hash_alg.get_active_id().as_deref().unwrap_or("gradient"));
+                let hash_size = combo_box_text_similar_images_hash_size.get_active_id().and_then(|id| id.parse::<u8>().ok()).unwrap_or(8);
+                let use_dct = check_button_similar_images_use_dct.get_active();
+                let max_distance = if check_button_similar_images_max_distance.get_active() {
+                    Some(scale_similar_images_max_distance.get_value() as u64)
+                } else {
+                    None
+                };
+                let use_cache = check_button_similar_images_use_cache.get_active();
+                let check_rotations = check_button_similar_images_check_rotations.get_active();
+                let additional_image_extensions = entry_similar_images_additional_extensions.get_text().to_string();
+                let excluded_image_extensions = entry_similar_images_excluded_extensions.get_text().to_string();
+                // Next searches look again for all similar images
+                let reference_images = mem::take(&mut *shared_similar_images_reference_images.borrow_mut());
 
                 let futures_sender_similar_images = futures_sender_similar_images.clone();
                 // Find similar images
@@ -283,10 +433,33 @@ pub fn connect_button_search(
                     sf.set_excluded_directory(excluded_directories);
                     sf.set_recursive_search(recursive_search);
                     sf.set_excluded_items(excluded_items);
+                    sf.set_allowed_extensions(allowed_extensions);
+                    sf.set_excluded_extensions(excluded_extensions);
+                    sf.set_extension_groups(extension_groups);
+                    sf.set_use_ignore_files(use_ignore_files);
+                    sf.set_max_depth(max_depth);
+                    sf.set_modified_after(modified_after);
+                    sf.set_modified_before(modified_before);
+                    sf.set_content_types(content_types);
+                    sf.set_detect_by_content(detect_by_content);
+                    sf.set_io_limit(io_limit);
+                    sf.set_low_priority(low_priority);
+                    sf.set_threads(cpu_threads);
                     sf.set_minimal_file_size(minimal_file_size);
                     sf.set_similarity(similarity);
-                    sf.find_similar_images(Some(&stop_receiver), Some(&futures_sender_similar_images));
-                    let _ = glib_stop_sender.send(Message::SimilarImages(sf));
+                    sf.set_max_distance(max_distance);
+                    sf.set_hash_algorithm(hash_algorithm);
+                    sf.set_hash_size(hash_size);
+                    sf.set_use_dct(use_dct);
+                    sf.set_use_cache(use_cache);
+                    sf.set_check_rotations(check_rotations);
+                    sf.set_additional_image_extensions(additional_image_extensions);
+                    sf.set_excluded_image_extensions(excluded_image_extensions);
+                    // Invalid reference image is shown as error instead of silently searching for all similar images
+                    if sf.set_reference_images(&reference_images) {
+                        sf.find_similar_images(Some(&scan_controller), Some(&futures_sender_similar_images));
+                    }
+                    let _ = glib_stop_sender.send(create_message(sf));
                 });
             }
             "notebook_main_zeroed_files_finder" => {
@@ -298,7 +471,7 @@ pub fn connect_button_search(
                 text_view_errors.get_buffer().unwrap().set_text("");
 
                 let glib_stop_sender = glib_stop_sender.clone();
-                let stop_receiver = stop_receiver.clone();
+                let scan_controller = scan_controller.clone();
 
                 let futures_sender_zeroed = futures_sender_zeroed.clone();
                 // Find zeroed files
@@ -310,10 +483,22 @@ pub fn connect_button_search(
                     zf.set_recursive_search(recursive_search);
                     zf.set_excluded_items(excluded_items);
                     zf.set_allowed_extensions(allowed_extensions);
-                    zf.find_zeroed_files(Some(&stop_receiver), Some(&futures_sender_zeroed));
-                    let _ = glib_stop_sender.send(Message::ZeroedFiles(zf));
+                    zf.set_excluded_extensions(excluded_extensions);
+                    zf.set_extension_groups(extension_groups);
+                    zf.set_use_ignore_files(use_ignore_files);
+                    zf.set_max_depth(max_depth);
+                    zf.set_modified_after(modified_after);
+                    zf.set_modified_before(modified_before);
+                    zf.set_content_types(content_types);
+                    zf.set_detect_by_content(detect_by_content);
+                    zf.set_io_limit(io_limit);
+                    zf.set_low_priority(low_priority);
+                    zf.set_threads(io_threads);
+                    zf.find_zeroed_files(Some(&scan_controller), Some(&futures_sender_zeroed));
+                    let _ = glib_stop_sender.send(create_message(zf));
                 });
             }
+            #[cfg(feature = "same-music")]
             "notebook_main_same_music_finder" => {
                 label_stage.show();
                 grid_progress_stages.show_all();
@@ -346,7 +531,7 @@ pub fn connect_button_search(
 
                 if music_similarity != MusicSimilarity::NONE {
                     let glib_stop_sender = glib_stop_sender.clone();
-                    let stop_receiver = stop_receiver.clone();
+                    let scan_controller = scan_controller.clone();
 
                     let futures_sender_same_music = futures_sender_same_music.clone();
                     // Find Similar music
@@ -356,11 +541,23 @@ pub fn connect_button_search(
                         mf.set_included_directory(included_directories);
                         mf.set_excluded_directory(excluded_directories);
                         mf.set_excluded_items(excluded_items);
+                        mf.set_allowed_extensions(allowed_extensions);
+                        mf.set_excluded_extensions(excluded_extensions);
+                        mf.set_extension_groups(extension_groups);
+                        mf.set_use_ignore_files(use_ignore_files);
+                        mf.set_max_depth(max_depth);
+                        mf.set_modified_after(modified_after);
+                        mf.set_modified_before(modified_before);
+                        mf.set_content_types(content_types);
+                        mf.set_detect_by_content(detect_by_content);
+                        mf.set_io_limit(io_limit);
+                        mf.set_low_priority(low_priority);
+                        mf.set_threads(io_threads);
                         mf.set_minimal_file_size(minimal_file_size);
                         mf.set_recursive_search(recursive_search);
                         mf.set_music_similarity(music_similarity);
-                        mf.find_same_music(Some(&stop_receiver), Some(&futures_sender_same_music));
-                        let _ = glib_stop_sender.send(Message::SameMusic(mf));
+                        mf.find_same_music(Some(&scan_controller), Some(&futures_sender_same_music));
+                        let _ = glib_stop_sender.send(create_message(mf));
                     });
                 } else {
                     notebook_main.set_sensitive(true);
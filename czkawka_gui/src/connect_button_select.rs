@@ -9,8 +9,11 @@ pub fn connect_button_select(gui_data: &GuiData) {
     let popover_select_duplicate = gui_data.popover_select_duplicate.clone();
     let popover_select_simple_list = gui_data.popover_select_simple_list.clone();
     let buttons_select = gui_data.buttons_select.clone();
+    let buttons_popover_duplicate_select_all_except_best_quality = gui_data.buttons_popover_duplicate_select_all_except_best_quality.clone();
     buttons_select_clone.connect_clicked(move |_| match notebook_main_children_names.get(notebook_main.get_current_page().unwrap() as usize).unwrap().as_str() {
-        "notebook_main_duplicate_finder_label" | "notebook_main_same_music_finder" | "notebook_main_similar_images_finder_label" => {
+        page @ ("notebook_main_duplicate_finder_label" | "notebook_main_same_music_finder" | "notebook_main_similar_images_finder_label") => {
+            // Quality of image can be compared only in similar images
+            buttons_popover_duplicate_select_all_except_best_quality.set_visible(page == "notebook_main_similar_images_finder_label");
             popover_select_duplicate.set_relative_to(Some(&buttons_select));
             popover_select_duplicate.popup();
         }
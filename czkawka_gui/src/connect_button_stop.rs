@@ -4,8 +4,8 @@ use gtk::prelude::*;
 
 pub fn connect_button_stop(gui_data: &GuiData) {
     let button_stop_in_dialog = gui_data.button_stop_in_dialog.clone();
-    let stop_sender = gui_data.stop_sender.clone();
+    let scan_controller = gui_data.scan_controller.clone();
     button_stop_in_dialog.connect_clicked(move |_| {
-        stop_sender.send(()).unwrap();
+        scan_controller.cancel();
     });
 }
@@ -0,0 +1,29 @@
+extern crate gtk;
+use crate::gui_data::GuiData;
+use gtk::prelude::*;
+
+pub fn connect_date_filter(gui_data: &GuiData) {
+    connect_calendar(&gui_data.buttons_modified_after_calendar, &gui_data.popover_modified_after, &gui_data.calendar_modified_after, &gui_data.entry_modified_after);
+    connect_calendar(&gui_data.buttons_modified_before_calendar, &gui_data.popover_modified_before, &gui_data.calendar_modified_before, &gui_data.entry_modified_before);
+}
+
+/// Shows calendar under button and puts picked day into entry
+fn connect_calendar(button: &gtk::Button, popover: &gtk::Popover, calendar: &gtk::Calendar, entry: &gtk::Entry) {
+    let popover_clone = popover.clone();
+    button.connect_clicked(move |button| {
+        popover_clone.set_relative_to(Some(button));
+        popover_clone.popup();
+    });
+
+    let entry = entry.clone();
+    calendar.connect_day_selected(move |calendar| {
+        let (year, month, day) = calendar.get_date();
+        // Months in GTK calendar are counted from 0
+        entry.set_text(format!("{:04}-{:02}-{:02}", year, month + 1, day).as_str());
+    });
+
+    let popover = popover.clone();
+    calendar.connect_day_selected_double_click(move |_| {
+        popover.popdown();
+    });
+}
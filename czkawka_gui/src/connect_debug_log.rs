@@ -0,0 +1,43 @@
+extern crate gtk;
+use crate::gui_data::GuiData;
+use glib::Sender;
+use gtk::prelude::*;
+use log::{LevelFilter, Log, Metadata, Record};
+use std::sync::Mutex;
+
+/// Sends log messages from scanning threads to main thread, where they are shown in debug log tab
+struct GuiLogger {
+    sender: Mutex<Sender<String>>,
+}
+
+impl Log for GuiLogger {
+    fn enabled(&self, _metadata: &Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &Record) {
+        let line = format!("{} [{}] {}", chrono::Local::now().format("%H:%M:%S%.3f"), record.level(), record.args());
+        let _ = self.sender.lock().unwrap().send(line);
+    }
+
+    fn flush(&self) {}
+}
+
+pub fn connect_debug_log(gui_data: &GuiData) {
+    let (sender, receiver) = glib::MainContext::channel(glib::PRIORITY_DEFAULT);
+    if log::set_boxed_logger(Box::new(GuiLogger { sender: Mutex::new(sender) })).is_ok() {
+        log::set_max_level(LevelFilter::Debug);
+    }
+
+    let text_view_debug_log = gui_data.text_view_debug_log.clone();
+    receiver.attach(None, move |line: String| {
+        let buffer = text_view_debug_log.get_buffer().unwrap();
+        buffer.insert(&mut buffer.get_end_iter(), format!("{}\n", line).as_str());
+        glib::Continue(true)
+    });
+
+    let text_view_debug_log = gui_data.text_view_debug_log.clone();
+    gui_data.button_clear_debug_log.connect_clicked(move |_| {
+        text_view_debug_log.get_buffer().unwrap().set_text("");
+    });
+}
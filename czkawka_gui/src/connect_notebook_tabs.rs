@@ -39,7 +39,9 @@ pub fn connect_notebook_tabs(gui_data: &GuiData) {
             hashmap.insert("notebook_upper_excluded_directories", "excluded_directories");
             hashmap.insert("notebook_upper_excluded_items", "excluded_items");
             hashmap.insert("notebook_upper_allowed_extensions", "allowed_extensions");
+            hashmap.insert("notebook_upper_modification_date", "modification_date");
             hashmap.insert("notebook_upper_settings", "settings");
+            hashmap.insert("notebook_upper_debug_log", "debug_log");
 
             for tab in &notebook_upper_children_names {
                 let name = hashmap.get(tab.as_str()).unwrap().to_string();
@@ -2,8 +2,12 @@ extern crate gtk;
 use crate::gui_data::GuiData;
 use crate::help_functions::*;
 use czkawka_core::common::Common;
+#[cfg(feature = "similar-images")]
+use czkawka_core::similar_images::get_image_quality;
 use gtk::prelude::*;
 use gtk::TreeIter;
+#[cfg(feature = "similar-images")]
+use std::path::{Path, PathBuf};
 
 // File length variable allows users to choose duplicates which have shorter file name
 // e.g. 'tar.gz' will be selected instead 'tar.gz (copy)' etc.
@@ -292,6 +296,75 @@ fn popover_one_newest(popover: &gtk::Popover, scrolled_window: &gtk::ScrolledWin
     popover.popdown();
 }
 
+#[cfg(feature = "similar-images")]
+#[allow(clippy::too_many_arguments)]
+fn popover_all_except_best_quality(popover: &gtk::Popover, scrolled_window: &gtk::ScrolledWindow, reference_images: &[PathBuf], column_color: i32, column_dimensions: i32, column_size_as_bytes: i32, column_file_name: i32, column_path: i32) {
+    let tree_view = get_tree_view(scrolled_window);
+    let selection = tree_view.get_selection();
+    let tree_model = tree_view.get_model().unwrap();
+
+    let tree_iter_all = tree_model.get_iter_first().unwrap(); // Never should be available button where there is no available records
+
+    let mut end: bool = false;
+
+    loop {
+        let mut tree_iter_array: Vec<TreeIter> = Vec::new();
+        let mut reference_indexes: Vec<usize> = Vec::new();
+        let mut best_index: Option<usize> = None;
+        let mut current_index: usize = 0;
+        let mut best_quality = (0, 0, 0);
+
+        loop {
+            let color = tree_model.get_value(&tree_iter_all, column_color).get::<String>().unwrap().unwrap();
+            if color == HEADER_ROW_COLOR {
+                if !tree_model.iter_next(&tree_iter_all) {
+                    end = true;
+                }
+                break;
+            }
+            tree_iter_array.push(tree_iter_all.clone());
+            let dimensions = tree_model.get_value(&tree_iter_all, column_dimensions).get::<String>().unwrap().unwrap();
+            let size = tree_model.get_value(&tree_iter_all, column_size_as_bytes).get::<u64>().unwrap().unwrap();
+            let file_name = tree_model.get_value(&tree_iter_all, column_file_name).get::<String>().unwrap().unwrap();
+            let path = tree_model.get_value(&tree_iter_all, column_path).get::<String>().unwrap().unwrap();
+            let full_path = Path::new(&path).join(file_name);
+            let quality = get_image_quality(&dimensions, size, &full_path);
+            // Reference images are never selected, same as when deleting them in core
+            if reference_images.contains(&full_path) {
+                reference_indexes.push(current_index);
+            }
+            // When quality is same, first image is kept, which is the reference image of group
+            if best_index.is_none() || quality > best_quality {
+                best_quality = quality;
+                best_index = Some(current_index);
+            }
+
+            current_index += 1;
+
+            if !tree_model.iter_next(&tree_iter_all) {
+                end = true;
+                break;
+            }
+        }
+        if best_index.is_none() {
+            continue;
+        }
+        for (index, tree_iter) in tree_iter_array.iter().enumerate() {
+            if index != best_index.unwrap() && !reference_indexes.contains(&index) {
+                selection.select_iter(tree_iter);
+            } else {
+                selection.unselect_iter(tree_iter);
+            }
+        }
+
+        if end {
+            break;
+        }
+    }
+
+    popover.popdown();
+}
+
 fn popover_select_custom(popover: &gtk::Popover, gui_data: &GuiData, scrolled_window: &gtk::ScrolledWindow, column_color: Option<i32>, column_file_name: i32, column_path: i32) {
     popover.popdown();
 
@@ -536,6 +609,8 @@ pub fn connect_popovers(gui_data: &GuiData) {
     connect_all_except_newest(&gui_data);
     connect_one_oldest(&gui_data);
     connect_one_newest(&gui_data);
+    #[cfg(feature = "similar-images")]
+    connect_all_except_best_quality(&gui_data);
 
     connect_select_custom(&gui_data);
     connect_unselect_custom(&gui_data);
@@ -852,6 +927,27 @@ pub fn connect_one_oldest(gui_data: &GuiData) {
     });
 }
 
+#[cfg(feature = "similar-images")]
+pub fn connect_all_except_best_quality(gui_data: &GuiData) {
+    let scrolled_window_similar_images_finder = gui_data.scrolled_window_similar_images_finder.clone();
+    let popover_select_duplicate = gui_data.popover_select_duplicate.clone();
+    let buttons_popover_duplicate_select_all_except_best_quality = gui_data.buttons_popover_duplicate_select_all_except_best_quality.clone();
+    let shared_similar_images_state = gui_data.shared_similar_images_state.clone();
+    // Button is visible only in similar images
+    buttons_popover_duplicate_select_all_except_best_quality.connect_clicked(move |_| {
+        let reference_images = shared_similar_images_state.borrow().get_reference_images().to_vec();
+        popover_all_except_best_quality(
+            &popover_select_duplicate,
+            &scrolled_window_similar_images_finder,
+            &reference_images,
+            ColumnsSimilarImages::Color as i32,
+            ColumnsSimilarImages::Dimensions as i32,
+            ColumnsSimilarImages::SizeAsBytes as i32,
+            ColumnsSimilarImages::Name as i32,
+            ColumnsSimilarImages::Path as i32,
+        );
+    });
+}
 pub fn connect_select_custom(gui_data: &GuiData) {
     let notebook_main_children_names = gui_data.notebook_main_children_names.clone();
     let notebook_main = gui_data.notebook_main.clone();
@@ -1,9 +1,14 @@
 use crate::gui_data::GuiData;
+use crate::help_functions::get_bytes_progress_text;
 
-use czkawka_core::{big_file, duplicate, empty_files, empty_folder, same_music, similar_images, temporary, zeroed};
+#[cfg(feature = "same-music")]
+use czkawka_core::same_music;
+#[cfg(feature = "similar-images")]
+use czkawka_core::similar_images;
+use czkawka_core::{big_file, duplicate, empty_files, empty_folder, temporary, zeroed};
 
 use futures::StreamExt;
-use gtk::{LabelExt, ProgressBarExt, WidgetExt};
+use gtk::{ButtonExt, LabelExt, ProgressBarExt, WidgetExt};
 
 #[allow(clippy::too_many_arguments)]
 pub fn connect_progress_window(
@@ -12,13 +17,29 @@ pub fn connect_progress_window(
     mut futures_receiver_empty_files: futures::channel::mpsc::Receiver<empty_files::ProgressData>,
     mut futures_receiver_empty_folder: futures::channel::mpsc::Receiver<empty_folder::ProgressData>,
     mut futures_receiver_big_files: futures::channel::mpsc::Receiver<big_file::ProgressData>,
-    mut futures_receiver_same_music: futures::channel::mpsc::Receiver<same_music::ProgressData>,
-    mut futures_receiver_similar_images: futures::channel::mpsc::Receiver<similar_images::ProgressData>,
+    #[cfg(feature = "same-music")] mut futures_receiver_same_music: futures::channel::mpsc::Receiver<same_music::ProgressData>,
+    #[cfg(feature = "similar-images")] mut futures_receiver_similar_images: futures::channel::mpsc::Receiver<similar_images::ProgressData>,
     mut futures_receiver_temporary: futures::channel::mpsc::Receiver<temporary::ProgressData>,
     mut futures_receiver_zeroed: futures::channel::mpsc::Receiver<zeroed::ProgressData>,
 ) {
     let main_context = glib::MainContext::default();
 
+    {
+        // Pause and resume
+        let button_pause_in_dialog = gui_data.button_pause_in_dialog.clone();
+        let label_pause_in_dialog = gui_data.label_pause_in_dialog.clone();
+        let scan_controller = gui_data.scan_controller.clone();
+        button_pause_in_dialog.connect_clicked(move |_| {
+            if scan_controller.is_paused() {
+                scan_controller.resume();
+                label_pause_in_dialog.set_text("Pause");
+            } else {
+                scan_controller.pause();
+                label_pause_in_dialog.set_text("Resume");
+            }
+        });
+    }
+
     {
         // Duplicate Files
         let label_stage = gui_data.label_stage.clone();
@@ -42,25 +63,33 @@ pub fn connect_progress_window(
                             1 => {
                                 progress_bar_current_stage.show();
                                 // progress_bar_all_stages.show();
-                                if item.files_to_check != 0 {
+                                if item.bytes_progress.bytes_to_check != 0 {
+                                    let fraction = item.bytes_progress.fraction();
+                                    progress_bar_all_stages.set_fraction((1f64 + fraction) / (item.max_stage + 1) as f64);
+                                    progress_bar_current_stage.set_fraction(fraction);
+                                } else if item.files_to_check != 0 {
                                     progress_bar_all_stages.set_fraction((1f64 + (item.files_checked) as f64 / item.files_to_check as f64) / (item.max_stage + 1) as f64);
                                     progress_bar_current_stage.set_fraction((item.files_checked) as f64 / item.files_to_check as f64);
                                 } else {
                                     progress_bar_all_stages.set_fraction((1f64) / (item.max_stage + 1) as f64);
                                     progress_bar_current_stage.set_fraction(0f64);
                                 }
-                                label_stage.set_text(format!("Analyzed partial hash of {}/{} files", item.files_checked, item.files_to_check).as_str());
+                                label_stage.set_text(format!("Analyzed partial hash of {}/{} files{}", item.files_checked, item.files_to_check, get_bytes_progress_text(&item.bytes_progress)).as_str());
                             }
                             // Hash - first 1MB of file or normal hash
                             2 => {
-                                if item.files_to_check != 0 {
+                                if item.bytes_progress.bytes_to_check != 0 {
+                                    let fraction = item.bytes_progress.fraction();
+                                    progress_bar_all_stages.set_fraction((2f64 + fraction) / (item.max_stage + 1) as f64);
+                                    progress_bar_current_stage.set_fraction(fraction);
+                                } else if item.files_to_check != 0 {
                                     progress_bar_all_stages.set_fraction((2f64 + (item.files_checked) as f64 / item.files_to_check as f64) / (item.max_stage + 1) as f64);
                                     progress_bar_current_stage.set_fraction((item.files_checked) as f64 / item.files_to_check as f64);
                                 } else {
                                     progress_bar_all_stages.set_fraction((2f64) / (item.max_stage + 1) as f64);
                                     progress_bar_current_stage.set_fraction(0f64);
                                 }
-                                label_stage.set_text(format!("Analyzed full hash of {}/{} files", item.files_checked, item.files_to_check).as_str());
+                                label_stage.set_text(format!("Analyzed full hash of {}/{} files{}", item.files_checked, item.files_to_check, get_bytes_progress_text(&item.bytes_progress)).as_str());
                             }
                             _ => {
                                 panic!("Not available current_stage");
@@ -117,6 +146,7 @@ pub fn connect_progress_window(
         };
         main_context.spawn_local(future);
     }
+    #[cfg(feature = "same-music")]
     {
         // Same Music
         let label_stage = gui_data.label_stage.clone();
@@ -131,14 +161,18 @@ pub fn connect_progress_window(
                     }
                     1 => {
                         progress_bar_current_stage.show();
-                        if item.music_to_check != 0 {
+                        if item.bytes_progress.bytes_to_check != 0 {
+                            let fraction = item.bytes_progress.fraction();
+                            progress_bar_all_stages.set_fraction((1f64 + fraction) / (item.max_stage + 1) as f64);
+                            progress_bar_current_stage.set_fraction(fraction);
+                        } else if item.music_to_check != 0 {
                             progress_bar_all_stages.set_fraction((1f64 + (item.music_checked) as f64 / item.music_to_check as f64) / (item.max_stage + 1) as f64);
                             progress_bar_current_stage.set_fraction((item.music_checked) as f64 / item.music_to_check as f64);
                         } else {
                             progress_bar_all_stages.set_fraction((1f64) / (item.max_stage + 1) as f64);
                             progress_bar_current_stage.set_fraction(0f64);
                         }
-                        label_stage.set_text(format!("Reading tags of {}/{} music files", item.music_checked, item.music_to_check).as_str());
+                        label_stage.set_text(format!("Reading tags of {}/{} music files{}", item.music_checked, item.music_to_check, get_bytes_progress_text(&item.bytes_progress)).as_str());
                     }
                     2 => {
                         if item.music_to_check != 0 {
@@ -158,6 +192,7 @@ pub fn connect_progress_window(
         };
         main_context.spawn_local(future);
     }
+    #[cfg(feature = "similar-images")]
     {
         // Similar Images
         let label_stage = gui_data.label_stage.clone();
@@ -172,14 +207,18 @@ pub fn connect_progress_window(
                     }
                     1 => {
                         progress_bar_current_stage.show();
-                        if item.images_to_check != 0 {
+                        if item.bytes_progress.bytes_to_check != 0 {
+                            let fraction = item.bytes_progress.fraction();
+                            progress_bar_all_stages.set_fraction((1f64 + fraction) / (item.max_stage + 1) as f64);
+                            progress_bar_current_stage.set_fraction(fraction);
+                        } else if item.images_to_check != 0 {
                             progress_bar_all_stages.set_fraction((1f64 + (item.images_checked) as f64 / item.images_to_check as f64) / (item.max_stage + 1) as f64);
                             progress_bar_current_stage.set_fraction((item.images_checked) as f64 / item.images_to_check as f64);
                         } else {
                             progress_bar_all_stages.set_fraction((1f64) / (item.max_stage + 1) as f64);
                             progress_bar_current_stage.set_fraction(0f64);
                         }
-                        label_stage.set_text(format!("Hashing {}/{} image", item.images_checked, item.images_to_check).as_str());
+                        label_stage.set_text(format!("Hashing {}/{} image{}", item.images_checked, item.images_to_check, get_bytes_progress_text(&item.bytes_progress)).as_str());
                     }
                     _ => {
                         panic!();
@@ -213,14 +252,18 @@ pub fn connect_progress_window(
                     }
                     1 => {
                         progress_bar_current_stage.show();
-                        if item.files_to_check != 0 {
+                        if item.bytes_progress.bytes_to_check != 0 {
+                            let fraction = item.bytes_progress.fraction();
+                            progress_bar_all_stages.set_fraction((1f64 + fraction) / (item.max_stage + 1) as f64);
+                            progress_bar_current_stage.set_fraction(fraction);
+                        } else if item.files_to_check != 0 {
                             progress_bar_all_stages.set_fraction((1f64 + (item.files_checked) as f64 / item.files_to_check as f64) / (item.max_stage + 1) as f64);
                             progress_bar_current_stage.set_fraction((item.files_checked) as f64 / item.files_to_check as f64);
                         } else {
                             progress_bar_all_stages.set_fraction((1f64) / (item.max_stage + 1) as f64);
                             progress_bar_current_stage.set_fraction(0f64);
                         }
-                        label_stage.set_text(format!("Checking {}/{} file", item.files_checked, item.files_to_check).as_str());
+                        label_stage.set_text(format!("Checking {}/{} file{}", item.files_checked, item.files_to_check, get_bytes_progress_text(&item.bytes_progress)).as_str());
                     }
                     _ => {
                         panic!();
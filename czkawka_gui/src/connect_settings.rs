@@ -1,6 +1,6 @@
 extern crate gtk;
 use crate::gui_data::GuiData;
-use crate::saving_loading::{load_configuration, reset_configuration, save_configuration};
+use crate::saving_loading::{load_configuration, remove_profile, reset_configuration, save_configuration};
 use gtk::prelude::*;
 
 pub fn connect_settings(gui_data: &GuiData) {
@@ -28,4 +28,23 @@ pub fn connect_settings(gui_data: &GuiData) {
             reset_configuration(&gui_data, true);
         });
     }
+    // Connect remove profile button
+    {
+        let gui_data = gui_data.clone();
+        let button_settings_remove_profile = gui_data.button_settings_remove_profile.clone();
+        button_settings_remove_profile.connect_clicked(move |_| {
+            remove_profile(&gui_data);
+        });
+    }
+    // Connect profile selector
+    {
+        let gui_data = gui_data.clone();
+        let combo_box_text_settings_profile = gui_data.combo_box_text_settings_profile.clone();
+        combo_box_text_settings_profile.connect_changed(move |combo_box_text_settings_profile| {
+            // Typing name of new profile also changes selector, but then no profile is chosen from list
+            if combo_box_text_settings_profile.get_active().is_some() {
+                load_configuration(&gui_data, true);
+            }
+        });
+    }
 }
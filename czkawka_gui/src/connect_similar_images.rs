@@ -0,0 +1,67 @@
+extern crate gtk;
+use crate::gui_data::GuiData;
+use czkawka_core::similar_images_cache::ImageHashCache;
+use gtk::prelude::*;
+
+pub fn connect_similar_images(gui_data: &GuiData) {
+    // Connect max distance check button, slider is used only when it is active
+    {
+        let scale_similar_images_max_distance = gui_data.scale_similar_images_max_distance.clone();
+        let check_button_similar_images_max_distance = gui_data.check_button_similar_images_max_distance.clone();
+        check_button_similar_images_max_distance.connect_toggled(move |check_button| {
+            scale_similar_images_max_distance.set_sensitive(check_button.get_active());
+        });
+    }
+    // Connect find similar to button, chosen images are used as reference images of new search
+    {
+        let window_main = gui_data.window_main.clone();
+        let buttons_search = gui_data.buttons_search.clone();
+        let shared_similar_images_reference_images = gui_data.shared_similar_images_reference_images.clone();
+        let button_similar_images_find_similar_to = gui_data.button_similar_images_find_similar_to.clone();
+        button_similar_images_find_similar_to.connect_clicked(move |_| {
+            let chooser = gtk::FileChooserDialog::with_buttons(
+                Some("Images to find similar to"),
+                Some(&window_main),
+                gtk::FileChooserAction::Open,
+                &[("Ok", gtk::ResponseType::Ok), ("Close", gtk::ResponseType::Cancel)],
+            );
+            chooser.set_select_multiple(true);
+            let filter = gtk::FileFilter::new();
+            filter.set_name(Some("Images"));
+            filter.add_pixbuf_formats();
+            chooser.add_filter(&filter);
+            chooser.show_all();
+            let response_type = chooser.run();
+            if response_type == gtk::ResponseType::Ok {
+                let reference_images = chooser.get_filenames();
+                if !reference_images.is_empty() {
+                    *shared_similar_images_reference_images.borrow_mut() = reference_images;
+                    buttons_search.clicked();
+                }
+            }
+            chooser.close();
+        });
+    }
+    // Connect prune cache button
+    {
+        let entry_info = gui_data.entry_info.clone();
+        let button_similar_images_prune_cache = gui_data.button_similar_images_prune_cache.clone();
+        button_similar_images_prune_cache.connect_clicked(move |_| {
+            match ImageHashCache::prune_all() {
+                Ok(removed) => entry_info.set_text(format!("Removed {} entries of missing files from cache of image hashes", removed).as_str()),
+                Err(e) => entry_info.set_text(format!("Failed to prune cache of image hashes, {}", e).as_str()),
+            };
+        });
+    }
+    // Connect clear cache button
+    {
+        let entry_info = gui_data.entry_info.clone();
+        let button_similar_images_clear_cache = gui_data.button_similar_images_clear_cache.clone();
+        button_similar_images_clear_cache.connect_clicked(move |_| {
+            match ImageHashCache::clear_all() {
+                Ok(removed) => entry_info.set_text(format!("Removed {} files with cache of image hashes", removed).as_str()),
+                Err(e) => entry_info.set_text(format!("Failed to clear cache of image hashes, {}", e).as_str()),
+            };
+        });
+    }
+}
@@ -1,10 +1,12 @@
 extern crate gtk;
-use crossbeam_channel::unbounded;
 use czkawka_core::big_file::BigFile;
+use czkawka_core::common_scan_controller::ScanController;
 use czkawka_core::duplicate::DuplicateFinder;
 use czkawka_core::empty_files::EmptyFiles;
 use czkawka_core::empty_folder::EmptyFolder;
+#[cfg(feature = "same-music")]
 use czkawka_core::same_music::SameMusic;
+#[cfg(feature = "similar-images")]
 use czkawka_core::similar_images::SimilarImages;
 use czkawka_core::temporary::Temporary;
 use czkawka_core::zeroed::ZeroedFiles;
@@ -12,6 +14,8 @@ use gtk::prelude::*;
 use gtk::{Builder, Button};
 use std::cell::RefCell;
 use std::collections::HashMap;
+#[cfg(feature = "similar-images")]
+use std::path::PathBuf;
 use std::rc::Rc;
 
 #[derive(Clone)]
@@ -25,7 +29,7 @@ pub struct GuiData {
 
     // States
     pub main_notebooks_labels: [String; 8],
-    pub upper_notebooks_labels: [String; 5],
+    pub upper_notebooks_labels: [String; 7],
     pub buttons_labels: [String; 4],
     // Buttons state
     pub shared_buttons: Rc<RefCell<HashMap<String, HashMap<String, bool>>>>,
@@ -39,17 +43,26 @@ pub struct GuiData {
     pub shared_empty_files_state: Rc<RefCell<EmptyFiles>>,
     pub shared_temporary_files_state: Rc<RefCell<Temporary>>,
     pub shared_big_files_state: Rc<RefCell<BigFile>>,
+    #[cfg(feature = "similar-images")]
     pub shared_similar_images_state: Rc<RefCell<SimilarImages>>,
+    /// Reference images chosen by "Find similar to", used only by search started by it
+    #[cfg(feature = "similar-images")]
+    pub shared_similar_images_reference_images: Rc<RefCell<Vec<PathBuf>>>,
     pub shared_zeroed_files_state: Rc<RefCell<ZeroedFiles>>,
+    #[cfg(feature = "same-music")]
     pub shared_same_music_state: Rc<RefCell<SameMusic>>,
 
     //// GUI Entry
     pub entry_similar_images_minimal_size: gtk::Entry,
     pub entry_duplicate_minimal_size: gtk::Entry,
     pub entry_allowed_extensions: gtk::Entry,
+    pub entry_excluded_extensions: gtk::Entry,
+    pub entry_extension_groups: gtk::Entry,
     pub entry_excluded_items: gtk::Entry,
     pub entry_big_files_number: gtk::Entry,
     pub entry_same_music_minimal_size: gtk::Entry,
+    pub entry_modified_after: gtk::Entry,
+    pub entry_modified_before: gtk::Entry,
 
     //// GUI Buttons
     pub buttons_search: gtk::Button,
@@ -63,6 +76,8 @@ pub struct GuiData {
     pub buttons_remove_included_directory: gtk::Button,
     pub buttons_add_excluded_directory: gtk::Button,
     pub buttons_remove_excluded_directory: gtk::Button,
+    pub buttons_modified_after_calendar: gtk::Button,
+    pub buttons_modified_before_calendar: gtk::Button,
 
     // Buttons search popover buttons
     pub buttons_popover_duplicate_select_all: gtk::Button,
@@ -72,6 +87,7 @@ pub struct GuiData {
     pub buttons_popover_duplicate_select_all_except_newest: gtk::Button,
     pub buttons_popover_duplicate_select_one_oldest: gtk::Button,
     pub buttons_popover_duplicate_select_one_newest: gtk::Button,
+    pub buttons_popover_duplicate_select_all_except_best_quality: gtk::Button,
     pub buttons_popover_duplicate_select_custom: gtk::Button,
     pub buttons_popover_duplicate_unselect_custom: gtk::Button,
 
@@ -84,15 +100,29 @@ pub struct GuiData {
     //// Popovers
     pub popover_select_duplicate: gtk::Popover,
     pub popover_select_simple_list: gtk::Popover,
+    pub popover_modified_after: gtk::Popover,
+    pub popover_modified_before: gtk::Popover,
+
+    //// Calendars
+    pub calendar_modified_after: gtk::Calendar,
+    pub calendar_modified_before: gtk::Calendar,
 
     //// Check Buttons
     pub check_button_recursive: gtk::CheckButton,
+    pub entry_max_depth: gtk::Entry,
+    pub check_button_use_ignore_files: gtk::CheckButton,
 
     pub check_button_music_title: gtk::CheckButton,
     pub check_button_music_artist: gtk::CheckButton,
     pub check_button_music_album_title: gtk::CheckButton,
     pub check_button_music_album_artist: gtk::CheckButton,
     pub check_button_music_year: gtk::CheckButton,
+    pub check_button_content_type_image: gtk::CheckButton,
+    pub check_button_content_type_audio: gtk::CheckButton,
+    pub check_button_content_type_video: gtk::CheckButton,
+    pub check_button_content_type_archive: gtk::CheckButton,
+    pub check_button_content_type_document: gtk::CheckButton,
+    pub check_button_detect_by_content: gtk::CheckButton,
 
     //// Radio Buttons
     // Duplicates
@@ -101,11 +131,40 @@ pub struct GuiData {
     pub radio_button_duplicates_hashmb: gtk::RadioButton,
     pub radio_button_duplicates_hash: gtk::RadioButton,
 
+    #[cfg(feature = "similar-images")]
     pub radio_button_similar_images_very_small: gtk::RadioButton,
+    #[cfg(feature = "similar-images")]
     pub radio_button_similar_images_small: gtk::RadioButton,
+    #[cfg(feature = "similar-images")]
     pub radio_button_similar_images_medium: gtk::RadioButton,
+    #[cfg(feature = "similar-images")]
     pub radio_button_similar_images_high: gtk::RadioButton,
+    #[cfg(feature = "similar-images")]
     pub radio_button_similar_images_very_high: gtk::RadioButton,
+    #[cfg(feature = "similar-images")]
+    pub combo_box_text_similar_images_hash_alg: gtk::ComboBoxText,
+    #[cfg(feature = "similar-images")]
+    pub combo_box_text_similar_images_hash_size: gtk::ComboBoxText,
+    #[cfg(feature = "similar-images")]
+    pub check_button_similar_images_use_dct: gtk::CheckButton,
+    #[cfg(feature = "similar-images")]
+    pub check_button_similar_images_max_distance: gtk::CheckButton,
+    #[cfg(feature = "similar-images")]
+    pub scale_similar_images_max_distance: gtk::Scale,
+    #[cfg(feature = "similar-images")]
+    pub check_button_similar_images_use_cache: gtk::CheckButton,
+    #[cfg(feature = "similar-images")]
+    pub check_button_similar_images_check_rotations: gtk::CheckButton,
+    #[cfg(feature = "similar-images")]
+    pub entry_similar_images_additional_extensions: gtk::Entry,
+    #[cfg(feature = "similar-images")]
+    pub entry_similar_images_excluded_extensions: gtk::Entry,
+    #[cfg(feature = "similar-images")]
+    pub button_similar_images_find_similar_to: gtk::Button,
+    #[cfg(feature = "similar-images")]
+    pub button_similar_images_prune_cache: gtk::Button,
+    #[cfg(feature = "similar-images")]
+    pub button_similar_images_clear_cache: gtk::Button,
 
     //// Notebooks
     pub notebook_main: gtk::Notebook,
@@ -147,6 +206,8 @@ pub struct GuiData {
     pub grid_progress_stages: gtk::Grid,
 
     pub button_stop_in_dialog: gtk::Button,
+    pub button_pause_in_dialog: gtk::Button,
+    pub label_pause_in_dialog: gtk::Label,
 
     //// Similar Images
     pub image_preview_similar_images: gtk::Image,
@@ -155,15 +216,25 @@ pub struct GuiData {
     pub check_button_settings_save_at_exit: gtk::CheckButton,
     pub check_button_settings_load_at_start: gtk::CheckButton,
     pub check_button_settings_confirm_deletion: gtk::CheckButton,
+    pub check_button_settings_low_priority: gtk::CheckButton,
+    pub entry_settings_io_limit: gtk::Entry,
+    pub entry_settings_io_threads: gtk::Entry,
+    pub entry_settings_cpu_threads: gtk::Entry,
+    pub combo_box_text_settings_profile: gtk::ComboBoxText,
 
     pub button_settings_save_configuration: gtk::Button,
     pub button_settings_load_configuration: gtk::Button,
     pub button_settings_reset_configuration: gtk::Button,
+    pub button_settings_remove_profile: gtk::Button,
+
+    //// Debug log
+    pub text_view_debug_log: gtk::TextView,
+    pub button_clear_debug_log: gtk::Button,
+
     //// Threads
 
-    // Used for sending stop signal to thread
-    pub stop_sender: crossbeam_channel::Sender<()>,
-    pub stop_receiver: crossbeam_channel::Receiver<()>,
+    // Used for stopping and pausing scan running in other thread
+    pub scan_controller: ScanController,
 }
 
 impl GuiData {
@@ -194,7 +265,9 @@ impl GuiData {
             "excluded_directories".to_string(),
             "excluded_items".to_string(),
             "allowed_extensions".to_string(),
+            "modification_date".to_string(),
             "settings".to_string(),
+            "debug_log".to_string(),
         ];
         let buttons_labels = ["search".to_string(), "select".to_string(), "delete".to_string(), "save".to_string()];
 
@@ -227,8 +300,7 @@ impl GuiData {
             shared_upper_notebooks.borrow_mut().insert(i.to_string(), temp_hashmap);
         }
         // Some upper notebook tabs are disabled
-        *shared_upper_notebooks.borrow_mut().get_mut("empty_file").unwrap().get_mut("allowed_extensions").unwrap() = false;
-        *shared_upper_notebooks.borrow_mut().get_mut("temporary_file").unwrap().get_mut("allowed_extensions").unwrap() = false;
+        *shared_upper_notebooks.borrow_mut().get_mut("empty_folder").unwrap().get_mut("allowed_extensions").unwrap() = false;
 
         // State of search results
 
@@ -237,8 +309,12 @@ impl GuiData {
         let shared_empty_files_state: Rc<RefCell<_>> = Rc::new(RefCell::new(EmptyFiles::new()));
         let shared_temporary_files_state: Rc<RefCell<_>> = Rc::new(RefCell::new(Temporary::new()));
         let shared_big_files_state: Rc<RefCell<_>> = Rc::new(RefCell::new(BigFile::new()));
+        #[cfg(feature = "similar-images")]
         let shared_similar_images_state: Rc<RefCell<_>> = Rc::new(RefCell::new(SimilarImages::new()));
+        #[cfg(feature = "similar-images")]
+        let shared_similar_images_reference_images: Rc<RefCell<_>> = Rc::new(RefCell::new(Vec::new()));
         let shared_zeroed_files_state: Rc<RefCell<_>> = Rc::new(RefCell::new(ZeroedFiles::new()));
+        #[cfg(feature = "same-music")]
         let shared_same_music_state: Rc<RefCell<_>> = Rc::new(RefCell::new(SameMusic::new()));
 
         ////////////////////////////////////////////////////////////////////////////////////////////////
@@ -247,9 +323,13 @@ impl GuiData {
         let entry_similar_images_minimal_size: gtk::Entry = builder.get_object("entry_similar_images_minimal_size").unwrap();
         let entry_duplicate_minimal_size: gtk::Entry = builder.get_object("entry_duplicate_minimal_size").unwrap();
         let entry_allowed_extensions: gtk::Entry = builder.get_object("entry_allowed_extensions").unwrap();
+        let entry_excluded_extensions: gtk::Entry = builder.get_object("entry_excluded_extensions").unwrap();
+        let entry_extension_groups: gtk::Entry = builder.get_object("entry_extension_groups").unwrap();
         let entry_excluded_items: gtk::Entry = builder.get_object("entry_excluded_items").unwrap();
         let entry_big_files_number: gtk::Entry = builder.get_object("entry_big_files_number").unwrap();
         let entry_same_music_minimal_size: gtk::Entry = builder.get_object("entry_same_music_minimal_size").unwrap();
+        let entry_modified_after: gtk::Entry = builder.get_object("entry_modified_after").unwrap();
+        let entry_modified_before: gtk::Entry = builder.get_object("entry_modified_before").unwrap();
 
         //// GUI Buttons
         let buttons_search: gtk::Button = builder.get_object("buttons_search").unwrap();
@@ -266,6 +346,8 @@ impl GuiData {
         let buttons_remove_included_directory: gtk::Button = builder.get_object("buttons_remove_included_directory").unwrap();
         let buttons_add_excluded_directory: gtk::Button = builder.get_object("buttons_add_excluded_directory").unwrap();
         let buttons_remove_excluded_directory: gtk::Button = builder.get_object("buttons_remove_excluded_directory").unwrap();
+        let buttons_modified_after_calendar: gtk::Button = builder.get_object("buttons_modified_after_calendar").unwrap();
+        let buttons_modified_before_calendar: gtk::Button = builder.get_object("buttons_modified_before_calendar").unwrap();
 
         // Buttons search popover buttons
         let buttons_popover_duplicate_select_all: gtk::Button = builder.get_object("buttons_popover_duplicate_select_all").unwrap();
@@ -275,6 +357,7 @@ impl GuiData {
         let buttons_popover_duplicate_select_all_except_newest: gtk::Button = builder.get_object("buttons_popover_duplicate_select_all_except_newest").unwrap();
         let buttons_popover_duplicate_select_one_oldest: gtk::Button = builder.get_object("buttons_popover_duplicate_select_one_oldest").unwrap();
         let buttons_popover_duplicate_select_one_newest: gtk::Button = builder.get_object("buttons_popover_duplicate_select_one_newest").unwrap();
+        let buttons_popover_duplicate_select_all_except_best_quality: gtk::Button = builder.get_object("buttons_popover_duplicate_select_all_except_best_quality").unwrap();
         let buttons_popover_duplicate_select_custom: gtk::Button = builder.get_object("buttons_popover_duplicate_select_custom").unwrap();
         let buttons_popover_duplicate_unselect_custom: gtk::Button = builder.get_object("buttons_popover_duplicate_unselect_custom").unwrap();
 
@@ -287,14 +370,28 @@ impl GuiData {
         //// Popovers
         let popover_select_duplicate: gtk::Popover = builder.get_object("popover_select_duplicate").unwrap();
         let popover_select_simple_list: gtk::Popover = builder.get_object("popover_select_simple_list").unwrap();
+        let popover_modified_after: gtk::Popover = builder.get_object("popover_modified_after").unwrap();
+        let popover_modified_before: gtk::Popover = builder.get_object("popover_modified_before").unwrap();
+
+        //// Calendars
+        let calendar_modified_after: gtk::Calendar = builder.get_object("calendar_modified_after").unwrap();
+        let calendar_modified_before: gtk::Calendar = builder.get_object("calendar_modified_before").unwrap();
 
         //// Check Buttons
         let check_button_recursive: gtk::CheckButton = builder.get_object("check_button_recursive").unwrap();
+        let entry_max_depth: gtk::Entry = builder.get_object("entry_max_depth").unwrap();
+        let check_button_use_ignore_files: gtk::CheckButton = builder.get_object("check_button_use_ignore_files").unwrap();
         let check_button_music_title: gtk::CheckButton = builder.get_object("check_button_music_title").unwrap();
         let check_button_music_artist: gtk::CheckButton = builder.get_object("check_button_music_artist").unwrap();
         let check_button_music_album_title: gtk::CheckButton = builder.get_object("check_button_music_album_title").unwrap();
         let check_button_music_album_artist: gtk::CheckButton = builder.get_object("check_button_music_album_artist").unwrap();
         let check_button_music_year: gtk::CheckButton = builder.get_object("check_button_music_year").unwrap();
+        let check_button_content_type_image: gtk::CheckButton = builder.get_object("check_button_content_type_image").unwrap();
+        let check_button_content_type_audio: gtk::CheckButton = builder.get_object("check_button_content_type_audio").unwrap();
+        let check_button_content_type_video: gtk::CheckButton = builder.get_object("check_button_content_type_video").unwrap();
+        let check_button_content_type_archive: gtk::CheckButton = builder.get_object("check_button_content_type_archive").unwrap();
+        let check_button_content_type_document: gtk::CheckButton = builder.get_object("check_button_content_type_document").unwrap();
+        let check_button_detect_by_content: gtk::CheckButton = builder.get_object("check_button_detect_by_content").unwrap();
 
         //// Radio Buttons
         let radio_button_duplicates_name: gtk::RadioButton = builder.get_object("radio_button_duplicates_name").unwrap();
@@ -302,11 +399,40 @@ impl GuiData {
         let radio_button_duplicates_hashmb: gtk::RadioButton = builder.get_object("radio_button_duplicates_hashmb").unwrap();
         let radio_button_duplicates_hash: gtk::RadioButton = builder.get_object("radio_button_duplicates_hash").unwrap();
 
+        #[cfg(feature = "similar-images")]
         let radio_button_similar_images_very_small: gtk::RadioButton = builder.get_object("radio_button_similar_images_very_small").unwrap();
+        #[cfg(feature = "similar-images")]
         let radio_button_similar_images_small: gtk::RadioButton = builder.get_object("radio_button_similar_images_small").unwrap();
+        #[cfg(feature = "similar-images")]
         let radio_button_similar_images_medium: gtk::RadioButton = builder.get_object("radio_button_similar_images_medium").unwrap();
+        #[cfg(feature = "similar-images")]
         let radio_button_similar_images_high: gtk::RadioButton = builder.get_object("radio_button_similar_images_high").unwrap();
+        #[cfg(feature = "similar-images")]
         let radio_button_similar_images_very_high: gtk::RadioButton = builder.get_object("radio_button_similar_images_very_high").unwrap();
+        #[cfg(feature = "similar-images")]
+        let combo_box_text_similar_images_hash_alg: gtk::ComboBoxText = builder.get_object("combo_box_text_similar_images_hash_alg").unwrap();
+        #[cfg(feature = "similar-images")]
+        let combo_box_text_similar_images_hash_size: gtk::ComboBoxText = builder.get_object("combo_box_text_similar_images_hash_size").unwrap();
+        #[cfg(feature = "similar-images")]
+        let check_button_similar_images_use_dct: gtk::CheckButton = builder.get_object("check_button_similar_images_use_dct").unwrap();
+        #[cfg(feature = "similar-images")]
+        let check_button_similar_images_max_distance: gtk::CheckButton = builder.get_object("check_button_similar_images_max_distance").unwrap();
+        #[cfg(feature = "similar-images")]
+        let scale_similar_images_max_distance: gtk::Scale = builder.get_object("scale_similar_images_max_distance").unwrap();
+        #[cfg(feature = "similar-images")]
+        let check_button_similar_images_use_cache: gtk::CheckButton = builder.get_object("check_button_similar_images_use_cache").unwrap();
+        #[cfg(feature = "similar-images")]
+        let check_button_similar_images_check_rotations: gtk::CheckButton = builder.get_object("check_button_similar_images_check_rotations").unwrap();
+        #[cfg(feature = "similar-images")]
+        let entry_similar_images_additional_extensions: gtk::Entry = builder.get_object("entry_similar_images_additional_extensions").unwrap();
+        #[cfg(feature = "similar-images")]
+        let entry_similar_images_excluded_extensions: gtk::Entry = builder.get_object("entry_similar_images_excluded_extensions").unwrap();
+        #[cfg(feature = "similar-images")]
+        let button_similar_images_find_similar_to: gtk::Button = builder.get_object("button_similar_images_find_similar_to").unwrap();
+        #[cfg(feature = "similar-images")]
+        let button_similar_images_prune_cache: gtk::Button = builder.get_object("button_similar_images_prune_cache").unwrap();
+        #[cfg(feature = "similar-images")]
+        let button_similar_images_clear_cache: gtk::Button = builder.get_object("button_similar_images_clear_cache").unwrap();
 
         //// Notebooks
         let notebook_main: gtk::Notebook = builder.get_object("notebook_main").unwrap();
@@ -355,6 +481,8 @@ impl GuiData {
         let grid_progress_stages: gtk::Grid = builder.get_object("grid_progress_stages").unwrap();
 
         let button_stop_in_dialog: gtk::Button = builder.get_object("button_stop_in_dialog").unwrap();
+        let button_pause_in_dialog: gtk::Button = builder.get_object("button_pause_in_dialog").unwrap();
+        let label_pause_in_dialog: gtk::Label = builder.get_object("label_pause_in_dialog").unwrap();
 
         //// Similar Images
         let image_preview_similar_images: gtk::Image = builder.get_object("image_preview_similar_images").unwrap();
@@ -363,16 +491,26 @@ impl GuiData {
         let check_button_settings_save_at_exit: gtk::CheckButton = builder.get_object("check_button_settings_save_at_exit").unwrap();
         let check_button_settings_load_at_start: gtk::CheckButton = builder.get_object("check_button_settings_load_at_start").unwrap();
         let check_button_settings_confirm_deletion: gtk::CheckButton = builder.get_object("check_button_settings_confirm_deletion").unwrap();
+        let check_button_settings_low_priority: gtk::CheckButton = builder.get_object("check_button_settings_low_priority").unwrap();
+        let entry_settings_io_limit: gtk::Entry = builder.get_object("entry_settings_io_limit").unwrap();
+        let entry_settings_io_threads: gtk::Entry = builder.get_object("entry_settings_io_threads").unwrap();
+        let entry_settings_cpu_threads: gtk::Entry = builder.get_object("entry_settings_cpu_threads").unwrap();
+        let combo_box_text_settings_profile: gtk::ComboBoxText = builder.get_object("combo_box_text_settings_profile").unwrap();
 
         let button_settings_save_configuration: gtk::Button = builder.get_object("button_settings_save_configuration").unwrap();
         let button_settings_load_configuration: gtk::Button = builder.get_object("button_settings_load_configuration").unwrap();
         let button_settings_reset_configuration: gtk::Button = builder.get_object("button_settings_reset_configuration").unwrap();
+        let button_settings_remove_profile: gtk::Button = builder.get_object("button_settings_remove_profile").unwrap();
+
+        //// Debug log
+        let text_view_debug_log: gtk::TextView = builder.get_object("text_view_debug_log").unwrap();
+        let button_clear_debug_log: gtk::Button = builder.get_object("button_clear_debug_log").unwrap();
 
         //// Threads
         // Types of messages to send to main thread where gui can be draw.
 
-        // Used for sending stop signal to thread
-        let (stop_sender, stop_receiver): (crossbeam_channel::Sender<()>, crossbeam_channel::Receiver<()>) = unbounded();
+        // Used for stopping and pausing scan running in other thread
+        let scan_controller = ScanController::new();
 
         Self {
             glade_src,
@@ -388,15 +526,23 @@ impl GuiData {
             shared_empty_files_state,
             shared_temporary_files_state,
             shared_big_files_state,
+            #[cfg(feature = "similar-images")]
             shared_similar_images_state,
+            #[cfg(feature = "similar-images")]
+            shared_similar_images_reference_images,
             shared_zeroed_files_state,
+            #[cfg(feature = "same-music")]
             shared_same_music_state,
             entry_similar_images_minimal_size,
             entry_duplicate_minimal_size,
             entry_allowed_extensions,
+            entry_excluded_extensions,
+            entry_extension_groups,
             entry_excluded_items,
             entry_big_files_number,
             entry_same_music_minimal_size,
+            entry_modified_after,
+            entry_modified_before,
             buttons_search,
             buttons_select,
             buttons_delete,
@@ -408,6 +554,8 @@ impl GuiData {
             buttons_remove_included_directory,
             buttons_add_excluded_directory,
             buttons_remove_excluded_directory,
+            buttons_modified_after_calendar,
+            buttons_modified_before_calendar,
             buttons_popover_duplicate_select_all,
             buttons_popover_duplicate_unselect_all,
             buttons_popover_duplicate_reverse,
@@ -415,6 +563,7 @@ impl GuiData {
             buttons_popover_duplicate_select_all_except_newest,
             buttons_popover_duplicate_select_one_oldest,
             buttons_popover_duplicate_select_one_newest,
+            buttons_popover_duplicate_select_all_except_best_quality,
             buttons_popover_duplicate_select_custom,
             buttons_popover_duplicate_unselect_custom,
             buttons_popover_simple_list_select_all,
@@ -424,21 +573,62 @@ impl GuiData {
             buttons_popover_simple_list_unselect_custom,
             popover_select_duplicate,
             popover_select_simple_list,
+            popover_modified_after,
+            popover_modified_before,
+            calendar_modified_after,
+            calendar_modified_before,
             check_button_recursive,
+            entry_max_depth,
+            check_button_use_ignore_files,
             check_button_music_title,
             check_button_music_artist,
             check_button_music_album_title,
             check_button_music_album_artist,
             check_button_music_year,
+            check_button_content_type_image,
+            check_button_content_type_audio,
+            check_button_content_type_video,
+            check_button_content_type_archive,
+            check_button_content_type_document,
+            check_button_detect_by_content,
             radio_button_duplicates_name,
             radio_button_duplicates_size,
             radio_button_duplicates_hashmb,
             radio_button_duplicates_hash,
+            #[cfg(feature = "similar-images")]
             radio_button_similar_images_very_small,
+            #[cfg(feature = "similar-images")]
             radio_button_similar_images_small,
+            #[cfg(feature = "similar-images")]
             radio_button_similar_images_medium,
+            #[cfg(feature = "similar-images")]
             radio_button_similar_images_high,
+            #[cfg(feature = "similar-images")]
             radio_button_similar_images_very_high,
+            #[cfg(feature = "similar-images")]
+            combo_box_text_similar_images_hash_alg,
+            #[cfg(feature = "similar-images")]
+            combo_box_text_similar_images_hash_size,
+            #[cfg(feature = "similar-images")]
+            check_button_similar_images_use_dct,
+            #[cfg(feature = "similar-images")]
+            check_button_similar_images_max_distance,
+            #[cfg(feature = "similar-images")]
+            scale_similar_images_max_distance,
+            #[cfg(feature = "similar-images")]
+            check_button_similar_images_use_cache,
+            #[cfg(feature = "similar-images")]
+            check_button_similar_images_check_rotations,
+            #[cfg(feature = "similar-images")]
+            entry_similar_images_additional_extensions,
+            #[cfg(feature = "similar-images")]
+            entry_similar_images_excluded_extensions,
+            #[cfg(feature = "similar-images")]
+            button_similar_images_find_similar_to,
+            #[cfg(feature = "similar-images")]
+            button_similar_images_prune_cache,
+            #[cfg(feature = "similar-images")]
+            button_similar_images_clear_cache,
             notebook_main,
             notebook_upper,
             notebook_main_children_names,
@@ -462,15 +652,24 @@ impl GuiData {
             label_stage,
             grid_progress_stages,
             button_stop_in_dialog,
+            button_pause_in_dialog,
+            label_pause_in_dialog,
             image_preview_similar_images,
             check_button_settings_save_at_exit,
             check_button_settings_load_at_start,
             check_button_settings_confirm_deletion,
+            check_button_settings_low_priority,
+            entry_settings_io_limit,
+            entry_settings_io_threads,
+            entry_settings_cpu_threads,
+            combo_box_text_settings_profile,
             button_settings_save_configuration,
             button_settings_load_configuration,
             button_settings_reset_configuration,
-            stop_sender,
-            stop_receiver,
+            button_settings_remove_profile,
+            text_view_debug_log,
+            button_clear_debug_log,
+            scan_controller,
         }
     }
 }
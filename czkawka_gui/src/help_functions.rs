@@ -1,26 +1,89 @@
-use czkawka_core::big_file::BigFile;
+use crate::gui_data::GuiData;
+use chrono::NaiveDateTime;
 use czkawka_core::common_messages::Messages;
-use czkawka_core::duplicate::DuplicateFinder;
-use czkawka_core::empty_files::EmptyFiles;
-use czkawka_core::empty_folder::EmptyFolder;
-use czkawka_core::same_music::SameMusic;
-use czkawka_core::similar_images::{SimilarImages, Similarity};
-use czkawka_core::temporary::Temporary;
-use czkawka_core::zeroed::ZeroedFiles;
+use czkawka_core::common_progress::BytesProgress;
+use czkawka_core::common_tool::Tool;
+#[cfg(feature = "similar-images")]
+use czkawka_core::similar_images::{HashAlgorithm, Similarity};
 use gtk::prelude::*;
 use gtk::{ListStore, TreeView};
 use std::collections::HashMap;
 use std::path::Path;
 
-pub enum Message {
-    Duplicates(DuplicateFinder),
-    EmptyFolders(EmptyFolder),
-    EmptyFiles(EmptyFiles),
-    BigFiles(BigFile),
-    Temporary(Temporary),
-    SimilarImages(SimilarImages),
-    ZeroedFiles(ZeroedFiles),
-    SameMusic(SameMusic),
+/// Results of search made by one of tools, sent to main thread to be shown in tab of tool
+pub type Message = Box<dyn ToolResults>;
+
+/// Shows results of tool in GUI, must be implemented by every tool which sends them as `Message`
+pub trait ShowResults: Tool {
+    /// Name of tab in `shared_buttons`
+    const TAB: &'static str;
+
+    /// Text shown in info entry after search
+    fn get_summary(&self) -> String;
+    fn get_scrolled_window(gui_data: &GuiData) -> gtk::ScrolledWindow;
+    /// Adds found entries to tab, by default as rows with name, path and modification date
+    fn add_rows(&self, list_store: &ListStore) {
+        let col_indices = [0, 1, 2];
+
+        for entry in self.get_result_groups().into_iter().flat_map(|e| e.entries) {
+            let (directory, file) = split_path(&entry.path);
+            let values: [&dyn ToValue; 3] = [&file, &directory, &(NaiveDateTime::from_timestamp(entry.modified_date as i64, 0).to_string())];
+            list_store.set(&list_store.append(), &col_indices, &values);
+        }
+    }
+    /// Replaces results of previous search, which are used later e.g. to delete or save them
+    fn save_state(self, gui_data: &GuiData);
+}
+
+/// Object safe view of `ShowResults`, because `Tool` has associated type
+pub trait ToolResults: Send {
+    fn get_tab(&self) -> &'static str;
+    fn get_name(&self) -> &'static str;
+    fn get_stopped_search(&self) -> bool;
+    fn get_text_messages(&self) -> &Messages;
+    fn get_summary(&self) -> String;
+    fn has_results(&self) -> bool;
+    fn get_scrolled_window(&self, gui_data: &GuiData) -> gtk::ScrolledWindow;
+    fn add_rows(&self, list_store: &ListStore);
+    fn save_state(self: Box<Self>, gui_data: &GuiData);
+}
+
+/// Wrapper over tool, so methods of `ToolResults` don't collide with methods of `Tool`
+struct Results<T>(T);
+
+impl<T: ShowResults + Send> ToolResults for Results<T> {
+    fn get_tab(&self) -> &'static str {
+        T::TAB
+    }
+    fn get_name(&self) -> &'static str {
+        self.0.get_name()
+    }
+    fn get_stopped_search(&self) -> bool {
+        self.0.get_stopped_search()
+    }
+    fn get_text_messages(&self) -> &Messages {
+        self.0.get_text_messages()
+    }
+    fn get_summary(&self) -> String {
+        self.0.get_summary()
+    }
+    fn has_results(&self) -> bool {
+        !self.0.get_result_groups().is_empty()
+    }
+    fn get_scrolled_window(&self, gui_data: &GuiData) -> gtk::ScrolledWindow {
+        T::get_scrolled_window(gui_data)
+    }
+    fn add_rows(&self, list_store: &ListStore) {
+        self.0.add_rows(list_store)
+    }
+    fn save_state(self: Box<Self>, gui_data: &GuiData) {
+        self.0.save_state(gui_data)
+    }
+}
+
+/// Creates message with results of finished search
+pub fn create_message<T: ShowResults + Send + 'static>(tool: T) -> Message {
+    Box::new(Results(tool))
 }
 
 pub enum ColumnsDuplicates {
@@ -69,6 +132,8 @@ pub enum ColumnsSimilarImages {
     ModificationAsSecs,
     Color,
     TextColor,
+    #[cfg(feature = "similar-images")]
+    SizeAsBytes,
 }
 pub enum ColumnsZeroedFiles {
     Size = 0,
@@ -142,7 +207,7 @@ pub fn print_text_messages_to_text_view(text_messages: &Messages, text_view: &gt
         messages += "############### WARNINGS ###############\n";
     }
     for text in &text_messages.warnings {
-        messages += text.as_str();
+        messages += text.to_string().as_str();
         messages += "\n";
     }
     if !text_messages.warnings.is_empty() {
@@ -152,16 +217,31 @@ pub fn print_text_messages_to_text_view(text_messages: &Messages, text_view: &gt
         messages += "############### ERRORS ###############\n";
     }
     for text in &text_messages.errors {
-        messages += text.as_str();
+        messages += text.to_string().as_str();
         messages += "\n";
     }
     if !text_messages.errors.is_empty() {
         messages += "\n";
     }
+    let counts = text_messages.count_by_kind();
+    if !counts.is_empty() {
+        messages += "############### SUMMARY ###############\n";
+    }
+    for (kind, count) in &counts {
+        messages += format!("{} - {}\n", kind, count).as_str();
+    }
 
     text_view.get_buffer().unwrap().set_text(messages.as_str());
 }
 
+/// Size, speed and estimated time left appended to stage label, empty when stage doesn't read content of files
+pub fn get_bytes_progress_text(bytes_progress: &BytesProgress) -> String {
+    if bytes_progress.bytes_to_check == 0 {
+        return "".to_string();
+    }
+    format!(" - {}", bytes_progress)
+}
+
 pub fn select_function_duplicates(_tree_selection: &gtk::TreeSelection, tree_model: &gtk::TreeModel, tree_path: &gtk::TreePath, _is_path_currently_selected: bool) -> bool {
     // let name = tree_model.get_value(&tree_model.get_iter(tree_path).unwrap(),ColumnsDuplicates::Name as i32).get::<String>().unwrap().unwrap();
     // let path = tree_model.get_value(&tree_model.get_iter(tree_path).unwrap(), ColumnsDuplicates::Path as i32).get::<String>().unwrap().unwrap();
@@ -218,6 +298,7 @@ pub fn hide_all_buttons(buttons_array: &[gtk::Button]) {
 //     }
 // }
 
+#[cfg(feature = "similar-images")]
 pub fn get_text_from_similarity(similarity: &Similarity) -> &str {
     match similarity {
         Similarity::None => "Original",
@@ -229,6 +310,27 @@ pub fn get_text_from_similarity(similarity: &Similarity) -> &str {
     }
 }
 
+/// Ids of hash algorithms used in combo box of similar images tab
+#[cfg(feature = "similar-images")]
+pub fn get_id_from_hash_algorithm(hash_algorithm: HashAlgorithm) -> &'static str {
+    match hash_algorithm {
+        HashAlgorithm::Mean => "mean",
+        HashAlgorithm::Gradient => "gradient",
+        HashAlgorithm::DoubleGradient => "double_gradient",
+        HashAlgorithm::Blockhash => "blockhash",
+    }
+}
+
+#[cfg(feature = "similar-images")]
+pub fn get_hash_algorithm_from_id(id: &str) -> HashAlgorithm {
+    match id {
+        "mean" => HashAlgorithm::Mean,
+        "double_gradient" => HashAlgorithm::DoubleGradient,
+        "blockhash" => HashAlgorithm::Blockhash,
+        _ => HashAlgorithm::Gradient,
+    }
+}
+
 pub fn get_list_store(scrolled_window: &gtk::ScrolledWindow) -> ListStore {
     let list_store = scrolled_window.get_children().get(0).unwrap().clone().downcast::<gtk::TreeView>().unwrap().get_model().unwrap().downcast::<gtk::ListStore>().unwrap();
 
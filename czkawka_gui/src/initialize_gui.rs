@@ -2,13 +2,19 @@ use crate::create_tree_view::*;
 use crate::double_click_opening::*;
 use crate::gui_data::*;
 use crate::help_functions::*;
+#[cfg(feature = "similar-images")]
 use directories_next::ProjectDirs;
 use gtk::prelude::*;
 use gtk::{SelectionMode, TreeView};
+#[cfg(feature = "similar-images")]
 use image::imageops::FilterType;
+#[cfg(feature = "similar-images")]
 use image::GenericImageView;
+#[cfg(feature = "similar-images")]
 use std::cmp::Ordering;
+#[cfg(feature = "similar-images")]
 use std::fs;
+#[cfg(feature = "similar-images")]
 use std::path::Path;
 
 pub fn initialize_gui(gui_data: &GuiData) {
@@ -130,7 +136,7 @@ pub fn initialize_gui(gui_data: &GuiData) {
             {
                 image_preview_similar_images.hide();
 
-                let col_types: [glib::types::Type; 9] = [
+                let col_types: [glib::types::Type; 10] = [
                     glib::types::Type::String,
                     glib::types::Type::String,
                     glib::types::Type::String,
@@ -140,6 +146,7 @@ pub fn initialize_gui(gui_data: &GuiData) {
                     glib::types::Type::U64,
                     glib::types::Type::String,
                     glib::types::Type::String,
+                    glib::types::Type::U64,
                 ];
                 let list_store: gtk::ListStore = gtk::ListStore::new(&col_types);
 
@@ -151,6 +158,8 @@ pub fn initialize_gui(gui_data: &GuiData) {
                 create_tree_view_similar_images(&mut tree_view);
 
                 tree_view.connect_button_press_event(opening_double_click_function_similar_images);
+                // Preview needs image decoders, which are available only together with tool
+                #[cfg(feature = "similar-images")]
                 tree_view.connect_button_release_event(move |tree_view, event| {
                     if event.get_event_type() == gdk::EventType::DoubleButtonPress {
                         common_open_function(tree_view, ColumnsSimilarImages::Name as i32, ColumnsSimilarImages::Path as i32);
@@ -305,4 +314,18 @@ pub fn initialize_gui(gui_data: &GuiData) {
             scrolled_window_excluded_directories.show_all();
         }
     }
+
+    //// Hide tabs of tools disabled at compile time
+    {
+        let notebook_main = gui_data.notebook_main.clone();
+        let tools_enabled = [("notebook_main_similar_images_finder_label", cfg!(feature = "similar-images")), ("notebook_main_same_music_finder", cfg!(feature = "same-music"))];
+        for (tab_name, enabled) in tools_enabled.iter() {
+            if *enabled {
+                continue;
+            }
+            if let Some(index) = gui_data.notebook_main_children_names.iter().position(|e| e == tab_name) {
+                notebook_main.get_nth_page(Some(index as u32)).unwrap().hide();
+            }
+        }
+    }
 }
@@ -7,12 +7,16 @@ mod connect_button_search;
 mod connect_button_select;
 mod connect_button_stop;
 mod connect_compute_results;
+mod connect_date_filter;
+mod connect_debug_log;
 mod connect_hide_text_view_errors;
 mod connect_notebook_tabs;
 mod connect_popovers;
 mod connect_progress_window;
 mod connect_selection_of_directories;
 mod connect_settings;
+#[cfg(feature = "similar-images")]
+mod connect_similar_images;
 mod create_tree_view;
 mod double_click_opening;
 mod gui_data;
@@ -29,12 +33,16 @@ use crate::connect_button_search::*;
 use crate::connect_button_select::*;
 use crate::connect_button_stop::*;
 use crate::connect_compute_results::*;
+use crate::connect_date_filter::*;
+use crate::connect_debug_log::*;
 use crate::connect_hide_text_view_errors::*;
 use crate::connect_notebook_tabs::*;
 use crate::connect_popovers::*;
 use crate::connect_progress_window::*;
 use crate::connect_selection_of_directories::*;
 use crate::connect_settings::*;
+#[cfg(feature = "similar-images")]
+use crate::connect_similar_images::*;
 use crate::gui_data::*;
 use crate::initialize_gui::*;
 use crate::saving_loading::*;
@@ -62,6 +70,9 @@ fn main() {
 
     let gui_data: GuiData = GuiData::new();
 
+    // Logger should be set before anything is logged, e.g. while loading configuration
+    connect_debug_log(&gui_data);
+
     // Used for getting data from thread
     let (glib_stop_sender, glib_stop_receiver) = glib::MainContext::channel(glib::PRIORITY_DEFAULT);
 
@@ -70,7 +81,9 @@ fn main() {
     let (futures_sender_empty_files, futures_receiver_empty_files): (futures::channel::mpsc::Sender<empty_files::ProgressData>, futures::channel::mpsc::Receiver<empty_files::ProgressData>) = futures::channel::mpsc::channel(20);
     let (futures_sender_empty_folder, futures_receiver_empty_folder): (futures::channel::mpsc::Sender<empty_folder::ProgressData>, futures::channel::mpsc::Receiver<empty_folder::ProgressData>) = futures::channel::mpsc::channel(20);
     let (futures_sender_big_file, futures_receiver_big_file): (futures::channel::mpsc::Sender<big_file::ProgressData>, futures::channel::mpsc::Receiver<big_file::ProgressData>) = futures::channel::mpsc::channel(20);
+    #[cfg(feature = "same-music")]
     let (futures_sender_same_music, futures_receiver_same_music): (futures::channel::mpsc::Sender<same_music::ProgressData>, futures::channel::mpsc::Receiver<same_music::ProgressData>) = futures::channel::mpsc::channel(20);
+    #[cfg(feature = "similar-images")]
     let (futures_sender_similar_images, futures_receiver_similar_images): (futures::channel::mpsc::Sender<similar_images::ProgressData>, futures::channel::mpsc::Receiver<similar_images::ProgressData>) = futures::channel::mpsc::channel(20);
     let (futures_sender_temporary, futures_receiver_temporary): (futures::channel::mpsc::Sender<temporary::ProgressData>, futures::channel::mpsc::Receiver<temporary::ProgressData>) = futures::channel::mpsc::channel(20);
     let (futures_sender_zeroed, futures_receiver_zeroed): (futures::channel::mpsc::Sender<zeroed::ProgressData>, futures::channel::mpsc::Receiver<zeroed::ProgressData>) = futures::channel::mpsc::channel(20);
@@ -88,7 +101,9 @@ fn main() {
         futures_sender_empty_files,
         futures_sender_empty_folder,
         futures_sender_big_file,
+        #[cfg(feature = "same-music")]
         futures_sender_same_music,
+        #[cfg(feature = "similar-images")]
         futures_sender_similar_images,
         futures_sender_temporary,
         futures_sender_zeroed,
@@ -105,13 +120,18 @@ fn main() {
         futures_receiver_empty_files,
         futures_receiver_empty_folder,
         futures_receiver_big_file,
+        #[cfg(feature = "same-music")]
         futures_receiver_same_music,
+        #[cfg(feature = "similar-images")]
         futures_receiver_similar_images,
         futures_receiver_temporary,
         futures_receiver_zeroed,
     );
     connect_hide_text_view_errors(&gui_data);
     connect_settings(&gui_data);
+    connect_date_filter(&gui_data);
+    #[cfg(feature = "similar-images")]
+    connect_similar_images(&gui_data);
 
     // Quit the program when X in main window was clicked
     {
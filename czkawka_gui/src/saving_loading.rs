@@ -1,317 +1,348 @@
 use crate::gui_data::*;
+#[cfg(feature = "similar-images")]
+use crate::help_functions::{get_hash_algorithm_from_id, get_id_from_hash_algorithm};
 use crate::help_functions::{get_list_store, ColumnsDirectory};
-use directories_next::ProjectDirs;
+use czkawka_core::common_profile::{Profile, Profiles, DEFAULT_PROFILE_NAME};
+use czkawka_core::duplicate::CheckingMethod;
+#[cfg(feature = "same-music")]
+use czkawka_core::same_music::MusicSimilarity;
+#[cfg(feature = "similar-images")]
+use czkawka_core::similar_images::Similarity;
 use gtk::prelude::*;
 use gtk::{EntryExt, GtkListStoreExt, ToggleButtonExt};
-use std::fs::File;
-use std::io::Write;
-use std::path::Path;
-use std::{env, fs};
+use std::env;
 
-const SAVE_FILE_NAME: &str = "czkawka_gui_config.txt";
-
-pub fn save_configuration(gui_data: &GuiData, manual_execution: bool) {
-    let check_button_settings_save_at_exit = gui_data.check_button_settings_save_at_exit.clone();
-    let text_view_errors = gui_data.text_view_errors.clone();
+fn show_message(gui_data: &GuiData, message: &str) {
+    gui_data.text_view_errors.get_buffer().unwrap().set_text(message);
+}
 
-    if !manual_execution && !check_button_settings_save_at_exit.get_active() {
-        // When check button is deselected, not save configuration at exit
-        return;
+/// Empty name means default profile
+fn get_selected_profile_name(gui_data: &GuiData) -> String {
+    let name = gui_data.combo_box_text_settings_profile.get_active_text().map(|e| e.trim().to_string()).unwrap_or_default();
+    if name.is_empty() {
+        DEFAULT_PROFILE_NAME.to_string()
+    } else {
+        name
     }
-    if let Some(proj_dirs) = ProjectDirs::from("pl", "Qarmin", "Czkawka") {
-        // Lin: /home/alice/.config/barapp
-        // Win: C:\Users\Alice\AppData\Roaming\Foo Corp\Bar App\config
-        // Mac: /Users/Alice/Library/Application Support/com.Foo-Corp.Bar-App
-
-        let config_dir = proj_dirs.config_dir();
-        if config_dir.exists() {
-            if !config_dir.is_dir() {
-                text_view_errors
-                    .get_buffer()
-                    .unwrap()
-                    .set_text(format!("Cannot create save file inside {} because this isn't a folder.", config_dir.display()).as_str());
-                return;
-            }
-        } else if fs::create_dir(config_dir).is_err() {
-            text_view_errors.get_buffer().unwrap().set_text(format!("Failed configuration to create configuration folder {}", config_dir.display()).as_str());
-            return;
-        }
+}
 
-        let mut data_to_save: Vec<String> = Vec::new();
+fn set_selected_profile_name(gui_data: &GuiData, name: &str) {
+    if let Some(entry) = gui_data.combo_box_text_settings_profile.get_child() {
+        entry.downcast::<gtk::Entry>().unwrap().set_text(name);
+    }
+}
 
-        //// Included Directories
-        data_to_save.push("--included_directories:".to_string());
-        let scrolled_window_included_directories = gui_data.scrolled_window_included_directories.clone();
-        let list_store = get_list_store(&scrolled_window_included_directories);
-        if let Some(iter) = list_store.get_iter_first() {
-            loop {
-                data_to_save.push(list_store.get_value(&iter, ColumnsDirectory::Path as i32).get::<String>().unwrap().unwrap());
-                if !list_store.iter_next(&iter) {
-                    break;
-                }
-            }
-        }
+fn fill_profile_selector(gui_data: &GuiData, profiles: &Profiles) {
+    let combo_box_text_settings_profile = gui_data.combo_box_text_settings_profile.clone();
+    combo_box_text_settings_profile.remove_all();
+    for name in profiles.profiles.keys() {
+        combo_box_text_settings_profile.append_text(name);
+    }
+}
 
-        //// Excluded Directories
-        data_to_save.push("--excluded_directories:".to_string());
-        let scrolled_window_excluded_directories = gui_data.scrolled_window_excluded_directories.clone();
-        let list_store = get_list_store(&scrolled_window_excluded_directories);
-        if let Some(iter) = list_store.get_iter_first() {
-            loop {
-                data_to_save.push(list_store.get_value(&iter, ColumnsDirectory::Path as i32).get::<String>().unwrap().unwrap());
-                if !list_store.iter_next(&iter) {
-                    break;
-                }
+fn get_directories(scrolled_window: &gtk::ScrolledWindow) -> Vec<String> {
+    let mut directories = Vec::new();
+    let list_store = get_list_store(scrolled_window);
+    if let Some(iter) = list_store.get_iter_first() {
+        loop {
+            directories.push(list_store.get_value(&iter, ColumnsDirectory::Path as i32).get::<String>().unwrap().unwrap());
+            if !list_store.iter_next(&iter) {
+                break;
             }
         }
+    }
+    directories
+}
 
-        //// Excluded Items
-        data_to_save.push("--excluded_items:".to_string());
-        let entry_excluded_items = gui_data.entry_excluded_items.clone();
-        for item in entry_excluded_items.get_text().split(',') {
-            if item.trim().is_empty() {
-                continue;
-            }
-            data_to_save.push(item.to_string());
-        }
+fn set_directories(scrolled_window: &gtk::ScrolledWindow, directories: &[String]) {
+    let list_store = get_list_store(scrolled_window);
+    list_store.clear();
 
-        //// Allowed extensions
-        data_to_save.push("--allowed_extensions:".to_string());
-        let entry_allowed_extensions = gui_data.entry_allowed_extensions.clone();
-        for extension in entry_allowed_extensions.get_text().split(',') {
-            if extension.trim().is_empty() {
-                continue;
-            }
-            data_to_save.push(extension.to_string());
-        }
+    let col_indices = [ColumnsDirectory::Path as u32];
+    for directory in directories {
+        let values: [&dyn ToValue; 1] = [directory];
+        list_store.set(&list_store.append(), &col_indices, &values);
+    }
+}
 
-        //// Save at exit
-        data_to_save.push("--save_at_exit:".to_string());
-        let check_button_settings_save_at_exit = gui_data.check_button_settings_save_at_exit.clone();
-        data_to_save.push(check_button_settings_save_at_exit.get_active().to_string());
+fn split_entry(entry: &gtk::Entry, separator: char) -> Vec<String> {
+    entry.get_text().split(separator).map(|e| e.trim()).filter(|e| !e.is_empty()).map(|e| e.to_string()).collect()
+}
 
-        //// Load at start
-        data_to_save.push("--load_at_start:".to_string());
-        let check_button_settings_load_at_start = gui_data.check_button_settings_load_at_start.clone();
-        data_to_save.push(check_button_settings_load_at_start.get_active().to_string());
+/// Values which can't be changed in GUI(e.g. set in CLI) are left unchanged
+fn update_profile_from_gui(gui_data: &GuiData, profile: &mut Profile) {
+    profile.included_directories = get_directories(&gui_data.scrolled_window_included_directories);
+    profile.excluded_directories = get_directories(&gui_data.scrolled_window_excluded_directories);
+    profile.excluded_items = split_entry(&gui_data.entry_excluded_items, ',');
+    profile.allowed_extensions = split_entry(&gui_data.entry_allowed_extensions, ',');
+    profile.excluded_extensions = split_entry(&gui_data.entry_excluded_extensions, ',');
+    profile.extension_groups = split_entry(&gui_data.entry_extension_groups, ';');
+
+    profile.content_types.clear();
+    for (check_button, content_type) in [
+        (&gui_data.check_button_content_type_image, "image"),
+        (&gui_data.check_button_content_type_audio, "audio"),
+        (&gui_data.check_button_content_type_video, "video"),
+        (&gui_data.check_button_content_type_archive, "archive"),
+        (&gui_data.check_button_content_type_document, "document"),
+    ]
+    .iter()
+    {
+        if check_button.get_active() {
+            profile.content_types.push(content_type.to_string());
+        }
+    }
+    profile.detect_by_content = gui_data.check_button_detect_by_content.get_active();
+
+    profile.recursive_search = gui_data.check_button_recursive.get_active();
+    profile.max_depth = gui_data.entry_max_depth.get_text().trim().parse().ok();
+    profile.use_ignore_files = gui_data.check_button_use_ignore_files.get_active();
+    profile.modified_after = gui_data.entry_modified_after.get_text().trim().to_string();
+    profile.modified_before = gui_data.entry_modified_before.get_text().trim().to_string();
+
+    profile.low_priority = gui_data.check_button_settings_low_priority.get_active();
+    profile.io_limit = gui_data.entry_settings_io_limit.get_text().trim().parse().ok();
+    profile.io_threads = gui_data.entry_settings_io_threads.get_text().trim().parse().ok();
+    profile.cpu_threads = gui_data.entry_settings_cpu_threads.get_text().trim().parse().ok();
+
+    //// Tools
+    let tools = &mut profile.tools;
+    tools.duplicate_minimal_file_size = gui_data.entry_duplicate_minimal_size.get_text().trim().parse().unwrap_or(tools.duplicate_minimal_file_size);
+    if gui_data.radio_button_duplicates_name.get_active() {
+        tools.duplicate_check_method = CheckingMethod::Name;
+    } else if gui_data.radio_button_duplicates_size.get_active() {
+        tools.duplicate_check_method = CheckingMethod::Size;
+    } else if gui_data.radio_button_duplicates_hashmb.get_active() {
+        tools.duplicate_check_method = CheckingMethod::HashMB;
+    } else if gui_data.radio_button_duplicates_hash.get_active() {
+        tools.duplicate_check_method = CheckingMethod::Hash;
+    }
+    tools.big_files_number = gui_data.entry_big_files_number.get_text().trim().parse().unwrap_or(tools.big_files_number);
+    tools.similar_images_minimal_file_size = gui_data.entry_similar_images_minimal_size.get_text().trim().parse().unwrap_or(tools.similar_images_minimal_file_size);
+    #[cfg(feature = "similar-images")]
+    if gui_data.radio_button_similar_images_very_small.get_active() {
+        tools.similar_images_similarity = Similarity::VerySmall;
+    } else if gui_data.radio_button_similar_images_small.get_active() {
+        tools.similar_images_similarity = Similarity::Small;
+    } else if gui_data.radio_button_similar_images_medium.get_active() {
+        tools.similar_images_similarity = Similarity::Medium;
+    } else if gui_data.radio_button_similar_images_high.get_active() {
+        tools.similar_images_similarity = Similarity::High;
+    } else if gui_data.radio_button_similar_images_very_high.get_active() {
+        tools.similar_images_similarity = Similarity::VeryHigh;
+    }
+    #[cfg(feature = "similar-images")]
+    {
+        if let Some(id) = gui_data.combo_box_text_similar_images_hash_alg.get_active_id() {
+            tools.similar_images_hash_algorithm = get_hash_algorithm_from_id(id.as_str());
+        }
+        if let Some(id) = gui_data.combo_box_text_similar_images_hash_size.get_active_id() {
+            tools.similar_images_hash_size = id.parse().unwrap_or(tools.similar_images_hash_size);
+        }
+        tools.similar_images_use_dct = gui_data.check_button_similar_images_use_dct.get_active();
+        tools.similar_images_max_distance = if gui_data.check_button_similar_images_max_distance.get_active() {
+            Some(gui_data.scale_similar_images_max_distance.get_value() as u64)
+        } else {
+            None
+        };
+        tools.similar_images_use_cache = gui_data.check_button_similar_images_use_cache.get_active();
+        tools.similar_images_check_rotations = gui_data.check_button_similar_images_check_rotations.get_active();
+        tools.similar_images_additional_extensions = split_entry(&gui_data.entry_similar_images_additional_extensions, ',');
+        tools.similar_images_excluded_extensions = split_entry(&gui_data.entry_similar_images_excluded_extensions, ',');
+    }
+    tools.same_music_minimal_file_size = gui_data.entry_same_music_minimal_size.get_text().trim().parse().unwrap_or(tools.same_music_minimal_file_size);
+    tools.same_music_similarity.clear();
+    for (check_button, tag) in [
+        (&gui_data.check_button_music_title, "title"),
+        (&gui_data.check_button_music_artist, "artist"),
+        (&gui_data.check_button_music_album_title, "album_title"),
+        (&gui_data.check_button_music_album_artist, "album_artist"),
+        (&gui_data.check_button_music_year, "year"),
+    ]
+    .iter()
+    {
+        if check_button.get_active() {
+            tools.same_music_similarity.push(tag.to_string());
+        }
+    }
 
-        //// Load at start
-        data_to_save.push("--confirm_deletion:".to_string());
-        let check_button_settings_confirm_deletion = gui_data.check_button_settings_confirm_deletion.clone();
-        data_to_save.push(check_button_settings_confirm_deletion.get_active().to_string());
+    profile.delete_policy.confirm_deletion = gui_data.check_button_settings_confirm_deletion.get_active();
+}
 
-        // Creating/Opening config file
+fn set_gui_from_profile(gui_data: &GuiData, profile: &Profile) {
+    set_directories(&gui_data.scrolled_window_included_directories, &profile.included_directories);
+    set_directories(&gui_data.scrolled_window_excluded_directories, &profile.excluded_directories);
+    gui_data.entry_excluded_items.set_text(profile.excluded_items.join(",").as_str());
+    gui_data.entry_allowed_extensions.set_text(profile.allowed_extensions.join(",").as_str());
+    gui_data.entry_excluded_extensions.set_text(profile.excluded_extensions.join(",").as_str());
+    gui_data.entry_extension_groups.set_text(profile.extension_groups.join(";").as_str());
+
+    let has_content_type = |content_type: &str| profile.content_types.iter().any(|e| e == content_type);
+    gui_data.check_button_content_type_image.set_active(has_content_type("image"));
+    gui_data.check_button_content_type_audio.set_active(has_content_type("audio"));
+    gui_data.check_button_content_type_video.set_active(has_content_type("video"));
+    gui_data.check_button_content_type_archive.set_active(has_content_type("archive"));
+    gui_data.check_button_content_type_document.set_active(has_content_type("document"));
+    gui_data.check_button_detect_by_content.set_active(profile.detect_by_content);
+
+    gui_data.check_button_recursive.set_active(profile.recursive_search);
+    gui_data.entry_max_depth.set_text(profile.max_depth.map(|e| e.to_string()).unwrap_or_default().as_str());
+    gui_data.check_button_use_ignore_files.set_active(profile.use_ignore_files);
+    gui_data.entry_modified_after.set_text(profile.modified_after.as_str());
+    gui_data.entry_modified_before.set_text(profile.modified_before.as_str());
+
+    gui_data.check_button_settings_low_priority.set_active(profile.low_priority);
+    gui_data.entry_settings_io_limit.set_text(profile.io_limit.map(|e| e.to_string()).unwrap_or_default().as_str());
+    gui_data.entry_settings_io_threads.set_text(profile.io_threads.map(|e| e.to_string()).unwrap_or_default().as_str());
+    gui_data.entry_settings_cpu_threads.set_text(profile.cpu_threads.map(|e| e.to_string()).unwrap_or_default().as_str());
+
+    //// Tools
+    let tools = &profile.tools;
+    gui_data.entry_duplicate_minimal_size.set_text(tools.duplicate_minimal_file_size.to_string().as_str());
+    match tools.duplicate_check_method {
+        CheckingMethod::Name => gui_data.radio_button_duplicates_name.set_active(true),
+        CheckingMethod::Size => gui_data.radio_button_duplicates_size.set_active(true),
+        CheckingMethod::HashMB => gui_data.radio_button_duplicates_hashmb.set_active(true),
+        CheckingMethod::Hash => gui_data.radio_button_duplicates_hash.set_active(true),
+        CheckingMethod::None => {}
+    }
+    gui_data.entry_big_files_number.set_text(tools.big_files_number.to_string().as_str());
+    gui_data.entry_similar_images_minimal_size.set_text(tools.similar_images_minimal_file_size.to_string().as_str());
+    #[cfg(feature = "similar-images")]
+    match tools.similar_images_similarity {
+        Similarity::VerySmall => gui_data.radio_button_similar_images_very_small.set_active(true),
+        Similarity::Small => gui_data.radio_button_similar_images_small.set_active(true),
+        Similarity::Medium => gui_data.radio_button_similar_images_medium.set_active(true),
+        Similarity::High => gui_data.radio_button_similar_images_high.set_active(true),
+        Similarity::VeryHigh => gui_data.radio_button_similar_images_very_high.set_active(true),
+        Similarity::None => {}
+    }
+    #[cfg(feature = "similar-images")]
+    {
+        gui_data.combo_box_text_similar_images_hash_alg.set_active_id(Some(get_id_from_hash_algorithm(tools.similar_images_hash_algorithm)));
+        gui_data.combo_box_text_similar_images_hash_size.set_active_id(Some(tools.similar_images_hash_size.to_string().as_str()));
+        gui_data.check_button_similar_images_use_dct.set_active(tools.similar_images_use_dct);
+        // Loading is done also before signals are connected, so sensitivity of slider is set here
+        gui_data.check_button_similar_images_max_distance.set_active(tools.similar_images_max_distance.is_some());
+        gui_data.scale_similar_images_max_distance.set_sensitive(tools.similar_images_max_distance.is_some());
+        if let Some(max_distance) = tools.similar_images_max_distance {
+            gui_data.scale_similar_images_max_distance.set_value(max_distance as f64);
+        }
+        gui_data.check_button_similar_images_use_cache.set_active(tools.similar_images_use_cache);
+        gui_data.check_button_similar_images_check_rotations.set_active(tools.similar_images_check_rotations);
+        gui_data.entry_similar_images_additional_extensions.set_text(tools.similar_images_additional_extensions.join(",").as_str());
+        gui_data.entry_similar_images_excluded_extensions.set_text(tools.similar_images_excluded_extensions.join(",").as_str());
+    }
+    gui_data.entry_same_music_minimal_size.set_text(tools.same_music_minimal_file_size.to_string().as_str());
+    #[cfg(feature = "same-music")]
+    {
+        let music_similarity = tools.get_same_music_similarity();
+        gui_data.check_button_music_title.set_active(music_similarity.contains(MusicSimilarity::TITLE));
+        gui_data.check_button_music_artist.set_active(music_similarity.contains(MusicSimilarity::ARTIST));
+        gui_data.check_button_music_album_title.set_active(music_similarity.contains(MusicSimilarity::ALBUM_TITLE));
+        gui_data.check_button_music_album_artist.set_active(music_similarity.contains(MusicSimilarity::ALBUM_ARTIST));
+        gui_data.check_button_music_year.set_active(music_similarity.contains(MusicSimilarity::YEAR));
+    }
 
-        let config_file = config_dir.join(Path::new(SAVE_FILE_NAME));
+    gui_data.check_button_settings_confirm_deletion.set_active(profile.delete_policy.confirm_deletion);
+}
 
-        let mut config_file_handler = match File::create(&config_file) {
-            Ok(t) => t,
-            Err(_) => {
-                text_view_errors.get_buffer().unwrap().set_text(format!("Failed to create config file {}", config_file.display()).as_str());
-                return;
-            }
-        };
+/// Current configuration is saved to profile with name entered in selector
+pub fn save_configuration(gui_data: &GuiData, manual_execution: bool) {
+    if !manual_execution && !gui_data.check_button_settings_save_at_exit.get_active() {
+        // When check button is deselected, not save configuration at exit
+        return;
+    }
 
-        for data in data_to_save {
-            match writeln!(config_file_handler, "{}", data) {
-                Ok(_) => {
-                    text_view_errors.get_buffer().unwrap().set_text(format!("Saved configuration to file {}", config_file.display()).as_str());
-                }
-                Err(_) => {
-                    text_view_errors.get_buffer().unwrap().set_text(format!("Failed to save configuration data to file {}", config_file.display()).as_str());
-                    return;
-                }
-            }
+    // Profiles which can't be loaded are not overwritten, because user would lose them
+    let mut profiles = match Profiles::load() {
+        Ok(t) => t,
+        Err(e) => {
+            show_message(gui_data, e.as_str());
+            return;
         }
-    } else {
-        text_view_errors.get_buffer().unwrap().set_text("Failed to get home directory, so can't save file.");
+    };
+
+    let name = get_selected_profile_name(gui_data);
+    let mut profile = profiles.get_profile(&name).cloned().unwrap_or_default();
+    update_profile_from_gui(gui_data, &mut profile);
+    profiles.set_profile(&name, profile);
+    profiles.current_profile = name.clone();
+    profiles.save_at_exit = gui_data.check_button_settings_save_at_exit.get_active();
+    profiles.load_at_start = gui_data.check_button_settings_load_at_start.get_active();
+
+    match profiles.save() {
+        Ok(profiles_file) => show_message(gui_data, format!("Saved configuration to profile \"{}\" in file {}", name, profiles_file.display()).as_str()),
+        Err(e) => show_message(gui_data, e.as_str()),
     }
-}
 
-enum TypeOfLoadedData {
-    None,
-    IncludedDirectories,
-    ExcludedDirectories,
-    ExcludedItems,
-    AllowedExtensions,
-    LoadingAtStart,
-    SavingAtExit,
-    ConfirmDeletion,
+    fill_profile_selector(gui_data, &profiles);
+    set_selected_profile_name(gui_data, &name);
 }
 
+/// At start profile used last time is loaded, later the one chosen in selector
 pub fn load_configuration(gui_data: &GuiData, manual_execution: bool) {
-    let text_view_errors = gui_data.text_view_errors.clone();
-    if let Some(proj_dirs) = ProjectDirs::from("pl", "Qarmin", "Czkawka") {
-        // Lin: /home/alice/.config/barapp
-        // Win: C:\Users\Alice\AppData\Roaming\Foo Corp\Bar App\config
-        // Mac: /Users/Alice/Library/Application Support/com.Foo-Corp.Bar-App
-
-        let config_dir = proj_dirs.config_dir();
-        let config_file = config_dir.join(Path::new(SAVE_FILE_NAME));
-        if !config_file.exists() || !config_file.is_file() {
-            if manual_execution {
-                // Don't show errors when there is no configuration file when starting app
-                text_view_errors.get_buffer().unwrap().set_text(format!("Cannot load configuration from file {:?}.", config_file.display()).as_str());
-            }
+    let profiles = match Profiles::load() {
+        Ok(t) => t,
+        Err(e) => {
+            show_message(gui_data, e.as_str());
             return;
         }
-
-        // Loading Data
-        let loaded_data: String = match fs::read_to_string(&config_file) {
-            Ok(t) => t,
-            Err(_) => {
-                text_view_errors.get_buffer().unwrap().set_text(format!("Failed to read data from file {:?}.", config_file).as_str());
-                return;
-            }
-        };
-
-        // Parsing Data
-
-        let mut included_directories: Vec<String> = Vec::new();
-        let mut excluded_directories: Vec<String> = Vec::new();
-        let mut excluded_items: Vec<String> = Vec::new();
-        let mut allowed_extensions: Vec<String> = Vec::new();
-        let mut loading_at_start: bool = true;
-        let mut saving_at_exit: bool = true;
-        let mut confirm_deletion: bool = true;
-
-        let mut current_type = TypeOfLoadedData::None;
-        for (line_number, line) in loaded_data.replace("\r\n", "\n").split('\n').enumerate() {
-            let line: String = line.trim().to_string();
-            if line.is_empty() {
-                continue; // Empty line, so we just skip it
-            }
-            if line.starts_with("--included_directories") {
-                current_type = TypeOfLoadedData::IncludedDirectories;
-            } else if line.starts_with("--excluded_directories") {
-                current_type = TypeOfLoadedData::ExcludedDirectories;
-            } else if line.starts_with("--excluded_items") {
-                current_type = TypeOfLoadedData::ExcludedItems;
-            } else if line.starts_with("--allowed_extensions") {
-                current_type = TypeOfLoadedData::AllowedExtensions;
-            } else if line.starts_with("--load_at_start") {
-                current_type = TypeOfLoadedData::LoadingAtStart;
-            } else if line.starts_with("--save_at_exit") {
-                current_type = TypeOfLoadedData::SavingAtExit;
-            } else if line.starts_with("--confirm_deletion") {
-                current_type = TypeOfLoadedData::ConfirmDeletion;
-            } else if line.starts_with("--") {
-                text_view_errors
-                    .get_buffer()
-                    .unwrap()
-                    .set_text(format!("Found invalid header in line {} \"\"\"{}\"\"\" when loading file {:?}", line_number, line, config_file).as_str());
-            } else {
-                match current_type {
-                    TypeOfLoadedData::None => {
-                        text_view_errors
-                            .get_buffer()
-                            .unwrap()
-                            .set_text(format!("Found orphan data in line {} \"\"\"{}\"\"\" when loading file {:?}", line_number, line, config_file).as_str());
-                        return;
-                    }
-                    TypeOfLoadedData::IncludedDirectories => {
-                        included_directories.push(line);
-                    }
-                    TypeOfLoadedData::ExcludedDirectories => {
-                        excluded_directories.push(line);
-                    }
-                    TypeOfLoadedData::ExcludedItems => {
-                        excluded_items.push(line);
-                    }
-                    TypeOfLoadedData::AllowedExtensions => {
-                        allowed_extensions.push(line);
-                    }
-                    TypeOfLoadedData::LoadingAtStart => {
-                        let line = line.to_lowercase();
-                        if line == "1" || line == "true" {
-                            loading_at_start = true;
-                        } else if line == "0" || line == "false" {
-                            loading_at_start = false;
-                        } else {
-                            text_view_errors
-                                .get_buffer()
-                                .unwrap()
-                                .set_text(format!("Found invalid data in line {} \"\"\"{}\"\"\" isn't proper value(0/1/true/false) when loading file {:?}", line_number, line, config_file).as_str());
-                        }
-                    }
-                    TypeOfLoadedData::SavingAtExit => {
-                        let line = line.to_lowercase();
-                        if line == "1" || line == "true" {
-                            saving_at_exit = true;
-                        } else if line == "0" || line == "false" {
-                            saving_at_exit = false;
-                        } else {
-                            text_view_errors
-                                .get_buffer()
-                                .unwrap()
-                                .set_text(format!("Found invalid data in line {} \"\"\"{}\"\"\" isn't proper value(0/1/true/false) when loading file {:?}", line_number, line, config_file).as_str());
-                        }
-                    }
-                    TypeOfLoadedData::ConfirmDeletion => {
-                        let line = line.to_lowercase();
-                        if line == "1" || line == "true" {
-                            confirm_deletion = true;
-                        } else if line == "0" || line == "false" {
-                            confirm_deletion = false;
-                        } else {
-                            text_view_errors
-                                .get_buffer()
-                                .unwrap()
-                                .set_text(format!("Found invalid data in line {} \"\"\"{}\"\"\" isn't proper value(0/1/true/false) when loading file {:?}", line_number, line, config_file).as_str());
-                        }
-                    }
-                }
-            }
+    };
+
+    if !manual_execution {
+        fill_profile_selector(gui_data, &profiles);
+        set_selected_profile_name(gui_data, &profiles.current_profile);
+        gui_data.check_button_settings_save_at_exit.set_active(profiles.save_at_exit);
+        gui_data.check_button_settings_load_at_start.set_active(profiles.load_at_start);
+        if !profiles.load_at_start {
+            return;
         }
+    }
 
-        // Setting data
-        if manual_execution || loading_at_start {
-            //// Included Directories
-            let scrolled_window_included_directories = gui_data.scrolled_window_included_directories.clone();
-            let list_store = get_list_store(&scrolled_window_included_directories);
-            list_store.clear();
-
-            let col_indices = [0];
-
-            for directory in included_directories {
-                let values: [&dyn ToValue; 1] = [&directory];
-                list_store.set(&list_store.append(), &col_indices, &values);
-            }
-
-            //// Exclude Directories
-            let scrolled_window_excluded_directories = gui_data.scrolled_window_excluded_directories.clone();
-            let list_store = get_list_store(&scrolled_window_excluded_directories);
-            list_store.clear();
-
-            let col_indices = [0];
-
-            for directory in excluded_directories {
-                let values: [&dyn ToValue; 1] = [&directory];
-                list_store.set(&list_store.append(), &col_indices, &values);
+    let name = get_selected_profile_name(gui_data);
+    let profile = match profiles.get_profile(&name) {
+        Ok(t) => t,
+        Err(e) => {
+            if manual_execution {
+                // Don't show errors when there is no saved profile when starting app
+                show_message(gui_data, e.as_str());
             }
+            return;
+        }
+    };
+    set_gui_from_profile(gui_data, profile);
 
-            //// Excluded Items
-            let entry_excluded_items = gui_data.entry_excluded_items.clone();
-            entry_excluded_items.set_text(excluded_items.iter().map(|e| e.to_string() + ",").collect::<String>().as_str());
-
-            //// Allowed extensions
-            let entry_allowed_extensions = gui_data.entry_allowed_extensions.clone();
-            entry_allowed_extensions.set_text(allowed_extensions.iter().map(|e| e.to_string() + ",").collect::<String>().as_str());
+    if manual_execution {
+        show_message(gui_data, format!("Properly loaded profile \"{}\"", name).as_str());
+    }
+}
 
-            //// Buttons
-            gui_data.check_button_settings_load_at_start.set_active(loading_at_start);
-            gui_data.check_button_settings_save_at_exit.set_active(saving_at_exit);
-            gui_data.check_button_settings_confirm_deletion.set_active(confirm_deletion);
-        } else {
-            gui_data.check_button_settings_load_at_start.set_active(false);
+pub fn remove_profile(gui_data: &GuiData) {
+    let mut profiles = match Profiles::load() {
+        Ok(t) => t,
+        Err(e) => {
+            show_message(gui_data, e.as_str());
+            return;
         }
+    };
 
-        if manual_execution {
-            text_view_errors.get_buffer().unwrap().set_text(format!("Properly loaded configuration from file {:?}", config_file).as_str());
-        }
-    } else {
-        text_view_errors.get_buffer().unwrap().set_text("Failed to get home directory, so can't load file.");
+    let name = get_selected_profile_name(gui_data);
+    if !profiles.remove_profile(&name) {
+        show_message(gui_data, format!("Profile \"{}\" doesn't exists", name).as_str());
+        return;
+    }
+    if profiles.current_profile == name {
+        profiles.current_profile = DEFAULT_PROFILE_NAME.to_string();
+    }
+
+    match profiles.save() {
+        Ok(_) => show_message(gui_data, format!("Removed profile \"{}\"", name).as_str()),
+        Err(e) => show_message(gui_data, e.as_str()),
     }
+
+    fill_profile_selector(gui_data, &profiles);
+    set_selected_profile_name(gui_data, &profiles.current_profile);
 }
 
 pub fn reset_configuration(gui_data: &GuiData, manual_clearing: bool) {
@@ -341,6 +372,9 @@ pub fn reset_configuration(gui_data: &GuiData, manual_clearing: bool) {
 
         let values: [&dyn ToValue; 1] = [&current_dir];
         list_store.set(&list_store.append(), &col_indices, &values);
+
+        gui_data.check_button_recursive.set_active(true);
+        gui_data.entry_max_depth.set_text("");
     }
     // Resetting excluded directories
     {
@@ -369,6 +403,22 @@ pub fn reset_configuration(gui_data: &GuiData, manual_clearing: bool) {
     {
         let entry_allowed_extensions = gui_data.entry_allowed_extensions.clone();
         entry_allowed_extensions.set_text("");
+        gui_data.entry_excluded_extensions.set_text("");
+        gui_data.entry_extension_groups.set_text("");
+    }
+    // Resetting content types
+    {
+        gui_data.check_button_content_type_image.set_active(false);
+        gui_data.check_button_content_type_audio.set_active(false);
+        gui_data.check_button_content_type_video.set_active(false);
+        gui_data.check_button_content_type_archive.set_active(false);
+        gui_data.check_button_content_type_document.set_active(false);
+        gui_data.check_button_detect_by_content.set_active(false);
+    }
+    // Resetting modification date
+    {
+        gui_data.entry_modified_after.set_text("");
+        gui_data.entry_modified_before.set_text("");
     }
 
     // Set settings
@@ -376,6 +426,10 @@ pub fn reset_configuration(gui_data: &GuiData, manual_clearing: bool) {
         gui_data.check_button_settings_save_at_exit.set_active(true);
         gui_data.check_button_settings_load_at_start.set_active(true);
         gui_data.check_button_settings_confirm_deletion.set_active(true);
+        gui_data.check_button_settings_low_priority.set_active(false);
+        gui_data.entry_settings_io_limit.set_text("");
+        gui_data.entry_settings_io_threads.set_text("");
+        gui_data.entry_settings_cpu_threads.set_text("");
     }
     if manual_clearing {
         text_view_errors.get_buffer().unwrap().set_text("Current configuration was cleared.");